@@ -1,7 +1,8 @@
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
@@ -11,12 +12,26 @@ use ringbuf::traits::{Consumer, Observer, Producer, Split};
 use ringbuf::HeapRb;
 use tokio::sync::{broadcast, mpsc, oneshot};
 use tracing::{debug, error, info, warn};
+use zeroize::{Zeroize, Zeroizing};
 
 use crate::domain::{
-    AtomicAudioState, AudioBuffer, AudioConfig, AudioDevice, AudioEvent, AudioState, DomainError,
+    AtomicAudioState, AudioBuffer, AudioConfig, AudioDevice, AudioEvent, AudioState,
+    CaptureRateMeasurement, ChannelMode, DomainError, LowPassFilter, StreamInfo, StreamResampler,
 };
 use crate::ports::AudioManager;
 
+/// How long to wait for the audio thread to answer a command.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Rates reported in `AudioDevice::supported_sample_rates`, when a device's
+/// supported range covers them.
+const STANDARD_SAMPLE_RATES: &[u32] = &[
+    8_000, 11_025, 16_000, 22_050, 32_000, 44_100, 48_000, 88_200, 96_000, 176_400, 192_000,
+];
+
+/// Minimum time between two `BufferFull` events.
+const BUFFER_FULL_EVENT_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Lock-free ring buffer for audio samples.
 type RingProducer = ringbuf::HeapProd<i16>;
 type RingConsumer = ringbuf::HeapCons<i16>;
@@ -29,29 +44,225 @@ enum AudioCommand {
     Stop {
         reply: oneshot::Sender<Result<Vec<i16>, DomainError>>,
     },
+    /// Close a warm stream that failed or has been idle for
+    /// `warm_idle_timeout_secs`.
+    ReleaseWarm,
     Shutdown,
 }
 
+/// Flags shared between the audio thread and a stream's callbacks.
+///
+/// Atomics only: the realtime callback must never wait on a lock.
+#[derive(Default)]
+struct StreamFlags {
+    /// Samples are kept only while set; a warm stream between dictations drops them.
+    capturing: AtomicBool,
+    /// Set by the error callback; a failed warm stream is rebuilt on the next start.
+    failed: AtomicBool,
+    /// Samples after which capture stops (`max_recording_secs`), set on each start.
+    max_samples: AtomicUsize,
+    /// Silence auto-stop threshold (f32 bits) and timeout, set on each start.
+    silence_threshold_dbfs: AtomicU32,
+    silence_timeout_ms: AtomicU32,
+    /// Counts recordings armed on the stream, so the callback sees each start
+    /// even if it wasn't called in between.
+    recordings: AtomicU64,
+    /// Pre-roll window of the stream, fixed when it is built.
+    preroll_samples: usize,
+}
+
+impl StreamFlags {
+    fn with_preroll(preroll_samples: usize) -> Self {
+        Self {
+            preroll_samples,
+            ..Default::default()
+        }
+    }
+
+    /// Set the per-recording limits from `config` before capture starts.
+    fn arm(&self, config: &AudioConfig) {
+        self.max_samples.store(config.max_recording_samples(), Ordering::SeqCst);
+        self.silence_threshold_dbfs
+            .store(config.silence_threshold_dbfs.to_bits(), Ordering::SeqCst);
+        self.silence_timeout_ms.store(config.silence_timeout_ms, Ordering::SeqCst);
+        self.recordings.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Whether the callback has anything to do with incoming audio.
+    fn wants_samples(&self) -> bool {
+        self.capturing.load(Ordering::Relaxed) || self.preroll_samples > 0
+    }
+}
+
+/// Per-recording state owned by a stream's callback.
+struct CaptureSession {
+    /// Recording the state belongs to (see `StreamFlags::recordings`).
+    recording: u64,
+    /// Audio kept while not capturing, for the start of the next recording.
+    preroll: PreRoll,
+    /// Silence auto-stop for the current recording, reset on each start.
+    silence: audio_processing::SilenceDetector,
+}
+
+impl CaptureSession {
+    fn new(preroll_samples: usize) -> Self {
+        Self {
+            recording: 0,
+            preroll: PreRoll::new(preroll_samples),
+            silence: audio_processing::SilenceDetector::default(),
+        }
+    }
+
+    /// Reset for a recording starting with `flags` armed. Returns the
+    /// pre-roll it opens with.
+    fn start(&mut self, flags: &StreamFlags, sample_rate: u32) -> Zeroizing<Vec<i16>> {
+        self.recording = flags.recordings.load(Ordering::SeqCst);
+        self.silence = audio_processing::SilenceDetector::new(
+            f32::from_bits(flags.silence_threshold_dbfs.load(Ordering::SeqCst)),
+            flags.silence_timeout_ms.load(Ordering::SeqCst),
+        );
+        let max_age = self.preroll.window(sample_rate);
+        self.preroll.take(Instant::now(), max_age)
+    }
+}
+
+/// Rolling window of the most recent audio on an idle warm stream.
+///
+/// Keeping a warm stream open is what makes pre-roll possible at all: a
+/// stream opened on the shortcut can't hear what came before it. The window
+/// is overwritten in place as audio arrives, so nothing older than it stays
+/// in memory while the device delivers; `take` also discards a window that
+/// went stale, and the storage is zeroized when the stream closes.
+#[derive(Default)]
+struct PreRoll {
+    samples: Zeroizing<Vec<i16>>,
+    capacity: usize,
+    /// Oldest sample once the window is full.
+    next: usize,
+    last_write: Option<Instant>,
+}
+
+impl PreRoll {
+    fn new(capacity: usize) -> Self {
+        Self {
+            samples: Zeroizing::new(Vec::with_capacity(capacity)),
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Window length at `sample_rate`.
+    fn window(&self, sample_rate: u32) -> Duration {
+        Duration::from_secs_f64(self.capacity as f64 / sample_rate.max(1) as f64)
+    }
+
+    fn push(&mut self, samples: &[i16], now: Instant) {
+        if !self.is_enabled() {
+            return;
+        }
+        for &sample in &samples[samples.len().saturating_sub(self.capacity)..] {
+            if self.samples.len() < self.capacity {
+                self.samples.push(sample);
+            } else {
+                self.samples[self.next] = sample;
+                self.next = (self.next + 1) % self.capacity;
+            }
+        }
+        self.last_write = Some(now);
+    }
+
+    /// Take the window in chronological order, or nothing if the last audio
+    /// arrived more than `max_age` ago. Leaves the pre-roll empty and zeroized.
+    fn take(&mut self, now: Instant, max_age: Duration) -> Zeroizing<Vec<i16>> {
+        let fresh = self.last_write.is_some_and(|last| now.duration_since(last) <= max_age);
+        let mut taken = Zeroizing::new(Vec::new());
+        if fresh {
+            taken.reserve_exact(self.samples.len());
+            taken.extend_from_slice(&self.samples[self.next..]);
+            taken.extend_from_slice(&self.samples[..self.next]);
+        }
+        self.samples.zeroize();
+        self.next = 0;
+        self.last_write = None;
+        taken
+    }
+}
+
+/// Device and settings a stream was built with, to tell whether a warm
+/// stream can be reused for the next recording.
+#[derive(Debug, Clone, PartialEq)]
+struct StreamKey {
+    device_id: Option<String>,
+    sample_rate: u32,
+    gain_db: f32,
+    channel_mode: ChannelMode,
+    capacity: usize,
+    preroll_samples: usize,
+}
+
+/// The open input stream, recording or kept warm between dictations.
+struct OpenStream {
+    // Dropping the stream closes the device
+    _stream: Stream,
+    consumer: RingConsumer,
+    flags: Arc<StreamFlags>,
+    key: StreamKey,
+    info: StreamInfo,
+    /// When the stream was last stopped, if it is warm.
+    idle_since: Option<Instant>,
+}
+
+/// Whether a warm stream built with `warm` can serve a recording that needs
+/// `wanted`: the device is kept warm, nothing changed and the stream didn't fail.
+fn warm_stream_reusable(
+    warm: &StreamKey,
+    wanted: &StreamKey,
+    keep_warm: bool,
+    failed: bool,
+) -> bool {
+    keep_warm && !failed && warm == wanted
+}
+
+/// Whether an idle stream should be closed: it failed, or has been idle for
+/// `timeout`. A recording stream (`idle_since` unset) is never released.
+fn warm_stream_expired(
+    idle_since: Option<Instant>,
+    failed: bool,
+    timeout: Duration,
+    now: Instant,
+) -> bool {
+    idle_since.is_some_and(|since| failed || now.duration_since(since) >= timeout)
+}
+
 /// Audio processing utilities.
 mod audio_processing {
     use super::*;
 
-    pub fn get_device(selected_device_id: Option<&str>) -> Result<Device, DomainError> {
+    /// Find the selected input device, falling back to the default unless `lock_device` is set.
+    pub fn get_device(
+        selected_device_id: Option<&str>,
+        lock_device: bool,
+    ) -> Result<Device, DomainError> {
         let host = cpal::default_host();
 
         if let Some(id) = selected_device_id {
-            let devices = host.input_devices().map_err(|e| DomainError::AudioDevice {
-                message: format!("Failed to enumerate devices: {}", e),
-            })?;
-
-            for device in devices {
-                if let Ok(name) = device.name() {
-                    if name == id {
-                        return Ok(device);
-                    }
-                }
+            let devices: Vec<Device> = host
+                .input_devices()
+                .map_err(|e| DomainError::AudioDevice {
+                    message: format!("Failed to enumerate devices: {}", e),
+                })?
+                .collect();
+
+            // IDs must be derived exactly as in `list_devices_internal`
+            let names: Vec<Option<String>> = devices.iter().map(|d| d.name().ok()).collect();
+            let position = find_device(&label_devices(&names), id, lock_device)?;
+            if let Some(device) = position.and_then(|i| devices.into_iter().nth(i)) {
+                return Ok(device);
             }
-            warn!(device_id = %id, "Selected device not found, falling back to default");
         }
 
         host.default_input_device()
@@ -60,6 +271,89 @@ mod audio_processing {
             })
     }
 
+    /// Position of device `id` among `labels` (see `label_devices`), or
+    /// `None` to fall back to the default device, which `lock_device` forbids.
+    pub fn find_device(
+        labels: &[(String, String)],
+        id: &str,
+        lock_device: bool,
+    ) -> Result<Option<usize>, DomainError> {
+        if let Some(position) = labels.iter().position(|(device_id, _)| device_id == id) {
+            return Ok(Some(position));
+        }
+        if lock_device {
+            return Err(DomainError::AudioDevice {
+                message: format!("Locked input device not found: {}", id),
+            });
+        }
+        warn!(device_id = %id, "Selected device not found, falling back to default");
+        Ok(None)
+    }
+
+    /// Assign a unique ID and display name to each enumerated device.
+    ///
+    /// IDs are the sanitized name, with `:N` appended for duplicates. `:` in
+    /// names is replaced so the suffix stays unambiguous, and devices whose
+    /// name is missing or blank get an index-based ID.
+    pub fn label_devices(names: &[Option<String>]) -> Vec<(String, String)> {
+        let mut name_counts: HashMap<String, usize> = HashMap::new();
+
+        names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let name: String = name
+                    .as_deref()
+                    .unwrap_or_default()
+                    .chars()
+                    .filter(|c| !c.is_control())
+                    .collect();
+                let name = name.trim();
+                if name.is_empty() {
+                    return (format!("#{}", index), format!("Input device {}", index + 1));
+                }
+
+                let base = name.replace(':', "_");
+                let count = name_counts.entry(base.clone()).or_insert(0);
+                let id = if *count == 0 {
+                    base
+                } else {
+                    format!("{}:{}", base, count)
+                };
+                *count += 1;
+                (id, name.to_string())
+            })
+            .collect()
+    }
+
+    /// Standard sample rates and the channel count a device supports for
+    /// input. Empty and 0 when its configs can't be enumerated, so one
+    /// misbehaving device doesn't fail the whole device list.
+    pub fn device_capabilities(device: &Device) -> (Vec<u32>, u16) {
+        let configs = match device.supported_input_configs() {
+            Ok(configs) => configs.collect::<Vec<_>>(),
+            Err(e) => {
+                debug!(error = %e, "Failed to query supported input configs");
+                return (Vec::new(), 0);
+            }
+        };
+        let ranges: Vec<(u32, u32)> = configs
+            .iter()
+            .map(|c| (c.min_sample_rate().0, c.max_sample_rate().0))
+            .collect();
+        let max_channels = configs.iter().map(|c| c.channels()).max().unwrap_or(0);
+        (standard_rates_in(&ranges), max_channels)
+    }
+
+    /// The `STANDARD_SAMPLE_RATES` covered by any of the inclusive `ranges`.
+    pub fn standard_rates_in(ranges: &[(u32, u32)]) -> Vec<u32> {
+        STANDARD_SAMPLE_RATES
+            .iter()
+            .copied()
+            .filter(|rate| ranges.iter().any(|(min, max)| (min..=max).contains(&rate)))
+            .collect()
+    }
+
     pub fn build_stream_config(device: &Device) -> Result<StreamConfig, DomainError> {
         let supported = device.default_input_config().map_err(|e| DomainError::AudioDevice {
             message: format!("Failed to get default config: {}", e),
@@ -79,53 +373,156 @@ mod audio_processing {
         })
     }
 
+    /// Count the frames `device` delivers over `duration` on a throwaway stream.
+    ///
+    /// The window runs from the first callback to the last, so stream startup
+    /// latency doesn't skew the result. Blocks the calling thread.
+    pub fn measure_capture_rate(
+        device: &Device,
+        duration: Duration,
+    ) -> Result<CaptureRateMeasurement, DomainError> {
+        let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+        let supported = device.default_input_config().map_err(|e| DomainError::AudioDevice {
+            message: format!("Failed to get default config: {}", e),
+        })?;
+        let config = build_stream_config(device)?;
+        let channels = config.channels.max(1) as u64;
+
+        // (first callback, last callback, frames after the first callback)
+        let window: Arc<Mutex<(Option<Instant>, Instant, u64)>> =
+            Arc::new(Mutex::new((None, Instant::now(), 0)));
+        let callback_window = Arc::clone(&window);
+
+        let stream = device
+            .build_input_stream_raw(
+                &config,
+                supported.sample_format(),
+                move |data: &cpal::Data, _: &cpal::InputCallbackInfo| {
+                    let now = Instant::now();
+                    let mut window = callback_window.lock();
+                    if window.0.is_none() {
+                        window.0 = Some(now);
+                    } else {
+                        window.2 += data.len() as u64 / channels;
+                    }
+                    window.1 = now;
+                },
+                |err| error!(?err, "Capture rate stream error"),
+                None,
+            )
+            .map_err(|e| DomainError::AudioDevice {
+                message: format!("Failed to build stream: {}", e),
+            })?;
+        stream.play().map_err(|e| DomainError::AudioDevice {
+            message: format!("Failed to start stream: {}", e),
+        })?;
+        thread::sleep(duration);
+        drop(stream);
+
+        let (first, last, frames) = *window.lock();
+        let elapsed = first
+            .map(|first| last.duration_since(first).as_secs_f64())
+            .filter(|secs| *secs > 0.0)
+            .ok_or_else(|| DomainError::AudioDevice {
+                message: format!("No audio received from {}", device_name),
+            })?;
+
+        let measurement =
+            CaptureRateMeasurement::new(device_name, config.sample_rate.0, frames, elapsed);
+        info!(
+            device = %measurement.device_name,
+            reported_rate = measurement.reported_rate,
+            measured_rate = measurement.measured_rate,
+            drift_ppm = measurement.drift_ppm,
+            "Capture rate measured"
+        );
+        Ok(measurement)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn build_stream(
         device: &Device,
+        device_name: String,
         config: &StreamConfig,
         sample_format: SampleFormat,
         target_sample_rate: u32,
+        gain: f32,
+        channel_mode: ChannelMode,
         mut producer: RingProducer,
+        flags: Arc<StreamFlags>,
         state: Arc<AtomicAudioState>,
         event_sender: broadcast::Sender<AudioEvent>,
         current_level: Arc<AtomicU32>,
+        current_peak: Arc<AtomicU32>,
+        release_tx: mpsc::WeakSender<AudioCommand>,
     ) -> Result<Stream, DomainError> {
         let channels = config.channels as usize;
         let device_sample_rate = config.sample_rate.0;
+        if channels > 1 && !channel_mode.fits(channels) {
+            warn!(?channel_mode, channels, "Selected channel not on device, using channel 0");
+        }
 
         // Calculate samples_per_update based on TARGET rate since we count resampled samples
         let samples_per_update = (target_sample_rate / 10) as usize;
         let mut sample_counter = 0usize;
         let mut level_samples = Vec::with_capacity(samples_per_update);
+        // Stateful, as audio arrives in chunks
+        let mut anti_alias = (device_sample_rate > target_sample_rate)
+            .then(|| LowPassFilter::anti_aliasing(device_sample_rate, target_sample_rate));
+        let mut resampler = (device_sample_rate != target_sample_rate)
+            .then(|| StreamResampler::new(device_sample_rate, target_sample_rate));
+        let mut overflow = OverflowThrottle::default();
+        let mut session = CaptureSession::new(flags.preroll_samples);
 
         let state_err = Arc::clone(&state);
         let event_sender_err = event_sender.clone();
+        let flags_err = Arc::clone(&flags);
+        let on_error = move |err: cpal::StreamError| {
+            error!(?err, device = %device_name, "Audio stream error");
+            flags_err.failed.store(true, Ordering::SeqCst);
+            handle_stream_error(&state_err, &event_sender_err, &device_name);
+            // Close the stream if it is only kept warm
+            if let Some(cmd_tx) = release_tx.upgrade() {
+                let _ = cmd_tx.try_send(AudioCommand::ReleaseWarm);
+            }
+        };
 
         let stream = match sample_format {
             SampleFormat::I16 => device.build_input_stream(
                 config,
                 move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                    if !flags.wants_samples() {
+                        return;
+                    }
                     process_samples_i16(
                         data,
                         channels,
-                        device_sample_rate,
+                        channel_mode,
                         target_sample_rate,
+                        gain,
                         &mut producer,
+                        &flags,
+                        &mut session,
+                        &mut anti_alias,
+                        &mut resampler,
+                        &mut overflow,
                         &mut level_samples,
                         &mut sample_counter,
                         samples_per_update,
                         &event_sender,
                         &current_level,
+                        &current_peak,
                     );
                 },
-                move |err| {
-                    error!(?err, "Audio stream error");
-                    handle_stream_error(&state_err, &event_sender_err);
-                },
+                on_error,
                 None,
             ),
             SampleFormat::F32 => device.build_input_stream(
                 config,
                 move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                    if !flags.wants_samples() {
+                        return;
+                    }
                     let i16_data: Vec<i16> = data
                         .iter()
                         .map(|&s| (s.clamp(-1.0, 1.0) * 32767.0) as i16)
@@ -134,20 +531,24 @@ mod audio_processing {
                     process_samples_i16(
                         &i16_data,
                         channels,
-                        device_sample_rate,
+                        channel_mode,
                         target_sample_rate,
+                        gain,
                         &mut producer,
+                        &flags,
+                        &mut session,
+                        &mut anti_alias,
+                        &mut resampler,
+                        &mut overflow,
                         &mut level_samples,
                         &mut sample_counter,
                         samples_per_update,
                         &event_sender,
                         &current_level,
+                        &current_peak,
                     );
                 },
-                move |err| {
-                    error!(?err, "Audio stream error");
-                    handle_stream_error(&state_err, &event_sender_err);
-                },
+                on_error,
                 None,
             ),
             _ => {
@@ -164,39 +565,85 @@ mod audio_processing {
     }
 
     #[allow(clippy::too_many_arguments)]
-    fn process_samples_i16(
+    pub fn process_samples_i16(
         data: &[i16],
         channels: usize,
-        device_sample_rate: u32,
+        channel_mode: ChannelMode,
         target_sample_rate: u32,
+        gain: f32,
         producer: &mut RingProducer,
+        flags: &StreamFlags,
+        session: &mut CaptureSession,
+        anti_alias: &mut Option<LowPassFilter>,
+        resampler: &mut Option<StreamResampler>,
+        overflow: &mut OverflowThrottle,
         level_samples: &mut Vec<i16>,
         sample_counter: &mut usize,
         samples_per_update: usize,
         event_sender: &broadcast::Sender<AudioEvent>,
         current_level: &AtomicU32,
+        current_peak: &AtomicU32,
     ) {
-        // Convert stereo to mono
-        let mono_samples: Vec<i16> = if channels > 1 {
-            data.chunks(channels)
-                .map(|chunk| {
-                    let sum: i32 = chunk.iter().map(|&s| s as i32).sum();
-                    (sum / channels as i32) as i16
-                })
-                .collect()
-        } else {
-            data.to_vec()
-        };
+        let capturing = flags.capturing.load(Ordering::SeqCst);
+        // A recording opens with the pre-roll and starts its own level windows
+        let started = capturing && session.recording != flags.recordings.load(Ordering::SeqCst);
+        let preroll = started.then(|| {
+            let preroll = session.start(flags, target_sample_rate);
+            if preroll.is_empty() {
+                // The chain sat idle since the last recording: drop its tail
+                if let Some(filter) = anti_alias.as_mut() {
+                    filter.reset();
+                }
+                if let Some(resampler) = resampler.as_mut() {
+                    resampler.reset();
+                }
+            }
+            level_samples.clear();
+            *sample_counter = 0;
+            preroll
+        });
+
+        let mut mono_samples = downmix(data, channels, channel_mode);
+
+        apply_gain(&mut mono_samples, gain);
+
+        // Remove what would fold back below the target Nyquist
+        if let Some(filter) = anti_alias {
+            filter.process(&mut mono_samples);
+        }
 
         // Resample if needed
-        let resampled = if device_sample_rate != target_sample_rate {
-            resample(&mono_samples, device_sample_rate, target_sample_rate)
-        } else {
-            mono_samples
+        let mut resampled = match resampler {
+            Some(resampler) => resampler.process(&mono_samples),
+            None => mono_samples,
         };
 
-        // Write to ring buffer
-        let _ = producer.push_slice(&resampled);
+        // Between recordings only the pre-roll is kept
+        if !capturing {
+            session.preroll.push(&resampled, Instant::now());
+            resampled.zeroize();
+            return;
+        }
+        if let Some(preroll) = preroll {
+            resampled.splice(0..0, preroll.iter().copied());
+        }
+
+        // Stop at the duration limit, before the buffer can overflow
+        let room = flags
+            .max_samples
+            .load(Ordering::Relaxed)
+            .saturating_sub(producer.occupied_len());
+        if resampled.len() >= room {
+            resampled.truncate(room);
+            flags.capturing.store(false, Ordering::SeqCst);
+            let _ = event_sender.send(AudioEvent::MaxDurationReached);
+        }
+
+        // Write to ring buffer; once full, the tail of the recording is lost
+        let pushed = producer.push_slice(&resampled);
+        if let Some(dropped_samples) = overflow.record(resampled.len() - pushed, Instant::now()) {
+            let _ = event_sender.send(AudioEvent::BufferFull { dropped_samples });
+        }
 
         // Update level periodically
         level_samples.extend_from_slice(&resampled);
@@ -204,73 +651,339 @@ mod audio_processing {
 
         if *sample_counter >= samples_per_update {
             let level = calculate_rms(level_samples);
+            let peak = calculate_peak(level_samples);
+            let level_db = calculate_rms_dbfs(level_samples);
             current_level.store(level.to_bits(), Ordering::Relaxed);
-            let _ = event_sender.send(AudioEvent::LevelUpdate { level });
+            current_peak.store(peak.to_bits(), Ordering::Relaxed);
+            let _ = event_sender.send(AudioEvent::LevelUpdate {
+                level,
+                peak,
+                level_db,
+            });
+
+            let window_ms = (level_samples.len() as u64 * 1000 / target_sample_rate as u64) as u32;
+            if let Some(silence_ms) = session.silence.update(level_db, window_ms) {
+                flags.capturing.store(false, Ordering::SeqCst);
+                let _ = event_sender.send(AudioEvent::SilenceTimeout { silence_ms });
+            }
             level_samples.clear();
             *sample_counter = 0;
         }
     }
 
-    pub fn calculate_rms(samples: &[i16]) -> f32 {
-        if samples.is_empty() {
-            return 0.0;
+    /// Reduce interleaved multi-channel frames to mono, averaging or keeping
+    /// a single channel according to `mode`.
+    pub fn downmix(data: &[i16], channels: usize, mode: ChannelMode) -> Vec<i16> {
+        if channels <= 1 {
+            return data.to_vec();
+        }
+        match mode.channel_index(channels) {
+            Some(index) => data.chunks_exact(channels).map(|frame| frame[index]).collect(),
+            None => data
+                .chunks(channels)
+                .map(|frame| {
+                    let sum: i32 = frame.iter().map(|&s| s as i32).sum();
+                    (sum / channels as i32) as i16
+                })
+                .collect(),
         }
-        let sum_squares: f64 = samples.iter().map(|&s| (s as f64).powi(2)).sum();
-        let rms = (sum_squares / samples.len() as f64).sqrt();
-        (rms / 32767.0).min(1.0) as f32
     }
 
-    pub fn resample(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
-        if from_rate == to_rate || samples.is_empty() {
-            return samples.to_vec();
+    pub use crate::domain::{calculate_peak, calculate_rms, calculate_rms_dbfs, resample};
+
+    /// Detects the end of speech: `timeout_ms` of audio below
+    /// `threshold_dbfs`, counted only once something above it was heard.
+    #[derive(Debug, Default)]
+    pub struct SilenceDetector {
+        threshold_dbfs: f32,
+        /// 0 disables detection.
+        timeout_ms: u32,
+        heard_speech: bool,
+        silent_ms: u32,
+    }
+
+    impl SilenceDetector {
+        pub fn new(threshold_dbfs: f32, timeout_ms: u32) -> Self {
+            Self {
+                threshold_dbfs,
+                timeout_ms,
+                ..Default::default()
+            }
         }
 
-        let ratio = from_rate as f64 / to_rate as f64;
-        let output_len = (samples.len() as f64 / ratio).ceil() as usize;
-        let mut output = Vec::with_capacity(output_len);
+        /// Feed the level of the next `window_ms` of audio. Returns the
+        /// silence length once it reaches the timeout, then starts over.
+        pub fn update(&mut self, level_db: f32, window_ms: u32) -> Option<u32> {
+            if self.timeout_ms == 0 {
+                return None;
+            }
+            if level_db >= self.threshold_dbfs {
+                self.heard_speech = true;
+                self.silent_ms = 0;
+                return None;
+            }
+            if !self.heard_speech {
+                return None;
+            }
+            self.silent_ms += window_ms;
+            if self.silent_ms < self.timeout_ms {
+                return None;
+            }
+            let silence_ms = self.silent_ms;
+            *self = Self::new(self.threshold_dbfs, self.timeout_ms);
+            Some(silence_ms)
+        }
+    }
 
-        for i in 0..output_len {
-            let src_pos = i as f64 * ratio;
-            let src_idx = src_pos.floor() as usize;
-            let frac = src_pos.fract();
+    /// Counts samples dropped by a full ring buffer, reporting them at most
+    /// once per `BUFFER_FULL_EVENT_INTERVAL`.
+    #[derive(Debug, Default)]
+    pub struct OverflowThrottle {
+        dropped: usize,
+        last_reported: Option<Instant>,
+    }
 
-            let sample = if src_idx + 1 < samples.len() {
-                let s0 = samples[src_idx] as f64;
-                let s1 = samples[src_idx + 1] as f64;
-                (s0 + (s1 - s0) * frac) as i16
-            } else if src_idx < samples.len() {
-                samples[src_idx]
-            } else {
-                0
-            };
-            output.push(sample);
+    impl OverflowThrottle {
+        /// Record `dropped` samples at `now`. Returns the total dropped since
+        /// the last report when a new one is due.
+        pub fn record(&mut self, dropped: usize, now: Instant) -> Option<usize> {
+            self.dropped += dropped;
+            if self.dropped == 0
+                || self
+                    .last_reported
+                    .is_some_and(|last| now.duration_since(last) < BUFFER_FULL_EVENT_INTERVAL)
+            {
+                return None;
+            }
+            self.last_reported = Some(now);
+            Some(std::mem::take(&mut self.dropped))
+        }
+    }
+
+    /// Convert a gain in dB to a linear amplitude factor.
+    pub fn db_to_linear(gain_db: f32) -> f32 {
+        10f32.powf(gain_db / 20.0)
+    }
+
+    /// Scale samples by a linear gain, saturating at the i16 range.
+    pub fn apply_gain(samples: &mut [i16], gain: f32) {
+        if gain == 1.0 {
+            return;
+        }
+        for sample in samples.iter_mut() {
+            *sample = (*sample as f32 * gain).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
         }
-        output
     }
 
-    fn handle_stream_error(state: &AtomicAudioState, event_sender: &broadcast::Sender<AudioEvent>) {
-        let current = state.load();
-        if current == AudioState::Recording {
-            state.store(AudioState::DeviceLost);
+    fn handle_stream_error(
+        state: &AtomicAudioState,
+        event_sender: &broadcast::Sender<AudioEvent>,
+        device_name: &str,
+    ) {
+        // Only a running capture can lose its device
+        if state.transition(AudioState::DeviceLost).is_ok() {
             let _ = event_sender.send(AudioEvent::DeviceLost {
-                device_name: "Unknown".to_string(),
+                device_name: device_name.to_string(),
             });
         }
     }
 }
 
-/// Audio thread runner - creates Stream on the audio thread.
-fn audio_thread_main(
-    config: AudioConfig,
-    selected_device_id: Arc<RwLock<Option<String>>>,
+/// Device recovery with exponential backoff.
+mod recovery {
+    use super::*;
+    use std::time::Duration;
+
+    /// Source of recovery delays, injectable so backoff can be tested without waiting.
+    #[async_trait]
+    pub trait Sleeper: Send + Sync {
+        async fn sleep(&self, duration: Duration);
+    }
+
+    /// Sleeper backed by the tokio timer.
+    pub struct TokioSleeper;
+
+    /// Request to stop a running recovery early, waking it from its backoff sleep.
+    #[derive(Default)]
+    pub struct RecoveryCancel {
+        requested: AtomicBool,
+        notify: tokio::sync::Notify,
+    }
+
+    impl RecoveryCancel {
+        pub fn cancel(&self) {
+            self.requested.store(true, Ordering::SeqCst);
+            self.notify.notify_waiters();
+        }
+
+        fn is_requested(&self) -> bool {
+            self.requested.load(Ordering::SeqCst)
+        }
+
+        /// Sleep for `duration`, returning early if cancelled meanwhile.
+        async fn sleep(&self, sleeper: &dyn Sleeper, duration: Duration) {
+            if self.is_requested() {
+                return;
+            }
+            tokio::select! {
+                _ = sleeper.sleep(duration) => {}
+                _ = self.notify.notified() => {}
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Sleeper for TokioSleeper {
+        async fn sleep(&self, duration: Duration) {
+            tokio::time::sleep(duration).await;
+        }
+    }
+
+    /// Recover from `DeviceLost`/`Error` by probing for a usable device: an
+    /// optional grace period, then up to `max_recovery_attempts` attempts,
+    /// each preceded by an exponential backoff delay.
+    ///
+    /// `probe` returns the recovered device name. Always ends in `Idle` or
+    /// `Error`, never `Recording`.
+    ///
+    /// If `cancel` fires, the remaining backoff is skipped: one last probe
+    /// decides between `Idle` (device available) and `Error`.
+    pub async fn run(
+        config: &AudioConfig,
+        sleeper: &dyn Sleeper,
+        cancel: &RecoveryCancel,
+        state: &AtomicAudioState,
+        event_sender: &broadcast::Sender<AudioEvent>,
+        mut probe: impl FnMut() -> Result<String, DomainError> + Send,
+    ) -> Result<(), DomainError> {
+        // Clear stale requests first, so a cancel arriving as soon as the
+        // state reads Recovering isn't lost
+        cancel.requested.store(false, Ordering::SeqCst);
+        let current = state.transition(AudioState::Recovering)?;
+        let _ = event_sender.send(AudioEvent::StateChanged {
+            from: current,
+            to: AudioState::Recovering,
+        });
+
+        // Grace period for devices that reappear on their own (e.g. Bluetooth)
+        if config.recovery_initial_delay_ms > 0 {
+            debug!(delay_ms = config.recovery_initial_delay_ms, "Waiting before recovery");
+            cancel
+                .sleep(sleeper, Duration::from_millis(config.recovery_initial_delay_ms))
+                .await;
+        }
+
+        let max_attempts = config.max_recovery_attempts;
+        let mut last_error = "Failed to recover audio device".to_string();
+        for attempt in 1..=max_attempts {
+            if !cancel.is_requested() {
+                let delay_ms = config.recovery_backoff_ms(attempt); // 500ms, 1s, 2s by default
+                cancel.sleep(sleeper, Duration::from_millis(delay_ms)).await;
+                info!(attempt, max_attempts, delay_ms, "Recovery attempt");
+            }
+            let cancelled = cancel.is_requested();
+
+            match probe() {
+                Ok(device_name) => {
+                    state.transition(AudioState::Idle)?;
+                    let _ = event_sender.send(AudioEvent::RecoverySuccess {
+                        device_name: device_name.clone(),
+                    });
+                    let _ = event_sender.send(AudioEvent::StateChanged {
+                        from: AudioState::Recovering,
+                        to: AudioState::Idle,
+                    });
+                    info!(device = %device_name, "Audio recovered successfully");
+                    return Ok(());
+                }
+                Err(e) if cancelled => {
+                    info!(error = %e, "Recovery cancelled with no device available");
+                    state.transition(AudioState::Error)?;
+                    let _ = event_sender.send(AudioEvent::StateChanged {
+                        from: AudioState::Recovering,
+                        to: AudioState::Error,
+                    });
+                    return Err(DomainError::AudioDevice {
+                        message: format!("Recovery cancelled: {}", e),
+                    });
+                }
+                Err(e) => {
+                    warn!(attempt, error = %e, "Recovery attempt failed");
+                    last_error = e.to_string();
+                }
+            }
+        }
+
+        // All attempts failed
+        state.transition(AudioState::Error)?;
+        let _ = event_sender.send(AudioEvent::RecoveryFailed {
+            attempts: max_attempts,
+            last_error,
+        });
+        let _ = event_sender.send(AudioEvent::StateChanged {
+            from: AudioState::Recovering,
+            to: AudioState::Error,
+        });
+
+        Err(DomainError::AudioDevice {
+            message: format!("Recovery failed after {} attempts", max_attempts),
+        })
+    }
+
+    /// Call `recover` on every `DeviceLost` event for which `enabled` holds,
+    /// until the event channel closes.
+    pub async fn watch<F>(
+        mut events: broadcast::Receiver<AudioEvent>,
+        enabled: impl Fn() -> bool + Send,
+        mut recover: impl FnMut() -> F + Send,
+    ) where
+        F: std::future::Future<Output = Result<(), DomainError>> + Send,
+    {
+        loop {
+            match events.recv().await {
+                Ok(AudioEvent::DeviceLost { device_name }) if enabled() => {
+                    info!(device = %device_name, "Audio device lost, recovering automatically");
+                    if let Err(e) = recover().await {
+                        warn!(error = %e, "Automatic recovery failed");
+                    }
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+/// State the audio thread reports through, shared with `CpalAudioManager`.
+struct AudioThreadShared {
     state: Arc<AtomicAudioState>,
     event_sender: broadcast::Sender<AudioEvent>,
     current_level: Arc<AtomicU32>,
+    current_peak: Arc<AtomicU32>,
+    stream_info: Arc<RwLock<Option<StreamInfo>>>,
+    /// Lets a failed stream ask for its own release; weak so the command
+    /// channel still closes when the manager is dropped.
+    release_tx: mpsc::WeakSender<AudioCommand>,
+}
+
+/// Audio thread runner - creates Stream on the audio thread.
+fn audio_thread_main(
+    config: Arc<RwLock<AudioConfig>>,
+    selected_device_id: Arc<RwLock<Option<String>>>,
+    shared: AudioThreadShared,
     mut cmd_rx: mpsc::Receiver<AudioCommand>,
 ) {
+    let AudioThreadShared {
+        state,
+        event_sender,
+        current_level,
+        current_peak,
+        stream_info,
+        release_tx,
+    } = shared;
+
     // Stream is kept here on the audio thread (not Send)
-    let mut stream: Option<Stream> = None;
-    let mut ring_consumer: Option<RingConsumer> = None;
+    let mut open: Option<OpenStream> = None;
 
     while let Some(cmd) = cmd_rx.blocking_recv() {
         match cmd {
@@ -280,14 +993,53 @@ fn audio_thread_main(
                         return Err(DomainError::AudioAlreadyRecording);
                     }
 
+                    let config = config.read().clone();
                     let device_id = selected_device_id.read().clone();
-                    let device = audio_processing::get_device(device_id.as_deref())?;
+                    let gain_db = config.gain_db_for(device_id.as_deref());
+                    let key = StreamKey {
+                        device_id: device_id.clone(),
+                        sample_rate: config.sample_rate,
+                        gain_db,
+                        channel_mode: config.channel_mode,
+                        capacity: config.buffer_capacity(),
+                        preroll_samples: config.preroll_samples(),
+                    };
+
+                    // Resume a warm stream if it was built for the same device and settings
+                    if let Some(warm) = open.as_mut() {
+                        let reusable = warm_stream_reusable(
+                            &warm.key,
+                            &key,
+                            config.keep_device_warm,
+                            warm.flags.failed.load(Ordering::SeqCst),
+                        );
+                        if reusable {
+                            // Only capture once the state machine agrees
+                            let from = state.transition(AudioState::Recording)?;
+                            warm.consumer.clear();
+                            warm.idle_since = None;
+                            warm.flags.arm(&config);
+                            warm.flags.capturing.store(true, Ordering::SeqCst);
+                            let _ = event_sender.send(AudioEvent::StateChanged {
+                                from,
+                                to: AudioState::Recording,
+                            });
+                            info!(gain_db, "Recording started on warm stream");
+                            return Ok(());
+                        }
+                    }
+                    open = None;
+
+                    let device =
+                        audio_processing::get_device(device_id.as_deref(), config.lock_device)?;
                     let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
                     let stream_config = audio_processing::build_stream_config(&device)?;
 
-                    let capacity = config.buffer_capacity();
-                    let ring = HeapRb::<i16>::new(capacity);
+                    let ring = HeapRb::<i16>::new(key.capacity);
                     let (producer, consumer) = ring.split();
+                    let flags = Arc::new(StreamFlags::with_preroll(key.preroll_samples));
+                    flags.arm(&config);
+                    flags.capturing.store(true, Ordering::SeqCst);
 
                     let sample_format = device.default_input_config().map_err(|e| DomainError::AudioDevice {
                         message: format!("Failed to get config: {}", e),
@@ -295,30 +1047,46 @@ fn audio_thread_main(
 
                     let new_stream = audio_processing::build_stream(
                         &device,
+                        device_name.clone(),
                         &stream_config,
                         sample_format,
                         config.sample_rate,
+                        audio_processing::db_to_linear(gain_db),
+                        config.channel_mode,
                         producer,
+                        Arc::clone(&flags),
                         Arc::clone(&state),
                         event_sender.clone(),
                         Arc::clone(&current_level),
+                        Arc::clone(&current_peak),
+                        release_tx.clone(),
                     )?;
 
                     new_stream.play().map_err(|e| DomainError::AudioDevice {
                         message: format!("Failed to start stream: {}", e),
                     })?;
 
-                    stream = Some(new_stream);
-                    ring_consumer = Some(consumer);
+                    let from = state.transition(AudioState::Recording)?;
+                    open = Some(OpenStream {
+                        _stream: new_stream,
+                        consumer,
+                        flags,
+                        key,
+                        info: StreamInfo {
+                            device_name: device_name.clone(),
+                            sample_rate: stream_config.sample_rate.0,
+                            channels: stream_config.channels,
+                            sample_format: sample_format.to_string(),
+                        },
+                        idle_since: None,
+                    });
 
-                    let from = state.load();
-                    state.store(AudioState::Recording);
                     let _ = event_sender.send(AudioEvent::StateChanged {
                         from,
                         to: AudioState::Recording,
                     });
 
-                    info!(device = %device_name, "Recording started");
+                    info!(device = %device_name, gain_db, "Recording started");
                     Ok(())
                 })();
                 let _ = reply.send(result);
@@ -329,22 +1097,29 @@ fn audio_thread_main(
                         return Err(DomainError::AudioNotRecording);
                     }
 
-                    // Stop and drop the stream
-                    stream.take();
-
-                    // Drain the ring buffer
-                    let mut consumer = ring_consumer.take().ok_or(DomainError::AudioNotRecording)?;
+                    let stream = open.as_mut().ok_or(DomainError::AudioNotRecording)?;
+                    stream.flags.capturing.store(false, Ordering::SeqCst);
 
-                    let available = consumer.occupied_len();
+                    // Drain the ring buffer, which starts with the pre-roll
+                    let available = stream.consumer.occupied_len();
                     let mut samples = vec![0i16; available];
-                    let read = consumer.pop_slice(&mut samples);
+                    let read = stream.consumer.pop_slice(&mut samples);
                     samples.truncate(read);
 
+                    // Keep the device open for the next dictation, or close it
+                    let failed = stream.flags.failed.load(Ordering::SeqCst);
+                    if config.read().keep_device_warm && !failed {
+                        stream.idle_since = Some(Instant::now());
+                        debug!("Input stream kept warm");
+                    } else {
+                        open = None;
+                    }
+
                     // Reset level
                     current_level.store(0f32.to_bits(), Ordering::Relaxed);
+                    current_peak.store(0f32.to_bits(), Ordering::Relaxed);
 
-                    let from = state.load();
-                    state.store(AudioState::Idle);
+                    let from = state.transition(AudioState::Idle)?;
                     let _ = event_sender.send(AudioEvent::StateChanged {
                         from,
                         to: AudioState::Idle,
@@ -355,10 +1130,26 @@ fn audio_thread_main(
                 })();
                 let _ = reply.send(result);
             }
+            AudioCommand::ReleaseWarm => {
+                let timeout = Duration::from_secs(config.read().warm_idle_timeout_secs as u64);
+                let expired = open.as_ref().is_some_and(|stream| {
+                    warm_stream_expired(
+                        stream.idle_since,
+                        stream.flags.failed.load(Ordering::SeqCst),
+                        timeout,
+                        Instant::now(),
+                    )
+                });
+                if expired {
+                    open = None;
+                    info!("Released idle warm input stream");
+                }
+            }
             AudioCommand::Shutdown => {
                 break;
             }
         }
+        *stream_info.write() = open.as_ref().map(|stream| stream.info.clone());
     }
     debug!("Audio thread shutting down");
 }
@@ -367,14 +1158,21 @@ fn audio_thread_main(
 ///
 /// Uses a dedicated audio thread to handle the non-Send Stream type.
 pub struct CpalAudioManager {
-    config: AudioConfig,
+    config: Arc<RwLock<AudioConfig>>,
     state: Arc<AtomicAudioState>,
     event_sender: broadcast::Sender<AudioEvent>,
     current_level: Arc<AtomicU32>,
+    current_peak: Arc<AtomicU32>,
     selected_device_id: Arc<RwLock<Option<String>>>,
+    /// Native format of the open stream, kept in sync by the audio thread.
+    stream_info: Arc<RwLock<Option<StreamInfo>>>,
     recording_start: Mutex<Option<Instant>>,
     cmd_tx: mpsc::Sender<AudioCommand>,
     thread_handle: Mutex<Option<JoinHandle<()>>>,
+    /// Cleared when the audio thread misses a command deadline.
+    responsive: AtomicBool,
+    sleeper: Arc<dyn recovery::Sleeper>,
+    recovery_cancel: recovery::RecoveryCancel,
 }
 
 impl CpalAudioManager {
@@ -386,38 +1184,40 @@ impl CpalAudioManager {
     /// Create a new CpalAudioManager with custom configuration.
     pub fn with_config(config: AudioConfig) -> Result<Self, DomainError> {
         let state = Arc::new(AtomicAudioState::default());
-        let (event_sender, _) = broadcast::channel(64);
+        let (event_sender, _) = broadcast::channel(config.event_capacity.max(1));
+        let command_capacity = config.command_capacity.max(1);
         let current_level = Arc::new(AtomicU32::new(0));
-        let selected_device_id = Arc::new(RwLock::new(None));
+        let current_peak = Arc::new(AtomicU32::new(0));
+        let selected_device_id = Arc::new(RwLock::new(config.device_id.clone()));
+        let config = Arc::new(RwLock::new(config));
 
-        let (cmd_tx, cmd_rx) = mpsc::channel(16);
+        let (cmd_tx, cmd_rx) = mpsc::channel(command_capacity);
 
         // Clone Arcs for the thread
-        let thread_config = config.clone();
+        let thread_config = Arc::clone(&config);
         let thread_device_id = Arc::clone(&selected_device_id);
-        let thread_state = Arc::clone(&state);
-        let thread_event_sender = event_sender.clone();
-        let thread_level = Arc::clone(&current_level);
+        let stream_info = Arc::new(RwLock::new(None));
+        let thread_shared = AudioThreadShared {
+            state: Arc::clone(&state),
+            event_sender: event_sender.clone(),
+            current_level: Arc::clone(&current_level),
+            current_peak: Arc::clone(&current_peak),
+            stream_info: Arc::clone(&stream_info),
+            release_tx: cmd_tx.downgrade(),
+        };
 
         let thread_handle = thread::Builder::new()
             .name("audio-capture".to_string())
             .spawn(move || {
-                audio_thread_main(
-                    thread_config,
-                    thread_device_id,
-                    thread_state,
-                    thread_event_sender,
-                    thread_level,
-                    cmd_rx,
-                )
+                audio_thread_main(thread_config, thread_device_id, thread_shared, cmd_rx)
             })
             .map_err(|e| DomainError::AudioDevice {
                 message: format!("Failed to spawn audio thread: {}", e),
             })?;
 
         info!(
-            buffer_duration = config.buffer_duration_secs,
-            sample_rate = config.sample_rate,
+            buffer_duration = config.read().buffer_duration_secs,
+            sample_rate = config.read().sample_rate,
             "CpalAudioManager initialized"
         );
 
@@ -426,13 +1226,53 @@ impl CpalAudioManager {
             state,
             event_sender,
             current_level,
+            current_peak,
             selected_device_id,
+            stream_info,
             recording_start: Mutex::new(None),
             cmd_tx,
             thread_handle: Mutex::new(Some(thread_handle)),
+            responsive: AtomicBool::new(true),
+            sleeper: Arc::new(recovery::TokioSleeper),
+            recovery_cancel: recovery::RecoveryCancel::default(),
         })
     }
 
+    /// Send a command to the audio thread and wait for its reply, failing
+    /// after `COMMAND_TIMEOUT` instead of hanging on a wedged thread.
+    async fn send_command<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<Result<T, DomainError>>) -> AudioCommand,
+    ) -> Result<T, DomainError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        tokio::time::timeout(COMMAND_TIMEOUT, self.cmd_tx.send(command(reply_tx)))
+            .await
+            .map_err(|_| self.mark_unresponsive())?
+            .map_err(|_| DomainError::AudioDevice {
+                message: "Audio thread not running".to_string(),
+            })?;
+
+        let result = tokio::time::timeout(COMMAND_TIMEOUT, reply_rx)
+            .await
+            .map_err(|_| self.mark_unresponsive())?
+            .map_err(|_| DomainError::AudioDevice {
+                message: "Audio thread did not respond".to_string(),
+            })?;
+
+        self.responsive.store(true, Ordering::Relaxed);
+        result
+    }
+
+    /// Record a missed deadline and build the error to return.
+    fn mark_unresponsive(&self) -> DomainError {
+        self.responsive.store(false, Ordering::Relaxed);
+        error!(timeout_secs = COMMAND_TIMEOUT.as_secs(), "Audio thread unresponsive");
+        DomainError::AudioDevice {
+            message: "Audio thread unresponsive".to_string(),
+        }
+    }
+
     /// List available input devices with unique IDs.
     fn list_devices_internal(&self) -> Result<Vec<AudioDevice>, DomainError> {
         let host = cpal::default_host();
@@ -442,27 +1282,24 @@ impl CpalAudioManager {
             message: format!("Failed to enumerate devices: {}", e),
         })?;
 
-        let mut result = Vec::new();
-        let mut name_counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
-
-        for device in devices {
-            if let Ok(name) = device.name() {
-                // Generate unique ID by appending index for duplicate names
-                let count = name_counts.entry(name.clone()).or_insert(0);
-                let id = if *count == 0 {
-                    name.clone()
-                } else {
-                    format!("{}:{}", name, count)
-                };
-                *count += 1;
-
-                result.push(AudioDevice {
+        let devices: Vec<Device> = devices.collect();
+        let names: Vec<Option<String>> = devices.iter().map(|d| d.name().ok()).collect();
+        let result: Vec<AudioDevice> = audio_processing::label_devices(&names)
+            .into_iter()
+            .zip(names.iter().zip(&devices))
+            .map(|((id, name), (raw_name, device))| {
+                let (supported_sample_rates, max_channels) =
+                    audio_processing::device_capabilities(device);
+                AudioDevice {
                     id,
-                    name: name.clone(),
-                    is_default: Some(&name) == default_name.as_ref(),
-                });
-            }
-        }
+                    is_default: raw_name.is_some() && *raw_name == default_name,
+                    is_virtual: AudioDevice::is_virtual_name(&name),
+                    name,
+                    supported_sample_rates,
+                    max_channels,
+                }
+            })
+            .collect();
 
         debug!(count = result.len(), "Listed input devices");
         Ok(result)
@@ -484,36 +1321,18 @@ impl Drop for CpalAudioManager {
 #[async_trait]
 impl AudioManager for CpalAudioManager {
     async fn start_recording(&self) -> Result<(), DomainError> {
-        let (reply_tx, reply_rx) = oneshot::channel();
-
-        self.cmd_tx
-            .send(AudioCommand::Start { reply: reply_tx })
-            .await
-            .map_err(|_| DomainError::AudioDevice {
-                message: "Audio thread not running".to_string(),
-            })?;
-
-        let result = reply_rx.await.map_err(|_| DomainError::AudioDevice {
-            message: "Audio thread did not respond".to_string(),
-        })??;
+        let result = self
+            .send_command(|reply| AudioCommand::Start { reply })
+            .await?;
 
         *self.recording_start.lock() = Some(Instant::now());
         Ok(result)
     }
 
     async fn stop_recording(&self) -> Result<AudioBuffer, DomainError> {
-        let (reply_tx, reply_rx) = oneshot::channel();
-
-        self.cmd_tx
-            .send(AudioCommand::Stop { reply: reply_tx })
-            .await
-            .map_err(|_| DomainError::AudioDevice {
-                message: "Audio thread not running".to_string(),
-            })?;
-
-        let samples = reply_rx.await.map_err(|_| DomainError::AudioDevice {
-            message: "Audio thread did not respond".to_string(),
-        })??;
+        let samples = self
+            .send_command(|reply| AudioCommand::Stop { reply })
+            .await?;
 
         let duration = self
             .recording_start
@@ -522,7 +1341,20 @@ impl AudioManager for CpalAudioManager {
             .map(|t| t.elapsed().as_secs_f32())
             .unwrap_or(0.0);
 
-        let mut buffer = AudioBuffer::with_capacity(self.config.sample_rate, samples.len());
+        let (sample_rate, keep_warm, warm_timeout) = {
+            let config = self.config.read();
+            (config.sample_rate, config.keep_device_warm, config.warm_idle_timeout_secs)
+        };
+        if keep_warm {
+            // The audio thread ignores this if another recording ran meanwhile
+            let cmd_tx = self.cmd_tx.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_secs(warm_timeout as u64)).await;
+                let _ = cmd_tx.send(AudioCommand::ReleaseWarm).await;
+            });
+        }
+
+        let mut buffer = AudioBuffer::with_capacity(sample_rate, samples.len());
         buffer.push_samples(&samples);
 
         info!(
@@ -539,11 +1371,24 @@ impl AudioManager for CpalAudioManager {
     }
 
     fn config(&self) -> AudioConfig {
-        self.config.clone()
+        self.config.read().clone()
+    }
+
+    fn update_config(&self, config: AudioConfig) {
+        *self.selected_device_id.write() = config.device_id.clone();
+        *self.config.write() = config;
     }
 
     fn list_input_devices(&self) -> Result<Vec<AudioDevice>, DomainError> {
-        self.list_devices_internal()
+        let mut devices = self.list_devices_internal()?;
+
+        // Hide virtual devices unless opted in, but keep the current selection visible
+        if !self.config.read().show_virtual_devices {
+            let selected = self.selected_device_id.read().clone();
+            devices.retain(|d| !d.is_virtual || selected.as_deref() == Some(d.id.as_str()));
+        }
+
+        Ok(devices)
     }
 
     fn select_input_device(&self, device_id: Option<&str>) -> Result<(), DomainError> {
@@ -561,69 +1406,53 @@ impl AudioManager for CpalAudioManager {
         Ok(())
     }
 
+    fn selected_input_device(&self) -> Option<String> {
+        self.selected_device_id.read().clone()
+    }
+
+    fn stream_info(&self) -> Option<StreamInfo> {
+        self.stream_info.read().clone()
+    }
+
     fn subscribe(&self) -> broadcast::Receiver<AudioEvent> {
         self.event_sender.subscribe()
     }
 
     async fn recover(&self) -> Result<(), DomainError> {
-        let current = self.state.load();
-        if !current.can_recover() {
-            return Err(DomainError::AudioStateTransition {
-                from: current,
-                to: AudioState::Recovering,
-            });
-        }
-
-        // Transition to Recovering
-        self.state.store(AudioState::Recovering);
-        let _ = self.event_sender.send(AudioEvent::StateChanged {
-            from: current,
-            to: AudioState::Recovering,
-        });
-
-        // Attempt recovery with exponential backoff
-        let max_attempts = self.config.max_recovery_attempts;
-        for attempt in 1..=max_attempts {
-            let delay_ms = 500 * (1 << (attempt - 1)); // 500ms, 1s, 2s
-            tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
-
-            info!(attempt, max_attempts, delay_ms, "Recovery attempt");
+        let config = self.config.read().clone();
+        let selected_device_id = Arc::clone(&self.selected_device_id);
+        recovery::run(
+            &config,
+            self.sleeper.as_ref(),
+            &self.recovery_cancel,
+            &self.state,
+            &self.event_sender,
+            || {
+                let device_id = selected_device_id.read().clone();
+                audio_processing::get_device(device_id.as_deref(), config.lock_device)
+                    .map(|device| device.name().unwrap_or_else(|_| "Unknown".to_string()))
+            },
+        )
+        .await
+    }
 
-            // Check if device is available
-            match audio_processing::get_device(self.selected_device_id.read().as_deref()) {
-                Ok(device) => {
-                    let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
-                    self.state.store(AudioState::Idle);
-                    let _ = self.event_sender.send(AudioEvent::RecoverySuccess {
-                        device_name: device_name.clone(),
-                    });
-                    let _ = self.event_sender.send(AudioEvent::StateChanged {
-                        from: AudioState::Recovering,
-                        to: AudioState::Idle,
-                    });
-                    info!(device = %device_name, "Audio recovered successfully");
-                    return Ok(());
-                }
-                Err(e) => {
-                    warn!(attempt, error = %e, "Recovery attempt failed");
-                }
-            }
+    fn cancel_recovery(&self) -> bool {
+        if self.state.load() != AudioState::Recovering {
+            return false;
         }
+        info!("Recovery cancellation requested");
+        self.recovery_cancel.cancel();
+        true
+    }
 
-        // All attempts failed
-        self.state.store(AudioState::Error);
-        let _ = self.event_sender.send(AudioEvent::RecoveryFailed {
-            attempts: max_attempts,
-            last_error: "Failed to recover audio device".to_string(),
-        });
-        let _ = self.event_sender.send(AudioEvent::StateChanged {
-            from: AudioState::Recovering,
-            to: AudioState::Error,
-        });
-
-        Err(DomainError::AudioDevice {
-            message: format!("Recovery failed after {} attempts", max_attempts),
-        })
+    async fn watch_device_loss(&self) {
+        recovery::watch(
+            self.subscribe(),
+            // Skip if a recovery is already running or the device came back
+            || self.config.read().auto_recover && self.state.load() == AudioState::DeviceLost,
+            || self.recover(),
+        )
+        .await
     }
 
     fn current_duration(&self) -> f32 {
@@ -637,6 +1466,33 @@ impl AudioManager for CpalAudioManager {
     fn current_level(&self) -> f32 {
         f32::from_bits(self.current_level.load(Ordering::Relaxed))
     }
+
+    fn current_peak(&self) -> f32 {
+        f32::from_bits(self.current_peak.load(Ordering::Relaxed))
+    }
+
+    fn is_responsive(&self) -> bool {
+        self.responsive.load(Ordering::Relaxed)
+    }
+
+    async fn measure_capture_rate(
+        &self,
+        duration: Duration,
+    ) -> Result<CaptureRateMeasurement, DomainError> {
+        if self.state.load() == AudioState::Recording {
+            return Err(DomainError::AudioAlreadyRecording);
+        }
+
+        // The stream isn't Send, so it lives entirely on a blocking thread
+        let device_id = self.selected_device_id.read().clone();
+        let lock_device = self.config.read().lock_device;
+        tokio::task::spawn_blocking(move || {
+            let device = audio_processing::get_device(device_id.as_deref(), lock_device)?;
+            audio_processing::measure_capture_rate(&device, duration)
+        })
+        .await
+        .map_err(|e| DomainError::Audio(format!("Capture rate measurement failed: {}", e)))?
+    }
 }
 
 #[cfg(test)]
@@ -655,6 +1511,553 @@ mod tests {
         assert!(half_rms > 0.4 && half_rms < 0.6);
     }
 
+    #[test]
+    fn test_apply_gain() {
+        // +6 dB roughly doubles amplitude
+        let gain = audio_processing::db_to_linear(6.0);
+        assert!((gain - 1.995).abs() < 0.01);
+
+        let mut samples = vec![1000, -1000, 20000, -20000];
+        audio_processing::apply_gain(&mut samples, gain);
+        assert_eq!(samples[0], 1995);
+        assert_eq!(samples[1], -1995);
+        // Saturates instead of wrapping
+        assert_eq!(samples[2], i16::MAX);
+        assert_eq!(samples[3], i16::MIN);
+
+        let mut unity = vec![123, -456];
+        audio_processing::apply_gain(&mut unity, audio_processing::db_to_linear(0.0));
+        assert_eq!(unity, vec![123, -456]);
+    }
+
+    #[test]
+    fn test_label_devices_duplicates_and_colons() {
+        let names = vec![
+            Some("USB Mic".to_string()),
+            Some("USB Mic".to_string()),
+            Some("hw:1,0".to_string()),
+            Some("USB Mic:1".to_string()),
+        ];
+        let ids: Vec<String> = audio_processing::label_devices(&names)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, vec!["USB Mic", "USB Mic:1", "hw_1,0", "USB Mic_1"]);
+    }
+
+    #[test]
+    fn test_find_device_respects_lock() {
+        let labels = audio_processing::label_devices(&[
+            Some("Built-in Mic".to_string()),
+            Some("USB Mic".to_string()),
+        ]);
+        let find = |id, lock| audio_processing::find_device(&labels, id, lock);
+
+        assert_eq!(find("USB Mic", true).unwrap(), Some(1));
+        assert_eq!(find("Headset", false).unwrap(), None);
+        assert!(matches!(find("Headset", true), Err(DomainError::AudioDevice { .. })));
+    }
+
+    #[test]
+    fn test_standard_rates_in_ranges() {
+        // A USB mic with a fixed rate and a pro interface with a wide range
+        assert_eq!(audio_processing::standard_rates_in(&[(48_000, 48_000)]), vec![48_000]);
+        assert_eq!(
+            audio_processing::standard_rates_in(&[(44_100, 96_000), (16_000, 16_000)]),
+            vec![16_000, 44_100, 48_000, 88_200, 96_000]
+        );
+        assert!(audio_processing::standard_rates_in(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_label_devices_unnamed() {
+        let names = vec![None, Some("  ".to_string()), Some("Mic\u{0}".to_string())];
+        let labels = audio_processing::label_devices(&names);
+        assert_eq!(labels[0], ("#0".to_string(), "Input device 1".to_string()));
+        assert_eq!(labels[1], ("#1".to_string(), "Input device 2".to_string()));
+        assert_eq!(labels[2], ("Mic".to_string(), "Mic".to_string()));
+    }
+
+    /// Sleeper that returns immediately and records requested delays.
+    #[derive(Default)]
+    struct RecordingSleeper {
+        delays: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl recovery::Sleeper for RecordingSleeper {
+        async fn sleep(&self, duration: std::time::Duration) {
+            self.delays.lock().push(duration.as_millis() as u64);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recovery_backoff_then_failure() {
+        let sleeper = RecordingSleeper::default();
+        let state = AtomicAudioState::new(AudioState::DeviceLost);
+        let (events, mut rx) = broadcast::channel(16);
+        let mut probes = 0;
+
+        let cancel = recovery::RecoveryCancel::default();
+        let result = recovery::run(&AudioConfig::default(), &sleeper, &cancel, &state, &events, || {
+            probes += 1;
+            Err(DomainError::AudioDevice {
+                message: "unplugged".to_string(),
+            })
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(probes, 3);
+        assert_eq!(*sleeper.delays.lock(), vec![500, 1000, 2000]);
+        assert_eq!(state.load(), AudioState::Error);
+
+        let mut failed = None;
+        while let Ok(event) = rx.try_recv() {
+            if let AudioEvent::RecoveryFailed { attempts, last_error } = event {
+                failed = Some((attempts, last_error));
+            }
+        }
+        let (attempts, last_error) = failed.expect("RecoveryFailed not emitted");
+        assert_eq!(attempts, 3);
+        assert!(last_error.contains("unplugged"));
+    }
+
+    #[tokio::test]
+    async fn test_watch_recovers_on_device_loss() {
+        let sleeper = RecordingSleeper::default();
+        let cancel = recovery::RecoveryCancel::default();
+        let state = AtomicAudioState::new(AudioState::DeviceLost);
+        let (events, mut rx) = broadcast::channel(16);
+        let (lost, watched) = broadcast::channel(16);
+        let config = AudioConfig {
+            auto_recover: true,
+            ..Default::default()
+        };
+
+        let mut recoveries = 0;
+        lost.send(AudioEvent::DeviceLost {
+            device_name: "USB Mic".to_string(),
+        })
+        .unwrap();
+        drop(lost);
+        recovery::watch(watched, || true, || {
+            recoveries += 1;
+            recovery::run(&config, &sleeper, &cancel, &state, &events, || {
+                Ok("Built-in Microphone".to_string())
+            })
+        })
+        .await;
+
+        // Same path as a manual recover: back to Idle, never Recording
+        assert_eq!(recoveries, 1);
+        assert_eq!(state.load(), AudioState::Idle);
+        let mut recovered = false;
+        while let Ok(event) = rx.try_recv() {
+            recovered |= matches!(event, AudioEvent::RecoverySuccess { .. });
+        }
+        assert!(recovered);
+
+        // Nothing happens while disabled
+        let (lost, watched) = broadcast::channel(16);
+        lost.send(AudioEvent::DeviceLost {
+            device_name: "USB Mic".to_string(),
+        })
+        .unwrap();
+        drop(lost);
+        let mut recoveries = 0;
+        recovery::watch(watched, || false, || {
+            recoveries += 1;
+            async { Ok(()) }
+        })
+        .await;
+        assert_eq!(recoveries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_grace_period_and_success() {
+        let sleeper = RecordingSleeper::default();
+        let state = AtomicAudioState::new(AudioState::Error);
+        let (events, _rx) = broadcast::channel(16);
+        let config = AudioConfig {
+            recovery_initial_delay_ms: 3000,
+            ..Default::default()
+        };
+        let mut probes = 0;
+        let cancel = recovery::RecoveryCancel::default();
+
+        recovery::run(&config, &sleeper, &cancel, &state, &events, || {
+            probes += 1;
+            if probes < 2 {
+                Err(DomainError::AudioDevice {
+                    message: "not yet".to_string(),
+                })
+            } else {
+                Ok("USB Mic".to_string())
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(*sleeper.delays.lock(), vec![3000, 500, 1000]);
+        assert_eq!(state.load(), AudioState::Idle);
+
+        // Not recoverable from Idle
+        assert!(recovery::run(&config, &sleeper, &cancel, &state, &events, || Ok(String::new()))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_recovery_cancel_skips_remaining_backoff() {
+        let config = AudioConfig::default();
+        let (events, _rx) = broadcast::channel(16);
+        let cancel = recovery::RecoveryCancel::default();
+
+        // Device came back: cancelling ends in Idle without further sleeps
+        let sleeper = RecordingSleeper::default();
+        let state = AtomicAudioState::new(AudioState::DeviceLost);
+        let mut probes = 0;
+        recovery::run(&config, &sleeper, &cancel, &state, &events, || {
+            probes += 1;
+            if probes == 1 {
+                cancel.cancel();
+                Err(DomainError::AudioDevice {
+                    message: "not yet".to_string(),
+                })
+            } else {
+                Ok("USB Mic".to_string())
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(*sleeper.delays.lock(), vec![500]);
+        assert_eq!(state.load(), AudioState::Idle);
+
+        // Still no device: cancelling ends in Error
+        let sleeper = RecordingSleeper::default();
+        let state = AtomicAudioState::new(AudioState::DeviceLost);
+        let result = recovery::run(&config, &sleeper, &cancel, &state, &events, || {
+            cancel.cancel();
+            Err(DomainError::AudioDevice {
+                message: "unplugged".to_string(),
+            })
+        })
+        .await;
+        assert!(result.unwrap_err().to_string().contains("cancelled"));
+        assert_eq!(*sleeper.delays.lock(), vec![500]);
+        assert_eq!(state.load(), AudioState::Error);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_cancel_wakes_backoff_sleep() {
+        let config = AudioConfig {
+            recovery_initial_delay_ms: 60_000,
+            ..Default::default()
+        };
+        let (events, _rx) = broadcast::channel(16);
+        let cancel = recovery::RecoveryCancel::default();
+        let state = AtomicAudioState::new(AudioState::DeviceLost);
+
+        let run = recovery::run(&config, &recovery::TokioSleeper, &cancel, &state, &events, || {
+            Ok("USB Mic".to_string())
+        });
+        let cancel_soon = async {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            cancel.cancel();
+        };
+        let (result, _) = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            async { tokio::join!(run, cancel_soon) },
+        )
+        .await
+        .expect("cancel did not interrupt the backoff");
+        result.unwrap();
+        assert_eq!(state.load(), AudioState::Idle);
+    }
+
+    #[test]
+    fn test_downmix_channel_modes() {
+        // Interleaved stereo: left is speech, right is silent
+        let stereo = [1000, 0, -2000, 0, 3000, 0];
+
+        assert_eq!(audio_processing::downmix(&stereo, 2, ChannelMode::Mix), vec![500, -1000, 1500]);
+        assert_eq!(
+            audio_processing::downmix(&stereo, 2, ChannelMode::Left),
+            vec![1000, -2000, 3000]
+        );
+        assert_eq!(audio_processing::downmix(&stereo, 2, ChannelMode::Right), vec![0, 0, 0]);
+        assert_eq!(
+            audio_processing::downmix(&stereo, 2, ChannelMode::Channel(0)),
+            vec![1000, -2000, 3000]
+        );
+        assert_eq!(
+            audio_processing::downmix(&stereo, 2, ChannelMode::Channel(1)),
+            vec![0, 0, 0]
+        );
+        // Missing channel falls back to the first one
+        assert!(!ChannelMode::Channel(5).fits(2));
+        assert_eq!(
+            audio_processing::downmix(&stereo, 2, ChannelMode::Channel(5)),
+            vec![1000, -2000, 3000]
+        );
+        // Mono input is passed through whatever the mode
+        assert_eq!(audio_processing::downmix(&[7, 8], 1, ChannelMode::Right), vec![7, 8]);
+    }
+
+    #[test]
+    fn test_warm_stream_reuse() {
+        let key = StreamKey {
+            device_id: Some("usb-mic".to_string()),
+            sample_rate: 16_000,
+            gain_db: 0.0,
+            channel_mode: ChannelMode::Mix,
+            capacity: 960_000,
+            preroll_samples: 0,
+        };
+        assert!(warm_stream_reusable(&key, &key.clone(), true, false));
+        assert!(!warm_stream_reusable(&key, &key.clone(), false, false));
+        assert!(!warm_stream_reusable(&key, &key.clone(), true, true));
+
+        // Any change to the device or settings rebuilds the stream
+        let other_device = StreamKey {
+            device_id: None,
+            ..key.clone()
+        };
+        let other_gain = StreamKey {
+            gain_db: 6.0,
+            ..key.clone()
+        };
+        assert!(!warm_stream_reusable(&key, &other_device, true, false));
+        assert!(!warm_stream_reusable(&key, &other_gain, true, false));
+    }
+
+    #[test]
+    fn test_warm_stream_release() {
+        let now = Instant::now();
+        let timeout = Duration::from_secs(30);
+        let idle_for = |secs| Some(now - Duration::from_secs(secs));
+
+        assert!(!warm_stream_expired(idle_for(10), false, timeout, now));
+        assert!(warm_stream_expired(idle_for(30), false, timeout, now));
+        // A failed stream is released right away, but never while recording
+        assert!(warm_stream_expired(idle_for(0), true, timeout, now));
+        assert!(!warm_stream_expired(None, true, timeout, now));
+    }
+
+    #[test]
+    fn test_capture_stops_at_max_duration_without_dropping() {
+        // Limit equal to the buffer capacity: the stop comes first
+        let (mut producer, consumer) = HeapRb::<i16>::new(1_000).split();
+        let flags = StreamFlags::default();
+        flags.max_samples.store(1_000, Ordering::SeqCst);
+        flags.recordings.store(1, Ordering::SeqCst);
+        flags.capturing.store(true, Ordering::SeqCst);
+        let mut session = CaptureSession::new(0);
+        let (event_sender, mut events) = broadcast::channel(16);
+        let (mut overflow, mut level_samples, mut counter) =
+            (audio_processing::OverflowThrottle::default(), Vec::new(), 0);
+
+        for _ in 0..4 {
+            if !flags.capturing.load(Ordering::SeqCst) {
+                break;
+            }
+            audio_processing::process_samples_i16(
+                &[100; 300],
+                1,
+                ChannelMode::Mix,
+                16_000,
+                1.0,
+                &mut producer,
+                &flags,
+                &mut session,
+                &mut None,
+                &mut None,
+                &mut overflow,
+                &mut level_samples,
+                &mut counter,
+                1_600,
+                &event_sender,
+                &AtomicU32::new(0),
+                &AtomicU32::new(0),
+            );
+        }
+
+        assert_eq!(consumer.occupied_len(), 1_000);
+        assert!(!flags.capturing.load(Ordering::SeqCst));
+        assert!(matches!(events.try_recv(), Ok(AudioEvent::MaxDurationReached)));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_recording_opens_with_preroll() {
+        let (mut producer, mut consumer) = HeapRb::<i16>::new(100).split();
+        let flags = StreamFlags::with_preroll(4);
+        flags.max_samples.store(100, Ordering::SeqCst);
+        let mut session = CaptureSession::new(flags.preroll_samples);
+        let (event_sender, _events) = broadcast::channel(16);
+        let (mut overflow, mut level_samples, mut counter) =
+            (audio_processing::OverflowThrottle::default(), Vec::new(), 0);
+        let mut process = |chunk: &[i16], session: &mut CaptureSession| {
+            audio_processing::process_samples_i16(
+                chunk,
+                1,
+                ChannelMode::Mix,
+                16_000,
+                1.0,
+                &mut producer,
+                &flags,
+                session,
+                &mut None,
+                &mut None,
+                &mut overflow,
+                &mut level_samples,
+                &mut counter,
+                1_600,
+                &event_sender,
+                &AtomicU32::new(0),
+                &AtomicU32::new(0),
+            )
+        };
+
+        // Idle: only the pre-roll window is kept
+        process(&[1, 2, 3], &mut session);
+        process(&[4, 5, 6], &mut session);
+        assert_eq!(consumer.occupied_len(), 0);
+
+        flags.recordings.fetch_add(1, Ordering::SeqCst);
+        flags.capturing.store(true, Ordering::SeqCst);
+        process(&[7, 8], &mut session);
+        let mut samples = [0i16; 6];
+        assert_eq!(consumer.pop_slice(&mut samples), 6);
+        assert_eq!(samples, [3, 4, 5, 6, 7, 8]);
+
+        // The next recording doesn't get it again
+        flags.capturing.store(false, Ordering::SeqCst);
+        process(&[], &mut session);
+        flags.recordings.fetch_add(1, Ordering::SeqCst);
+        flags.capturing.store(true, Ordering::SeqCst);
+        process(&[9], &mut session);
+        assert_eq!(consumer.pop_slice(&mut samples), 1);
+        assert_eq!(samples[0], 9);
+    }
+
+    #[test]
+    fn test_warm_recording_starts_clean() {
+        let (mut producer, mut consumer) = HeapRb::<i16>::new(10_000).split();
+        let flags = StreamFlags::default();
+        flags.max_samples.store(10_000, Ordering::SeqCst);
+        let mut session = CaptureSession::new(0);
+        let mut anti_alias = Some(LowPassFilter::anti_aliasing(48_000, 16_000));
+        let mut resampler = Some(StreamResampler::new(48_000, 16_000));
+        let (event_sender, _events) = broadcast::channel(16);
+        let (mut overflow, mut level_samples, mut counter) =
+            (audio_processing::OverflowThrottle::default(), Vec::new(), 0);
+        let mut process = |chunk: &[i16], session: &mut CaptureSession| {
+            audio_processing::process_samples_i16(
+                chunk,
+                1,
+                ChannelMode::Mix,
+                16_000,
+                1.0,
+                &mut producer,
+                &flags,
+                session,
+                &mut anti_alias,
+                &mut resampler,
+                &mut overflow,
+                &mut level_samples,
+                &mut counter,
+                1_600,
+                &event_sender,
+                &AtomicU32::new(0),
+                &AtomicU32::new(0),
+            )
+        };
+
+        // A loud first recording, stopped mid-chunk
+        flags.recordings.fetch_add(1, Ordering::SeqCst);
+        flags.capturing.store(true, Ordering::SeqCst);
+        process(&[20_000; 480], &mut session);
+        flags.capturing.store(false, Ordering::SeqCst);
+        consumer.clear();
+
+        // The next one on the same stream hears none of it
+        flags.recordings.fetch_add(1, Ordering::SeqCst);
+        flags.capturing.store(true, Ordering::SeqCst);
+        process(&[0; 480], &mut session);
+        let mut samples = vec![0i16; consumer.occupied_len()];
+        consumer.pop_slice(&mut samples);
+        assert!(!samples.is_empty());
+        assert!(samples.iter().all(|&s| s == 0), "{:?}", samples);
+    }
+
+    #[test]
+    fn test_preroll_keeps_latest_window() {
+        let mut preroll = PreRoll::new(4);
+        let start = Instant::now();
+        let window = preroll.window(16_000);
+        assert!(preroll.take(start, window).is_empty());
+
+        preroll.push(&[1, 2, 3], start);
+        preroll.push(&[4, 5, 6], start);
+        assert_eq!(*preroll.take(start, window), vec![3, 4, 5, 6]);
+        // Taking empties it
+        assert!(preroll.take(start, window).is_empty());
+
+        // A chunk longer than the window keeps its tail
+        preroll.push(&[1, 2, 3, 4, 5, 6, 7], start);
+        assert_eq!(*preroll.take(start, window), vec![4, 5, 6, 7]);
+
+        // Audio older than the window is discarded
+        preroll.push(&[1, 2], start);
+        assert!(preroll.take(start + Duration::from_secs(1), window).is_empty());
+
+        let mut disabled = PreRoll::new(0);
+        disabled.push(&[1, 2], start);
+        assert!(!disabled.is_enabled());
+        assert!(disabled.take(start, window).is_empty());
+    }
+
+    #[test]
+    fn test_silence_detector_waits_for_speech() {
+        let mut detector = audio_processing::SilenceDetector::new(-45.0, 1_000);
+        // Leading silence, however long, doesn't end the recording
+        for _ in 0..30 {
+            assert_eq!(detector.update(-60.0, 100), None);
+        }
+        assert_eq!(detector.update(-20.0, 100), None);
+        // A short pause mid-sentence resets once speech resumes
+        for _ in 0..5 {
+            assert_eq!(detector.update(-60.0, 100), None);
+        }
+        assert_eq!(detector.update(-25.0, 100), None);
+        for _ in 0..9 {
+            assert_eq!(detector.update(-50.0, 100), None);
+        }
+        assert_eq!(detector.update(-50.0, 100), Some(1_000));
+
+        let mut disabled = audio_processing::SilenceDetector::new(-45.0, 0);
+        assert_eq!(disabled.update(-20.0, 100), None);
+        for _ in 0..100 {
+            assert_eq!(disabled.update(-60.0, 100), None);
+        }
+    }
+
+    #[test]
+    fn test_overflow_throttle() {
+        let mut throttle = audio_processing::OverflowThrottle::default();
+        let start = Instant::now();
+        assert_eq!(throttle.record(0, start), None);
+        assert_eq!(throttle.record(100, start), Some(100));
+
+        // Further drops within a second are accumulated, not reported
+        assert_eq!(throttle.record(50, start + Duration::from_millis(300)), None);
+        assert_eq!(throttle.record(50, start + Duration::from_millis(900)), None);
+        assert_eq!(throttle.record(0, start + Duration::from_millis(1000)), Some(100));
+        assert_eq!(throttle.record(0, start + Duration::from_millis(2500)), None);
+    }
+
     #[test]
     fn test_resample_same_rate() {
         let samples = vec![100, 200, 300, 400];
@@ -675,4 +2078,31 @@ mod tests {
         let result = audio_processing::resample(&samples, 8000, 16000);
         assert!(result.len() >= 7 && result.len() <= 9);
     }
+
+    #[test]
+    fn test_resample_suppresses_aliasing() {
+        // Linear sweep at 48kHz; one second, `from`-`to` Hz
+        let sweep = |from: f64, to: f64| -> Vec<i16> {
+            (0..48_000)
+                .map(|i| {
+                    let t = i as f64 / 48_000.0;
+                    let phase = 2.0 * std::f64::consts::PI * (from * t + (to - from) * t * t / 2.0);
+                    (phase.sin() * 10_000.0) as i16
+                })
+                .collect()
+        };
+        let rms = |samples: &[i16]| audio_processing::calculate_rms(samples);
+
+        // Everything above the 8kHz target Nyquist would alias into the band;
+        // linear interpolation lets nearly all of it through
+        let high = sweep(9_000.0, 23_000.0);
+        let resampled = audio_processing::resample(&high, 48_000, 16_000);
+        assert_eq!(resampled.len(), 16_000);
+        assert!(rms(&resampled) < rms(&high) / 50.0);
+
+        // Speech band content passes through
+        let low = sweep(100.0, 6_000.0);
+        let resampled = audio_processing::resample(&low, 48_000, 16_000);
+        assert!((rms(&resampled) / rms(&low) - 1.0).abs() < 0.02);
+    }
 }
@@ -1,7 +1,7 @@
 use std::fs;
 use std::path::PathBuf;
 
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use crate::domain::{AppConfig, DomainError};
 use crate::ports::ConfigStore;
@@ -95,7 +95,12 @@ impl ConfigStore for TomlConfigStore {
         if config_path.exists() {
             debug!(path = ?config_path, "Loading configuration");
             let content = fs::read_to_string(&config_path)?;
-            let config: AppConfig = toml::from_str(&content)?;
+            let mut config: AppConfig = toml::from_str(&content)?;
+            // Out-of-range values fall back to defaults rather than keeping
+            // the app from starting; the settings UI can then fix them
+            for reset in config.repair() {
+                warn!(setting = %reset, "Invalid setting in config file, using the default");
+            }
             info!(path = ?config_path, "Configuration loaded");
             Ok(config)
         } else {
@@ -137,6 +142,7 @@ impl ConfigStore for TomlConfigStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::OutputMode;
     use std::env;
 
     #[test]
@@ -176,4 +182,45 @@ mod tests {
         // Cleanup
         let _ = fs::remove_dir_all(&temp_dir);
     }
+
+    #[test]
+    fn test_load_repairs_invalid_values() {
+        let temp_dir = env::temp_dir().join("opensay_test_invalid_config");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+        let store = TomlConfigStore {
+            data_dir: temp_dir.clone(),
+        };
+        fs::write(
+            store.config_path(),
+            "[transcription]\nlanguage = \"fr\"\nvad_no_speech_threshold = 5.0\n\n\
+             [audio]\nbuffer_duration_secs = 1\ninput_gain_db = 6.0\n",
+        )
+        .unwrap();
+
+        // Invalid values are reset, valid ones next to them are kept
+        let loaded = store.load().unwrap();
+        assert!(loaded.validate().is_ok());
+        assert_eq!(loaded.transcription.language, "fr");
+        assert_eq!(
+            loaded.transcription.vad_no_speech_threshold,
+            AppConfig::default().transcription.vad_no_speech_threshold
+        );
+        assert_eq!(loaded.audio.buffer_duration_secs, 60);
+        assert_eq!(loaded.audio.input_gain_db, 6.0);
+
+        // Cleanup
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_output_mode_parse() {
+        let config: AppConfig = toml::from_str("[output]\nmode = \"clipboard_only\"\n").unwrap();
+        assert_eq!(config.output.mode, OutputMode::ClipboardOnly);
+        assert_eq!(config.output.paste_delay_ms, 100);
+
+        // Missing mode falls back to paste
+        let config: AppConfig = toml::from_str("[output]\npaste_delay_ms = 50\n").unwrap();
+        assert_eq!(config.output.mode, OutputMode::Paste);
+    }
 }
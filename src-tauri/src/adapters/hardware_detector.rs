@@ -1,5 +1,6 @@
 use std::sync::OnceLock;
 
+use parking_lot::RwLock;
 use tracing::{debug, info};
 
 #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
@@ -11,12 +12,99 @@ use crate::domain::{
 };
 use crate::ports::HardwareDetector;
 
+/// Upper bound on a detected thread count, in case a source reports nonsense.
+const MAX_CPU_THREADS: u32 = 256;
+
+/// Detect the number of CPU threads available to the app.
+///
+/// `available_parallelism` reports 1 in some containers even on multi-core
+/// hosts, so the OS-specific sources (cgroup quota and `nproc` on Linux,
+/// `sysctl` on macOS) are also consulted and the largest answer wins,
+/// clamped to `1..=MAX_CPU_THREADS`.
+pub fn detect_cpu_threads() -> u32 {
+    let mut sources = vec![(
+        "available_parallelism",
+        std::thread::available_parallelism().ok().map(|p| p.get() as u32),
+    )];
+    sources.extend(os_cpu_thread_sources());
+
+    let (source, threads) = sources
+        .into_iter()
+        .filter_map(|(source, threads)| threads.map(|t| (source, t)))
+        .max_by_key(|&(_, threads)| threads)
+        .unwrap_or(("default", 1));
+    let threads = threads.clamp(1, MAX_CPU_THREADS);
+
+    info!(threads, source, "Detected CPU threads");
+    threads
+}
+
+/// Linux: the cgroup CPU quota (v2, then v1) and `nproc`.
+#[cfg(target_os = "linux")]
+fn os_cpu_thread_sources() -> Vec<(&'static str, Option<u32>)> {
+    let read = |path: &str| std::fs::read_to_string(path).ok();
+    let cgroup = read("/sys/fs/cgroup/cpu.max")
+        .and_then(|cpu_max| parse_cgroup_v2_cpu_max(&cpu_max))
+        .or_else(|| {
+            let quota = read("/sys/fs/cgroup/cpu/cpu.cfs_quota_us")?;
+            let period = read("/sys/fs/cgroup/cpu/cpu.cfs_period_us")?;
+            parse_cgroup_v1_quota(&quota, &period)
+        });
+
+    vec![("cgroup quota", cgroup), ("nproc", command_u32("nproc", &[]))]
+}
+
+/// macOS: logical CPUs from `sysctl`.
+#[cfg(target_os = "macos")]
+fn os_cpu_thread_sources() -> Vec<(&'static str, Option<u32>)> {
+    vec![("sysctl", command_u32("sysctl", &["-n", "hw.logicalcpu"]))]
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn os_cpu_thread_sources() -> Vec<(&'static str, Option<u32>)> {
+    Vec::new()
+}
+
+/// Run a command and parse its output as a single number.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn command_u32(program: &str, args: &[&str]) -> Option<u32> {
+    let output = std::process::Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// Parse cgroup v2 `cpu.max` ("<quota> <period>", or "max <period>" when
+/// unlimited) into a CPU count, rounding partial CPUs up.
+#[cfg(any(target_os = "linux", test))]
+fn parse_cgroup_v2_cpu_max(cpu_max: &str) -> Option<u32> {
+    let mut parts = cpu_max.split_whitespace();
+    let quota = parts.next()?;
+    let period = parts.next().unwrap_or("100000");
+    parse_cgroup_v1_quota(quota, period)
+}
+
+/// Parse cgroup v1 `cpu.cfs_quota_us` / `cpu.cfs_period_us` into a CPU
+/// count, rounding partial CPUs up. A quota of -1 means unlimited.
+#[cfg(any(target_os = "linux", test))]
+fn parse_cgroup_v1_quota(quota: &str, period: &str) -> Option<u32> {
+    let quota: i64 = quota.trim().parse().ok()?;
+    let period: i64 = period.trim().parse().ok()?;
+    if quota <= 0 || period <= 0 {
+        return None;
+    }
+    u32::try_from((quota + period - 1) / period).ok()
+}
+
 /// CPU-based hardware detector.
 ///
 /// Detects CPU architecture, cores, SIMD capabilities, and RAM.
 /// Results are cached after the first detection.
 pub struct CpuHardwareDetector {
     profile: OnceLock<HardwareProfile>,
+    /// User-supplied profile that replaces the detected one.
+    override_profile: RwLock<Option<HardwareProfile>>,
 }
 
 impl CpuHardwareDetector {
@@ -24,6 +112,7 @@ impl CpuHardwareDetector {
     pub fn new() -> Self {
         Self {
             profile: OnceLock::new(),
+            override_profile: RwLock::new(None),
         }
     }
 
@@ -34,9 +123,7 @@ impl CpuHardwareDetector {
         let os = OsType::detect();
 
         // Get CPU thread count (logical processors)
-        let threads = std::thread::available_parallelism()
-            .map(|p| p.get() as u32)
-            .unwrap_or(1);
+        let threads = detect_cpu_threads();
 
         // Use thread count as core count since hyperthreading detection is unreliable
         // (Apple Silicon doesn't use HT, AMD has different HT ratios).
@@ -150,6 +237,82 @@ impl CpuHardwareDetector {
         ))
     }
 
+    /// Detect available memory from the `vm_stat` page counts.
+    #[cfg(target_os = "macos")]
+    fn detect_available_memory() -> Result<u64, DomainError> {
+        use std::process::Command;
+
+        let output = Command::new("vm_stat")
+            .output()
+            .map_err(|e| DomainError::Hardware(format!("Failed to run vm_stat: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(DomainError::Hardware("vm_stat command failed".to_string()));
+        }
+
+        let stats = String::from_utf8_lossy(&output.stdout);
+        let page_size = stats
+            .split("page size of ")
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(|size| size.parse::<u64>().ok())
+            .unwrap_or(4096);
+
+        let pages = |key: &str| -> u64 {
+            stats
+                .lines()
+                .find(|line| line.starts_with(key))
+                .and_then(|line| line.rsplit(':').next())
+                .and_then(|count| count.trim().trim_end_matches('.').parse().ok())
+                .unwrap_or(0)
+        };
+
+        let free_pages = pages("Pages free") + pages("Pages inactive") + pages("Pages speculative");
+        Ok(free_pages * page_size)
+    }
+
+    /// Detect available memory from `MemAvailable` in /proc/meminfo.
+    #[cfg(target_os = "linux")]
+    fn detect_available_memory() -> Result<u64, DomainError> {
+        let meminfo = std::fs::read_to_string("/proc/meminfo")
+            .map_err(|e| DomainError::Hardware(format!("Failed to read /proc/meminfo: {}", e)))?;
+
+        meminfo_kb(&meminfo, "MemAvailable:")
+            .map(|kb| kb * 1024)
+            .ok_or_else(|| {
+                DomainError::Hardware("Could not find MemAvailable in /proc/meminfo".to_string())
+            })
+    }
+
+    /// Detect available memory via wmic.
+    #[cfg(target_os = "windows")]
+    fn detect_available_memory() -> Result<u64, DomainError> {
+        use std::process::Command;
+
+        let output = Command::new("wmic")
+            .args(["OS", "get", "FreePhysicalMemory"])
+            .output()
+            .map_err(|e| DomainError::Hardware(format!("Failed to run wmic: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(DomainError::Hardware("wmic command failed".to_string()));
+        }
+
+        // Reported in KB
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .find_map(|line| line.parse::<u64>().ok())
+            .map(|kb| kb * 1024)
+            .ok_or_else(|| DomainError::Hardware("Could not parse wmic output".to_string()))
+    }
+
+    /// Available memory is not measured on unsupported platforms; assume total RAM.
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    fn detect_available_memory() -> Result<u64, DomainError> {
+        Self::detect_ram()
+    }
+
     /// Detect total system RAM (fallback for unsupported platforms).
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     fn detect_ram() -> Result<u64, DomainError> {
@@ -158,6 +321,16 @@ impl CpuHardwareDetector {
     }
 }
 
+/// Read a `Key:  N kB` value from /proc/meminfo content.
+#[cfg(any(target_os = "linux", test))]
+fn meminfo_kb(meminfo: &str, key: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find(|line| line.starts_with(key))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|kb| kb.parse().ok())
+}
+
 impl Default for CpuHardwareDetector {
     fn default() -> Self {
         Self::new()
@@ -166,14 +339,7 @@ impl Default for CpuHardwareDetector {
 
 impl HardwareDetector for CpuHardwareDetector {
     fn detect(&self) -> Result<HardwareProfile, DomainError> {
-        if let Some(profile) = self.profile.get() {
-            return Ok(profile.clone());
-        }
-
-        let profile = Self::detect_hardware()?;
-        // Try to set, but don't fail if another thread beat us
-        let _ = self.profile.set(profile.clone());
-        Ok(profile)
+        self.profile()
     }
 
     fn recommend_model(&self, catalog: &ModelCatalog) -> Result<ModelRecommendation, DomainError> {
@@ -222,12 +388,24 @@ impl HardwareDetector for CpuHardwareDetector {
         };
 
         // Verify the model exists in catalog
-        if catalog.get(model_id).is_none() {
-            return Err(DomainError::ModelNotFound(format!(
+        let info = catalog.get(model_id).ok_or_else(|| {
+            DomainError::ModelNotFound(format!(
                 "Recommended model '{}' not found in catalog",
                 model_id
-            )));
-        }
+            ))
+        })?;
+
+        // Use a quantization the model actually offers
+        let quantization = info
+            .variant(quantization)
+            .or_else(|| info.default_variant())
+            .map(|v| v.quantization)
+            .ok_or_else(|| {
+                DomainError::ModelNotFound(format!(
+                    "Recommended model '{}' has no variants",
+                    model_id
+                ))
+            })?;
 
         Ok(ModelRecommendation {
             model_id: model_id.to_string(),
@@ -236,24 +414,63 @@ impl HardwareDetector for CpuHardwareDetector {
         })
     }
 
-    fn profile(&self) -> Result<&HardwareProfile, DomainError> {
+    fn available_memory(&self) -> Result<u64, DomainError> {
+        Self::detect_available_memory()
+    }
+
+    fn profile(&self) -> Result<HardwareProfile, DomainError> {
+        if let Some(profile) = self.override_profile.read().as_ref() {
+            return Ok(profile.clone());
+        }
         if let Some(profile) = self.profile.get() {
-            return Ok(profile);
+            return Ok(profile.clone());
         }
 
-        // Need to detect first
         let profile = Self::detect_hardware()?;
-        // This might race, but that's fine - we'll get a valid profile either way
-        let _ = self.profile.set(profile);
-        self.profile
-            .get()
-            .ok_or_else(|| DomainError::Hardware("Failed to cache hardware profile".to_string()))
+        // Try to set, but don't fail if another thread beat us
+        let _ = self.profile.set(profile.clone());
+        Ok(profile)
+    }
+
+    fn set_override(&self, profile: Option<HardwareProfile>) {
+        match &profile {
+            Some(profile) => info!(
+                cores = profile.cores,
+                ram_gb = profile.ram_gb(),
+                "Hardware profile overridden"
+            ),
+            None => info!("Hardware profile override cleared"),
+        }
+        *self.override_profile.write() = profile;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::model::{ModelInfo, ModelVariant};
+
+    #[test]
+    fn test_parse_cgroup_cpu_quota() {
+        // v2: two and a half CPUs round up to three
+        assert_eq!(parse_cgroup_v2_cpu_max("250000 100000\n"), Some(3));
+        assert_eq!(parse_cgroup_v2_cpu_max("400000 100000"), Some(4));
+        assert_eq!(parse_cgroup_v2_cpu_max("50000 100000"), Some(1));
+        assert_eq!(parse_cgroup_v2_cpu_max("max 100000"), None);
+        assert_eq!(parse_cgroup_v2_cpu_max(""), None);
+
+        // v1
+        assert_eq!(parse_cgroup_v1_quota("200000\n", "100000\n"), Some(2));
+        assert_eq!(parse_cgroup_v1_quota("-1", "100000"), None);
+        assert_eq!(parse_cgroup_v1_quota("100000", "0"), None);
+        assert_eq!(parse_cgroup_v1_quota("garbage", "100000"), None);
+    }
+
+    #[test]
+    fn test_detect_cpu_threads_in_range() {
+        let threads = detect_cpu_threads();
+        assert!((1..=MAX_CPU_THREADS).contains(&threads));
+    }
 
     #[test]
     fn test_hardware_detection() {
@@ -275,4 +492,83 @@ mod tests {
         assert_eq!(profile1.threads, profile2.threads);
         assert_eq!(profile1.ram_bytes, profile2.ram_bytes);
     }
+
+    #[test]
+    fn test_meminfo_kb() {
+        let meminfo = "MemTotal:       16318480 kB\n\
+                       MemFree:         1234567 kB\n\
+                       MemAvailable:    8123456 kB\n";
+        assert_eq!(meminfo_kb(meminfo, "MemAvailable:"), Some(8_123_456));
+        assert_eq!(meminfo_kb(meminfo, "MemTotal:"), Some(16_318_480));
+        assert_eq!(meminfo_kb(meminfo, "SwapTotal:"), None);
+    }
+
+    #[test]
+    fn test_recommendation_uses_offered_variant() {
+        let catalog = |quants: &[Quantization]| ModelCatalog {
+            version: 1,
+            language_minimums: Default::default(),
+            models: ["whisper-tiny", "whisper-base", "whisper-small"]
+                .iter()
+                .map(|id| ModelInfo {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    description: String::new(),
+                    min_ram_gb: 1,
+                    english_only: false,
+                    variants: quants
+                        .iter()
+                        .map(|&quantization| ModelVariant {
+                            quantization,
+                            size_bytes: 0,
+                            sha256: String::new(),
+                            url: String::new(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        };
+        let detector = CpuHardwareDetector::new();
+
+        let recommendation = detector
+            .recommend_model(&catalog(&[Quantization::Q4_0]))
+            .unwrap();
+        assert_eq!(recommendation.quantization, Quantization::Q4_0);
+
+        let recommendation = detector
+            .recommend_model(&catalog(&[Quantization::Q4_0, Quantization::Q5_1]))
+            .unwrap();
+        assert_eq!(recommendation.quantization, Quantization::Q5_1);
+
+        assert!(detector.recommend_model(&catalog(&[])).is_err());
+    }
+
+    #[test]
+    fn test_override_takes_precedence() {
+        let detector = CpuHardwareDetector::new();
+        let detected = detector.detect().unwrap();
+
+        let low_end = HardwareProfile {
+            cores: 2,
+            threads: 2,
+            ram_bytes: 2 * 1024 * 1024 * 1024,
+            ..detected.clone()
+        };
+        detector.set_override(Some(low_end.clone()));
+        assert_eq!(detector.detect().unwrap(), low_end);
+        assert_eq!(detector.profile().unwrap(), low_end);
+
+        // The empty catalog makes the error name the model the override led to
+        let err = detector
+            .recommend_model(&ModelCatalog {
+                version: 1,
+                models: Vec::new(),
+                language_minimums: Default::default(),
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("whisper-tiny"));
+
+        detector.set_override(None);
+        assert_eq!(detector.detect().unwrap(), detected);
+    }
 }
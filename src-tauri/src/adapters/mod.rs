@@ -4,12 +4,18 @@ pub mod hardware_detector;
 pub mod model_manager;
 pub mod output_manager;
 pub mod privacy_guard;
+pub mod recording_store;
+pub mod stats_store;
+pub mod transcript_sink;
 pub mod whisper_cpp;
 
 pub use audio_cpal::CpalAudioManager;
 pub use config_store::TomlConfigStore;
-pub use hardware_detector::CpuHardwareDetector;
+pub use hardware_detector::{detect_cpu_threads, CpuHardwareDetector};
 pub use model_manager::LocalModelManager;
 pub use output_manager::ClipboardOutputManager;
 pub use privacy_guard::PrivacyGuard;
+pub use recording_store::EncryptedRecordingStore;
+pub use stats_store::{TomlBenchmarkStore, TomlUsageStatsStore};
+pub use transcript_sink::JsonlTranscriptSink;
 pub use whisper_cpp::WhisperCppTranscriber;
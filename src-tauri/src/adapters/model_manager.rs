@@ -1,26 +1,170 @@
 use std::fs::{self, File};
 use std::io::{BufReader, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use async_trait::async_trait;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use sha2::{Digest, Sha256};
+use tokio::sync::Notify;
 use tracing::{debug, info, warn};
 
 use crate::adapters::PrivacyGuard;
 use crate::domain::{
-    DomainError, DownloadProgress, InstalledModel, ModelCatalog, Quantization,
+    DomainError, DownloadProgress, DownloadQueueEntry, InstalledModel, ModelCatalog,
+    ModelFileKind, ModelsDirEntry, Quantization,
 };
 use crate::ports::{HttpClient, ModelManager};
 
 /// Embedded model catalog JSON.
 const CATALOG_JSON: &str = include_str!("../../resources/model_catalog.json");
 
+/// FIFO of pending downloads; only the front entry transfers at a time.
+#[derive(Default)]
+struct DownloadQueue {
+    entries: Mutex<Vec<DownloadProgress>>,
+    changed: Notify,
+}
+
+impl DownloadQueue {
+    fn position(
+        entries: &[DownloadProgress],
+        model_id: &str,
+        quant: Quantization,
+    ) -> Option<usize> {
+        entries
+            .iter()
+            .position(|e| e.model_id == model_id && e.quantization == quant)
+    }
+
+    /// Append a download, rejecting duplicates.
+    fn push(&self, model_id: &str, quant: Quantization) -> Result<(), DomainError> {
+        let mut entries = self.entries.lock();
+        if Self::position(&entries, model_id, quant).is_some() {
+            return Err(DomainError::ModelDownload(format!(
+                "{}-{} is already queued",
+                model_id, quant
+            )));
+        }
+        entries.push(DownloadProgress::new(model_id.to_string(), quant));
+        Ok(())
+    }
+
+    /// Wait until the download reaches the front of the queue.
+    /// Fails if it was removed while waiting.
+    async fn wait_turn(&self, model_id: &str, quant: Quantization) -> Result<(), DomainError> {
+        loop {
+            // Register before checking so a change in between isn't missed
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            match Self::position(&self.entries.lock(), model_id, quant) {
+                Some(0) => return Ok(()),
+                Some(_) => {}
+                None => {
+                    return Err(DomainError::ModelDownload(format!(
+                        "{}-{} was removed from the queue",
+                        model_id, quant
+                    )))
+                }
+            }
+            changed.await;
+        }
+    }
+
+    fn update(&self, model_id: &str, quant: Quantization, downloaded: u64, total: u64) {
+        let mut entries = self.entries.lock();
+        if let Some(i) = Self::position(&entries, model_id, quant) {
+            entries[i].update(downloaded, total);
+        }
+    }
+
+    /// Drop a download from the queue and wake waiting downloads.
+    fn finish(&self, model_id: &str, quant: Quantization) {
+        let mut entries = self.entries.lock();
+        if let Some(i) = Self::position(&entries, model_id, quant) {
+            entries.remove(i);
+        }
+        drop(entries);
+        self.changed.notify_waiters();
+    }
+
+    fn snapshot(&self) -> Vec<DownloadQueueEntry> {
+        self.entries
+            .lock()
+            .iter()
+            .enumerate()
+            .map(|(i, progress)| DownloadQueueEntry {
+                progress: progress.clone(),
+                active: i == 0,
+            })
+            .collect()
+    }
+
+    /// Index of a queued (not active) download.
+    fn queued_position(
+        entries: &[DownloadProgress],
+        model_id: &str,
+        quant: Quantization,
+    ) -> Result<usize, DomainError> {
+        match Self::position(entries, model_id, quant) {
+            Some(0) => Err(DomainError::ModelDownload(format!(
+                "{}-{} is already downloading",
+                model_id, quant
+            ))),
+            Some(i) => Ok(i),
+            None => Err(DomainError::ModelNotFound(format!(
+                "{}-{} is not queued",
+                model_id, quant
+            ))),
+        }
+    }
+
+    fn reorder(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+        position: usize,
+    ) -> Result<(), DomainError> {
+        let mut entries = self.entries.lock();
+        let from = Self::queued_position(&entries, model_id, quant)?;
+        let entry = entries.remove(from);
+        let to = position.clamp(1, entries.len());
+        entries.insert(to, entry);
+        Ok(())
+    }
+
+    fn remove(&self, model_id: &str, quant: Quantization) -> Result<(), DomainError> {
+        let mut entries = self.entries.lock();
+        let i = Self::queued_position(&entries, model_id, quant)?;
+        entries.remove(i);
+        drop(entries);
+        self.changed.notify_waiters();
+        Ok(())
+    }
+}
+
+/// Removes a download from the queue when its `download` call ends,
+/// including when the call is cancelled.
+struct QueueSlot<'a> {
+    queue: &'a DownloadQueue,
+    model_id: &'a str,
+    quant: Quantization,
+}
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.queue.finish(self.model_id, self.quant);
+    }
+}
+
 /// Local model manager using filesystem storage.
 pub struct LocalModelManager {
     catalog: ModelCatalog,
     models_dir: PathBuf,
     installed: RwLock<Vec<InstalledModel>>,
+    downloads: Arc<DownloadQueue>,
 }
 
 impl LocalModelManager {
@@ -37,6 +181,7 @@ impl LocalModelManager {
             catalog,
             models_dir,
             installed: RwLock::new(Vec::new()),
+            downloads: Arc::new(DownloadQueue::default()),
         };
 
         // Scan for installed models
@@ -102,15 +247,59 @@ impl LocalModelManager {
             path: path.clone(),
             sha256: variant.sha256.clone(),
             size_bytes: size,
+            verification_failed: Self::corrupt_marker(path).exists(),
         })
     }
 
+    /// Forget a failed verification of the model at `path`.
+    fn clear_verification_failed(&self, model_id: &str, quant: Quantization, path: &Path) {
+        for model in self.installed.write().iter_mut() {
+            if model.id == model_id && model.quantization == quant {
+                model.verification_failed = false;
+            }
+        }
+        let _ = fs::remove_file(Self::corrupt_marker(path));
+    }
+
+    /// Marker file persisting a failed verification of the model at `path`.
+    fn corrupt_marker(path: &Path) -> PathBuf {
+        path.with_extension("corrupt")
+    }
+
     /// Get the path for a model file.
     fn get_model_path(&self, model_id: &str, quant: Quantization) -> PathBuf {
         self.models_dir
             .join(format!("{}-{}.bin", model_id, quant.suffix()))
     }
 
+    /// Classify a models directory file by name.
+    fn classify_file(&self, name: &str) -> ModelFileKind {
+        let installed = self
+            .installed
+            .read()
+            .iter()
+            .any(|m| m.path.file_name().and_then(|n| n.to_str()) == Some(name));
+        if installed {
+            ModelFileKind::InstalledModel
+        } else if name.ends_with(".download") || name.ends_with(".import") {
+            ModelFileKind::PartialDownload
+        } else if name.ends_with(".corrupt") {
+            ModelFileKind::CorruptMarker
+        } else {
+            ModelFileKind::Unknown
+        }
+    }
+
+    /// Map a transfer failure to `ModelDownload`, keeping privacy blocks distinct.
+    fn download_error(model_id: &str, quant: Quantization, err: DomainError) -> DomainError {
+        match err {
+            DomainError::HttpRequest(message) | DomainError::Io(message) => {
+                DomainError::ModelDownload(format!("{}-{}: {}", model_id, quant, message))
+            }
+            other => other,
+        }
+    }
+
     /// Calculate SHA-256 hash of a file.
     fn calculate_sha256(path: &PathBuf) -> Result<String, DomainError> {
         let file = File::open(path)?;
@@ -182,6 +371,15 @@ impl ModelManager for LocalModelManager {
 
         let target_path = self.get_model_path(model_id, quant);
 
+        // Wait for earlier downloads to finish
+        self.downloads.push(model_id, quant)?;
+        let _slot = QueueSlot {
+            queue: &self.downloads,
+            model_id,
+            quant,
+        };
+        self.downloads.wait_turn(model_id, quant).await?;
+
         info!(
             model_id = model_id,
             quant = %quant,
@@ -190,21 +388,24 @@ impl ModelManager for LocalModelManager {
             "Starting model download"
         );
 
-        // Create progress wrapper
-        let progress_wrapper: Option<Box<dyn Fn(u64, u64) + Send + Sync>> = progress.map(|p| {
-            let model_id = model_id.to_string();
-            let wrapper: Box<dyn Fn(u64, u64) + Send + Sync> = Box::new(move |downloaded, total| {
-                let mut dp = DownloadProgress::new(model_id.clone(), quant);
-                dp.update(downloaded, total);
-                p(dp);
-            });
-            wrapper
-        });
+        // Create progress wrapper (also tracks progress in the queue)
+        let queue = Arc::clone(&self.downloads);
+        let owned_id = model_id.to_string();
+        let progress_wrapper: Option<Box<dyn Fn(u64, u64) + Send + Sync>> =
+            Some(Box::new(move |downloaded, total| {
+                queue.update(&owned_id, quant, downloaded, total);
+                if let Some(p) = &progress {
+                    let mut dp = DownloadProgress::new(owned_id.clone(), quant);
+                    dp.update(downloaded, total);
+                    p(dp);
+                }
+            }));
 
         // Download via PrivacyGuard
         PrivacyGuard::global()
             .download_file(&variant.url, &target_path, progress_wrapper)
-            .await?;
+            .await
+            .map_err(|e| Self::download_error(model_id, quant, e))?;
 
         // Verify checksum
         info!(target = ?target_path, "Download complete, verifying checksum");
@@ -219,16 +420,20 @@ impl ModelManager for LocalModelManager {
         }
 
         let size = fs::metadata(&target_path)?.len();
+        let _ = fs::remove_file(Self::corrupt_marker(&target_path));
         let installed = InstalledModel {
             id: model_id.to_string(),
             quantization: quant,
             path: target_path,
             sha256: variant.sha256.clone(),
             size_bytes: size,
+            verification_failed: false,
         };
 
-        // Add to installed list
-        self.installed.write().push(installed.clone());
+        // Add to installed list, replacing a previous (corrupt) copy
+        let mut models = self.installed.write();
+        models.retain(|m| !(m.id == model_id && m.quantization == quant));
+        models.push(installed.clone());
 
         info!(
             model_id = model_id,
@@ -240,6 +445,69 @@ impl ModelManager for LocalModelManager {
         Ok(installed)
     }
 
+    fn install_from_file(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+        source: &Path,
+    ) -> Result<InstalledModel, DomainError> {
+        let variant = self
+            .catalog
+            .get(model_id)
+            .and_then(|info| info.variant(quant))
+            .ok_or_else(|| DomainError::ModelNotFound(format!("{}-{}", model_id, quant)))?;
+
+        let target_path = self.get_model_path(model_id, quant);
+        // Not `.download`, which a queued download of the same model writes to
+        let temp_path = target_path.with_extension("import");
+        fs::copy(source, &temp_path)?;
+
+        let actual_sha256 = Self::calculate_sha256(&temp_path)?;
+        if actual_sha256 != variant.sha256 {
+            let _ = fs::remove_file(&temp_path);
+            return Err(DomainError::ModelVerification {
+                expected: variant.sha256.clone(),
+                actual: actual_sha256,
+            });
+        }
+        fs::rename(&temp_path, &target_path)?;
+        let _ = fs::remove_file(Self::corrupt_marker(&target_path));
+
+        let installed = InstalledModel {
+            id: model_id.to_string(),
+            quantization: quant,
+            path: target_path.clone(),
+            sha256: variant.sha256.clone(),
+            size_bytes: fs::metadata(&target_path)?.len(),
+            verification_failed: false,
+        };
+        let mut models = self.installed.write();
+        models.retain(|m| m.path != target_path);
+        models.push(installed.clone());
+
+        info!(model_id, quant = %quant, source = ?source, "Model installed from file");
+        Ok(installed)
+    }
+
+    fn download_queue(&self) -> Vec<DownloadQueueEntry> {
+        self.downloads.snapshot()
+    }
+
+    fn reorder_download(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+        position: usize,
+    ) -> Result<(), DomainError> {
+        self.downloads.reorder(model_id, quant, position)
+    }
+
+    fn remove_from_queue(&self, model_id: &str, quant: Quantization) -> Result<(), DomainError> {
+        self.downloads.remove(model_id, quant)?;
+        info!(model_id, quant = %quant, "Download removed from queue");
+        Ok(())
+    }
+
     fn verify(&self, model_id: &str, quant: Quantization) -> Result<bool, DomainError> {
         let path = self
             .model_path(model_id, quant)
@@ -266,17 +534,33 @@ impl ModelManager for LocalModelManager {
                 actual = %actual_sha256,
                 "Model verification failed"
             );
+        } else {
+            // e.g. the file was repaired since it was marked
+            self.clear_verification_failed(model_id, quant, &path);
         }
 
         Ok(valid)
     }
 
+    fn mark_verification_failed(&self, model_id: &str, quant: Quantization) {
+        for model in self.installed.write().iter_mut() {
+            if model.id == model_id && model.quantization == quant {
+                model.verification_failed = true;
+                // Remembered across restarts until the model is replaced
+                if let Err(e) = fs::write(Self::corrupt_marker(&model.path), b"") {
+                    warn!(error = %e, model_id, "Failed to persist verification failure");
+                }
+            }
+        }
+    }
+
     fn delete(&self, model_id: &str, quant: Quantization) -> Result<(), DomainError> {
         let path = self
             .model_path(model_id, quant)
             .ok_or_else(|| DomainError::ModelNotFound(format!("{}-{}", model_id, quant)))?;
 
         fs::remove_file(&path)?;
+        let _ = fs::remove_file(Self::corrupt_marker(&path));
 
         // Remove from installed list
         let mut installed = self.installed.write();
@@ -289,11 +573,64 @@ impl ModelManager for LocalModelManager {
     fn models_dir(&self) -> PathBuf {
         self.models_dir.clone()
     }
+
+    fn list_dir_contents(&self) -> Result<Vec<ModelsDirEntry>, DomainError> {
+        if !self.models_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.models_dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            entries.push(ModelsDirEntry {
+                kind: self.classify_file(&name),
+                name,
+                size_bytes: metadata.len(),
+            });
+        }
+
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    fn delete_dir_file(&self, name: &str) -> Result<(), DomainError> {
+        // Only plain names inside the models directory
+        if Path::new(name).file_name().and_then(|n| n.to_str()) != Some(name) {
+            return Err(DomainError::Io(format!("Invalid file name: {}", name)));
+        }
+        let path = self.models_dir.join(name);
+        if !path.is_file() {
+            return Err(DomainError::Io(format!("No such file in models directory: {}", name)));
+        }
+
+        let downloading = self.downloads.snapshot().into_iter().any(|entry| {
+            let target = self.get_model_path(&entry.progress.model_id, entry.progress.quantization);
+            entry.active && target.with_extension("download") == path
+        });
+        if downloading {
+            return Err(DomainError::Io(format!("{} is being downloaded", name)));
+        }
+
+        let kind = self.classify_file(name);
+        fs::remove_file(&path)?;
+        if kind == ModelFileKind::InstalledModel {
+            self.installed.write().retain(|m| m.path != path);
+        }
+
+        info!(name, kind = ?kind, "Models directory file deleted");
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::normalize_language;
     use std::env;
 
     #[test]
@@ -305,6 +642,12 @@ mod tests {
         // Check that whisper-small exists
         let small = catalog.get("whisper-small");
         assert!(small.is_some());
+
+        // Language minimums use canonical codes and known models
+        for (language, model) in &catalog.language_minimums {
+            assert_eq!(normalize_language(language).as_deref(), Some(language.as_str()));
+            assert!(catalog.get(model).is_some(), "unknown model {}", model);
+        }
     }
 
     #[test]
@@ -318,4 +661,156 @@ mod tests {
         // Cleanup
         let _ = fs::remove_dir_all(&temp_dir);
     }
+
+    #[tokio::test]
+    async fn test_download_queue_order() {
+        let queue = DownloadQueue::default();
+        for id in ["a", "b", "c"] {
+            queue.push(id, Quantization::Q5_1).unwrap();
+        }
+        assert!(queue.push("a", Quantization::Q5_1).is_err());
+
+        let order = |queue: &DownloadQueue| -> Vec<String> {
+            queue.snapshot().into_iter().map(|e| e.progress.model_id).collect()
+        };
+
+        // The active download can't be moved or removed, and stays first
+        assert!(queue.reorder("a", Quantization::Q5_1, 2).is_err());
+        assert!(queue.remove("a", Quantization::Q5_1).is_err());
+        queue.reorder("c", Quantization::Q5_1, 0).unwrap();
+        assert_eq!(order(&queue), vec!["a", "c", "b"]);
+        assert!(queue.snapshot()[0].active);
+
+        // A removed download fails instead of waiting forever
+        queue.remove("b", Quantization::Q5_1).unwrap();
+        assert!(queue.wait_turn("b", Quantization::Q5_1).await.is_err());
+
+        queue.wait_turn("a", Quantization::Q5_1).await.unwrap();
+        queue.finish("a", Quantization::Q5_1);
+        queue.wait_turn("c", Quantization::Q5_1).await.unwrap();
+    }
+
+    #[test]
+    fn test_download_error_mapping() {
+        let err = LocalModelManager::download_error(
+            "whisper-small",
+            Quantization::Q5_1,
+            DomainError::HttpRequest("HTTP 404".to_string()),
+        );
+        assert!(matches!(err, DomainError::ModelDownload(ref m) if m.contains("HTTP 404")));
+
+        let err = LocalModelManager::download_error(
+            "whisper-small",
+            Quantization::Q5_1,
+            DomainError::NetworkBlocked {
+                reason: "local only".to_string(),
+            },
+        );
+        assert!(matches!(err, DomainError::NetworkBlocked { .. }));
+    }
+
+    #[test]
+    fn test_models_dir_contents() {
+        let temp_dir = env::temp_dir().join("opensay_models_dir_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let models_dir = temp_dir.join("models");
+        fs::create_dir_all(&models_dir).unwrap();
+        fs::write(models_dir.join("whisper-base-q5_1.bin"), b"model").unwrap();
+        fs::write(models_dir.join("whisper-small-q5_1.download"), b"part").unwrap();
+        fs::write(models_dir.join("whisper-tiny-q5_1.import"), b"imp").unwrap();
+        fs::write(models_dir.join("ggml-base.bin"), b"foreign model").unwrap();
+        fs::write(temp_dir.join("outside.txt"), b"keep").unwrap();
+
+        let manager = LocalModelManager::new(temp_dir.clone()).unwrap();
+        let contents = manager.list_dir_contents().unwrap();
+        let kinds: Vec<(&str, ModelFileKind, u64)> = contents
+            .iter()
+            .map(|e| (e.name.as_str(), e.kind, e.size_bytes))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("ggml-base.bin", ModelFileKind::Unknown, 13),
+                ("whisper-base-q5_1.bin", ModelFileKind::InstalledModel, 5),
+                ("whisper-small-q5_1.download", ModelFileKind::PartialDownload, 4),
+                ("whisper-tiny-q5_1.import", ModelFileKind::PartialDownload, 3),
+            ]
+        );
+
+        // Paths outside the directory are rejected
+        assert!(manager.delete_dir_file("../outside.txt").is_err());
+        assert!(temp_dir.join("outside.txt").exists());
+        assert!(manager.delete_dir_file("missing.bin").is_err());
+
+        manager.delete_dir_file("whisper-base-q5_1.bin").unwrap();
+        assert!(!manager.is_installed("whisper-base", Quantization::Q5_1));
+        manager.delete_dir_file("ggml-base.bin").unwrap();
+        assert_eq!(manager.list_dir_contents().unwrap().len(), 2);
+
+        // Cleanup
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_install_from_file_verifies_checksum() {
+        let temp_dir = env::temp_dir().join("opensay_install_from_file_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let manager = LocalModelManager::new(temp_dir.clone()).unwrap();
+
+        let source = temp_dir.join("bundled.bin");
+        fs::write(&source, b"not the real model").unwrap();
+        let result = manager.install_from_file("whisper-base", Quantization::Q5_1, &source);
+        assert!(matches!(result, Err(DomainError::ModelVerification { .. })));
+        assert!(!manager.is_installed("whisper-base", Quantization::Q5_1));
+        assert!(manager.list_dir_contents().unwrap().is_empty());
+
+        let unknown = manager.install_from_file("whisper-none", Quantization::Q5_1, &source);
+        assert!(matches!(unknown, Err(DomainError::ModelNotFound(_))));
+
+        // Cleanup
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_truncated_model_fails_verification() {
+        let temp_dir = env::temp_dir().join("opensay_truncated_model_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let models_dir = temp_dir.join("models");
+        fs::create_dir_all(&models_dir).unwrap();
+        // Right name, so it is listed as installed, but only a few bytes
+        fs::write(models_dir.join("whisper-base-q5_1.bin"), b"lmgg\x01\x00").unwrap();
+
+        let manager = LocalModelManager::new(temp_dir.clone()).unwrap();
+        assert!(manager.is_installed("whisper-base", Quantization::Q5_1));
+        assert!(!manager.verify("whisper-base", Quantization::Q5_1).unwrap());
+
+        manager.mark_verification_failed("whisper-base", Quantization::Q5_1);
+        let installed = manager.list_installed().unwrap();
+        assert_eq!(installed.len(), 1);
+        assert!(installed[0].verification_failed);
+
+        // Still flagged after a restart
+        let manager = LocalModelManager::new(temp_dir.clone()).unwrap();
+        assert!(manager.list_installed().unwrap()[0].verification_failed);
+        let contents = manager.list_dir_contents().unwrap();
+        let marker = contents.iter().find(|e| e.name == "whisper-base-q5_1.corrupt").unwrap();
+        assert_eq!(marker.kind, ModelFileKind::CorruptMarker);
+
+        // Passing verification later (e.g. a repaired file) clears the flag
+        let mut manager = manager;
+        let path = models_dir.join("whisper-base-q5_1.bin");
+        let repaired = LocalModelManager::calculate_sha256(&path).unwrap();
+        for model in manager.catalog.models.iter_mut().filter(|m| m.id == "whisper-base") {
+            model.variants[0].sha256 = repaired.clone();
+        }
+        assert!(manager.verify("whisper-base", Quantization::Q5_1).unwrap());
+        assert!(!manager.list_installed().unwrap()[0].verification_failed);
+        assert!(!models_dir.join("whisper-base-q5_1.corrupt").exists());
+
+        manager.delete("whisper-base", Quantization::Q5_1).unwrap();
+        assert!(manager.list_dir_contents().unwrap().is_empty());
+
+        // Cleanup
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
 }
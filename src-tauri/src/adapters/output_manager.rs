@@ -1,23 +1,29 @@
-use std::time::Duration;
+use std::process::Command;
+use std::time::{Duration, Instant};
 
 use arboard::Clipboard;
 use async_trait::async_trait;
 use enigo::{Direction, Enigo, Key, Keyboard, Settings};
-use parking_lot::Mutex;
-use tracing::{debug, info};
+use parking_lot::{Mutex, RwLock};
+use tracing::{debug, info, warn};
 
-use crate::domain::config::OutputConfig;
+use crate::domain::config::{OutputConfig, OutputMode};
 use crate::domain::error::DomainError;
+use crate::domain::SessionType;
 use crate::ports::OutputManager;
 
+/// How long `wtype` may take to type the paste shortcut.
+const WTYPE_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// macOS implementation of OutputManager using clipboard + simulated paste.
 ///
 /// Note: This replaces the user's clipboard content with the transcribed text.
 /// The original clipboard content is NOT restored to avoid race conditions
 /// where the user might paste before restoration completes.
 pub struct ClipboardOutputManager {
-    config: OutputConfig,
+    config: RwLock<OutputConfig>,
     clipboard: Mutex<Clipboard>,
+    session_type: SessionType,
 }
 
 impl ClipboardOutputManager {
@@ -26,12 +32,23 @@ impl ClipboardOutputManager {
         let clipboard = Clipboard::new()
             .map_err(|e| DomainError::Clipboard(format!("Failed to initialize clipboard: {}", e)))?;
 
+        let session_type = SessionType::detect();
+        if session_type == SessionType::Wayland {
+            info!("Wayland session detected, paste will be simulated via wtype");
+        }
+
         Ok(Self {
-            config,
+            config: RwLock::new(config),
             clipboard: Mutex::new(clipboard),
+            session_type,
         })
     }
 
+    /// Get the detected graphical session type.
+    pub fn session_type(&self) -> SessionType {
+        self.session_type
+    }
+
     /// Set text to clipboard.
     fn set_clipboard_text(&self, text: &str) -> Result<(), DomainError> {
         let mut clipboard = self.clipboard.lock();
@@ -39,9 +56,67 @@ impl ClipboardOutputManager {
             .set_text(text)
             .map_err(|e| DomainError::Clipboard(format!("Failed to set clipboard text: {}", e)))?;
         debug!("Set clipboard text ({} chars)", text.len());
+
+        if self.config.read().use_primary_selection {
+            Self::set_primary_selection(&mut clipboard, text);
+        }
         Ok(())
     }
 
+    /// Also set the PRIMARY selection (middle-click paste). Failures are only
+    /// logged since CLIPBOARD already holds the text.
+    #[cfg(target_os = "linux")]
+    fn set_primary_selection(clipboard: &mut Clipboard, text: &str) {
+        use arboard::{LinuxClipboardKind, SetExtLinux};
+
+        match clipboard.set().clipboard(LinuxClipboardKind::Primary).text(text) {
+            Ok(()) => debug!("Set primary selection ({} chars)", text.len()),
+            Err(e) => warn!(error = %e, "Failed to set primary selection"),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn set_primary_selection(_clipboard: &mut Clipboard, _text: &str) {}
+
+    /// Run a command and return its trimmed stdout, if it succeeded and
+    /// printed anything.
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+        let output = Command::new(program).args(args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!stdout.is_empty()).then_some(stdout)
+    }
+
+    /// Identifier of the frontmost application, see `OutputManager::frontmost_app`.
+    #[cfg(target_os = "macos")]
+    fn detect_frontmost_app(_session_type: SessionType) -> Option<String> {
+        Self::command_stdout(
+            "osascript",
+            &[
+                "-e",
+                "tell application \"System Events\" to get bundle identifier of \
+                 first application process whose frontmost is true",
+            ],
+        )
+    }
+
+    #[cfg(target_os = "linux")]
+    fn detect_frontmost_app(session_type: SessionType) -> Option<String> {
+        // Wayland doesn't expose the focused window to clients
+        if session_type == SessionType::Wayland {
+            return None;
+        }
+        Self::command_stdout("xdotool", &["getactivewindow", "getwindowclassname"])
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    fn detect_frontmost_app(_session_type: SessionType) -> Option<String> {
+        None
+    }
+
     /// Simulate Cmd+V paste on macOS.
     fn simulate_paste(&self) -> Result<(), DomainError> {
         let mut enigo = Enigo::new(&Settings::default())
@@ -63,6 +138,35 @@ impl ClipboardOutputManager {
         debug!("Simulated Cmd+V paste");
         Ok(())
     }
+
+    /// Simulate Ctrl+V paste on Wayland using `wtype` (virtual-keyboard protocol).
+    async fn simulate_paste_wayland() -> Result<(), DomainError> {
+        let child = tokio::process::Command::new("wtype")
+            .args(["-M", "ctrl", "v", "-m", "ctrl"])
+            .kill_on_drop(true)
+            .status();
+        let status = tokio::time::timeout(WTYPE_TIMEOUT, child)
+            .await
+            .map_err(|_| {
+                DomainError::InputSimulation("wtype timed out; use clipboard-only mode".to_string())
+            })?
+            .map_err(|e| {
+                warn!(error = %e, "wtype unavailable on Wayland session");
+                DomainError::InputSimulation(
+                    "Wayland synthetic input unsupported; use clipboard-only mode".to_string(),
+                )
+            })?;
+
+        if !status.success() {
+            return Err(DomainError::InputSimulation(format!(
+                "wtype failed ({}); use clipboard-only mode",
+                status
+            )));
+        }
+
+        debug!("Simulated Ctrl+V paste via wtype");
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -73,19 +177,42 @@ impl OutputManager for ClipboardOutputManager {
             return Ok(());
         }
 
+        let config = self.config.read().clone();
+
         info!("Injecting transcribed text ({} chars)", text.len());
 
         // Step 1: Write transcribed text to clipboard
         self.set_clipboard_text(text)?;
 
+        if config.mode == OutputMode::ClipboardOnly {
+            info!("Clipboard-only mode, skipping paste simulation");
+            return Ok(());
+        }
+
         // Step 2: Wait for clipboard to sync
-        let delay = Duration::from_millis(self.config.paste_delay_ms);
-        tokio::time::sleep(delay).await;
+        tokio::time::sleep(Duration::from_millis(config.paste_delay_ms)).await;
 
         // Step 3: Simulate paste (Cmd+V on macOS)
-        self.simulate_paste()?;
+        // enigo's X11 backend is ignored by Wayland compositors
+        if self.session_type == SessionType::Wayland {
+            Self::simulate_paste_wayland().await?;
+        } else {
+            self.simulate_paste()?;
+        }
 
         info!("Text injection completed successfully");
         Ok(())
     }
+
+    fn copy_to_clipboard(&self, text: &str) -> Result<(), DomainError> {
+        self.set_clipboard_text(text)
+    }
+
+    fn frontmost_app(&self) -> Option<String> {
+        Self::detect_frontmost_app(self.session_type)
+    }
+
+    fn update_config(&self, config: OutputConfig) {
+        *self.config.write() = config;
+    }
 }
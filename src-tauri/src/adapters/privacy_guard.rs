@@ -1,5 +1,5 @@
 use std::path::Path;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use async_trait::async_trait;
 use once_cell::sync::OnceCell;
@@ -11,9 +11,12 @@ use tracing::{info, warn};
 use url::Url;
 
 use crate::domain::config::PrivacyConfig;
-use crate::domain::DomainError;
+use crate::domain::{redact_secrets, DomainError};
 use crate::ports::HttpClient;
 
+/// Default size of the write buffer for downloads (2 MiB).
+const DEFAULT_DOWNLOAD_BUFFER_BYTES: usize = 2 * 1024 * 1024;
+
 /// Global singleton instance of PrivacyGuard.
 static INSTANCE: OnceCell<PrivacyGuard> = OnceCell::new();
 
@@ -24,6 +27,8 @@ pub struct PrivacyGuard {
     client: Client,
     local_only: AtomicBool,
     allowed_domains: RwLock<Vec<String>>,
+    /// Downloaded bytes collected in memory before each file write.
+    download_buffer_bytes: AtomicUsize,
 }
 
 impl PrivacyGuard {
@@ -70,6 +75,7 @@ impl PrivacyGuard {
             client,
             local_only: AtomicBool::new(local_only),
             allowed_domains: RwLock::new(allowed_domains),
+            download_buffer_bytes: AtomicUsize::new(DEFAULT_DOWNLOAD_BUFFER_BYTES),
         })
     }
 
@@ -93,33 +99,50 @@ impl PrivacyGuard {
         info!(allowed_domains = ?*guard, "PrivacyGuard allowed domains updated");
     }
 
+    /// Set how many downloaded bytes are buffered before writing to disk.
+    pub fn set_download_buffer_size(&self, bytes: usize) {
+        let previous = self.download_buffer_bytes.swap(bytes, Ordering::SeqCst);
+        if previous != bytes {
+            info!(bytes, "Download buffer size changed");
+        }
+    }
+
     /// Check if a URL is allowed based on current settings.
     fn is_url_allowed(&self, url: &str) -> Result<(), DomainError> {
         if self.local_only.load(Ordering::SeqCst) {
-            warn!(url = url, "Network request blocked: local-only mode enabled");
+            warn!(url = %redact_secrets(url), "Network request blocked: local-only mode enabled");
             return Err(DomainError::NetworkBlocked {
                 reason: "Local-only mode is enabled. All network requests are blocked.".to_string(),
             });
         }
 
-        let parsed = Url::parse(url).map_err(|e| DomainError::HttpRequest(e.to_string()))?;
+        let parsed = Url::parse(url).map_err(request_error)?;
         let host = parsed
             .host_str()
             .ok_or_else(|| DomainError::HttpRequest("Invalid URL: no host".to_string()))?;
 
         let allowed = self.allowed_domains.read();
         if !allowed.iter().any(|d| host == d || host.ends_with(&format!(".{}", d))) {
-            warn!(url = url, host = host, "Network request blocked: domain not in whitelist");
+            warn!(
+                url = %redact_secrets(url),
+                host = host,
+                "Network request blocked: domain not in whitelist"
+            );
             return Err(DomainError::NetworkBlocked {
                 reason: format!("Domain '{}' is not in the allowed list", host),
             });
         }
 
-        info!(url = url, "Network request allowed");
+        info!(url = %redact_secrets(url), "Network request allowed");
         Ok(())
     }
 }
 
+/// Map a request error, scrubbing secrets from the URL it usually contains.
+fn request_error(e: impl std::fmt::Display) -> DomainError {
+    DomainError::HttpRequest(redact_secrets(&e.to_string()).into_owned())
+}
+
 #[async_trait]
 impl HttpClient for PrivacyGuard {
     async fn get(&self, url: &str) -> Result<String, DomainError> {
@@ -130,20 +153,21 @@ impl HttpClient for PrivacyGuard {
             .get(url)
             .send()
             .await
-            .map_err(|e| DomainError::HttpRequest(e.to_string()))?;
+            .map_err(request_error)?;
 
         let status = response.status();
         if !status.is_success() {
             return Err(DomainError::HttpRequest(format!(
                 "HTTP {} for {}",
-                status, url
+                status,
+                redact_secrets(url)
             )));
         }
 
         response
             .text()
             .await
-            .map_err(|e| DomainError::HttpRequest(e.to_string()))
+            .map_err(request_error)
     }
 
     async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, DomainError> {
@@ -154,20 +178,21 @@ impl HttpClient for PrivacyGuard {
             .get(url)
             .send()
             .await
-            .map_err(|e| DomainError::HttpRequest(e.to_string()))?;
+            .map_err(request_error)?;
 
         let status = response.status();
         if !status.is_success() {
             return Err(DomainError::HttpRequest(format!(
                 "HTTP {} for {}",
-                status, url
+                status,
+                redact_secrets(url)
             )));
         }
 
         response
             .json()
             .await
-            .map_err(|e| DomainError::HttpRequest(e.to_string()))
+            .map_err(request_error)
     }
 
     async fn post_json<T: Serialize + Send + Sync, R: DeserializeOwned>(
@@ -183,20 +208,45 @@ impl HttpClient for PrivacyGuard {
             .json(body)
             .send()
             .await
-            .map_err(|e| DomainError::HttpRequest(e.to_string()))?;
+            .map_err(request_error)?;
 
         let status = response.status();
         if !status.is_success() {
             return Err(DomainError::HttpRequest(format!(
                 "HTTP {} for {}",
-                status, url
+                status,
+                redact_secrets(url)
             )));
         }
 
         response
             .json()
             .await
-            .map_err(|e| DomainError::HttpRequest(e.to_string()))
+            .map_err(request_error)
+    }
+
+    async fn check_bearer_token(&self, url: &str, token: &str) -> Result<bool, DomainError> {
+        self.is_url_allowed(url)?;
+
+        let response = self
+            .client
+            .get(url)
+            .bearer_auth(token)
+            .timeout(std::time::Duration::from_secs(15))
+            .send()
+            .await
+            .map_err(request_error)?;
+
+        let status = response.status();
+        match status {
+            s if s.is_success() => Ok(true),
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => Ok(false),
+            _ => Err(DomainError::HttpRequest(format!(
+                "HTTP {} for {}",
+                status,
+                redact_secrets(url)
+            ))),
+        }
     }
 
     async fn download_file(
@@ -216,13 +266,14 @@ impl HttpClient for PrivacyGuard {
             .timeout(std::time::Duration::from_secs(3600)) // 1 hour timeout for large models
             .send()
             .await
-            .map_err(|e| DomainError::HttpRequest(e.to_string()))?;
+            .map_err(request_error)?;
 
         let status = response.status();
         if !status.is_success() {
             return Err(DomainError::HttpRequest(format!(
                 "HTTP {} for {}",
-                status, url
+                status,
+                redact_secrets(url)
             )));
         }
 
@@ -242,8 +293,10 @@ impl HttpClient for PrivacyGuard {
             async move { let _ = tokio::fs::remove_file(&temp).await; }
         };
 
+        // Chunks are small; batch them into larger writes
+        let buffer_size = self.download_buffer_bytes.load(Ordering::SeqCst);
         let mut file = match tokio::fs::File::create(&temp_path).await {
-            Ok(f) => f,
+            Ok(f) => tokio::io::BufWriter::with_capacity(buffer_size, f),
             Err(e) => {
                 cleanup_temp().await;
                 return Err(DomainError::Io(e.to_string()));
@@ -276,6 +329,7 @@ impl HttpClient for PrivacyGuard {
             }
         }
 
+        // Write out whatever is still buffered
         if let Err(e) = file.flush().await {
             drop(file);
             cleanup_temp().await;
@@ -339,4 +393,13 @@ mod tests {
         let result = guard.is_url_allowed("https://cdn-lfs.huggingface.co/file");
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_bearer_check_respects_local_only() {
+        let guard = PrivacyGuard::try_with_config(true, vec!["api.openai.com".to_string()]).unwrap();
+        let result = guard
+            .check_bearer_token("https://api.openai.com/v1/models", "sk-test")
+            .await;
+        assert!(matches!(result, Err(DomainError::NetworkBlocked { .. })));
+    }
 }
@@ -0,0 +1,330 @@
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use once_cell::sync::OnceCell;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::hkdf;
+use ring::rand::{SecureRandom, SystemRandom};
+use tracing::{debug, info, warn};
+use zeroize::Zeroizing;
+
+use crate::domain::{AudioBuffer, DomainError, StoredRecording};
+use crate::ports::RecordingStore;
+
+/// Keychain entry holding the master secret.
+const KEYCHAIN_SERVICE: &str = "OpenSay";
+const KEYCHAIN_ACCOUNT: &str = "recording-key";
+
+/// File magic and format version.
+const MAGIC: &[u8; 4] = b"OSR1";
+/// Random bytes in a recording ID, hex-encoded in its file name.
+const ID_LEN: usize = 16;
+/// Magic, sample rate (u32), timestamp in ms (u64) and sample count (u64).
+/// Stored in the clear so recordings can be listed without decrypting, but
+/// authenticated as associated data.
+const HEADER_LEN: usize = 4 + 4 + 8 + 8;
+const EXTENSION: &str = "osr";
+
+/// Recording store that encrypts audio with ChaCha20-Poly1305.
+///
+/// The key is derived (HKDF-SHA256) from a random secret kept in the OS
+/// keychain, created on first use. Plaintext only ever exists in zeroized
+/// memory.
+pub struct EncryptedRecordingStore {
+    dir: PathBuf,
+    secret: OnceCell<Zeroizing<Vec<u8>>>,
+    rng: SystemRandom,
+}
+
+impl EncryptedRecordingStore {
+    /// Create a store in `data_dir/recordings`.
+    pub fn new(data_dir: PathBuf) -> Self {
+        Self {
+            dir: data_dir.join("recordings"),
+            secret: OnceCell::new(),
+            rng: SystemRandom::new(),
+        }
+    }
+
+    /// Derive the AEAD key from the master secret.
+    fn key(&self) -> Result<LessSafeKey, DomainError> {
+        let secret = self.secret.get_or_try_init(|| self.keychain_secret())?;
+        let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, b"opensay-recordings").extract(secret);
+        let okm = prk
+            .expand(&[b"chacha20-poly1305 v1"], &CHACHA20_POLY1305)
+            .map_err(|_| DomainError::Encryption("Key derivation failed".to_string()))?;
+        Ok(LessSafeKey::new(UnboundKey::from(okm)))
+    }
+
+    /// Read the master secret from the OS keychain, creating it on first use.
+    fn keychain_secret(&self) -> Result<Zeroizing<Vec<u8>>, DomainError> {
+        let keychain_error = |e: keyring::Error| {
+            DomainError::Encryption(format!("OS keychain unavailable: {}", e))
+        };
+        let entry =
+            keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT).map_err(keychain_error)?;
+
+        match entry.get_password() {
+            Ok(encoded) => decode_hex(&Zeroizing::new(encoded)),
+            Err(keyring::Error::NoEntry) => {
+                let mut secret = Zeroizing::new(vec![0u8; 32]);
+                self.rng.fill(&mut secret).map_err(|_| {
+                    DomainError::Encryption("Failed to generate key".to_string())
+                })?;
+                entry
+                    .set_password(&encode_hex(&secret))
+                    .map_err(keychain_error)?;
+                info!("Created recording encryption key in OS keychain");
+                Ok(secret)
+            }
+            Err(e) => Err(keychain_error(e)),
+        }
+    }
+
+    fn path(&self, id: &str) -> Result<PathBuf, DomainError> {
+        // IDs are generated by `save`; reject anything that could escape the directory
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(DomainError::Io(format!("Invalid recording ID: {}", id)));
+        }
+        Ok(self.dir.join(format!("{}.{}", id, EXTENSION)))
+    }
+}
+
+/// Parse a file header into `(sample_rate, timestamp_ms, sample_count)`.
+fn parse_header(header: &[u8]) -> Option<(u32, u64, u64)> {
+    if header.len() < HEADER_LEN || &header[..4] != MAGIC {
+        return None;
+    }
+    let sample_rate = u32::from_le_bytes(header[4..8].try_into().ok()?);
+    let timestamp_ms = u64::from_le_bytes(header[8..16].try_into().ok()?);
+    let sample_count = u64::from_le_bytes(header[16..24].try_into().ok()?);
+    Some((sample_rate, timestamp_ms, sample_count))
+}
+
+fn recording_info(
+    id: String,
+    sample_rate: u32,
+    timestamp_ms: u64,
+    samples: u64,
+) -> StoredRecording {
+    StoredRecording {
+        id,
+        timestamp_ms,
+        duration_secs: samples as f32 / sample_rate.max(1) as f32,
+        sample_rate,
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> Zeroizing<String> {
+    Zeroizing::new(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn decode_hex(encoded: &str) -> Result<Zeroizing<Vec<u8>>, DomainError> {
+    let invalid = || DomainError::Encryption("Malformed key in OS keychain".to_string());
+    if encoded.len() % 2 != 0 || !encoded.is_ascii() {
+        return Err(invalid());
+    }
+    (0..encoded.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&encoded[i..i + 2], 16).map_err(|_| invalid()))
+        .collect::<Result<Vec<u8>, _>>()
+        .map(Zeroizing::new)
+}
+
+impl RecordingStore for EncryptedRecordingStore {
+    fn save(&self, audio: &AudioBuffer) -> Result<StoredRecording, DomainError> {
+        let key = self.key()?;
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let sample_count = audio.samples().len() as u64;
+
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&audio.sample_rate().to_le_bytes());
+        header.extend_from_slice(&timestamp_ms.to_le_bytes());
+        header.extend_from_slice(&sample_count.to_le_bytes());
+
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| DomainError::Encryption("Failed to generate nonce".to_string()))?;
+
+        let mut data = Zeroizing::new(Vec::with_capacity(
+            audio.samples().len() * 2 + CHACHA20_POLY1305.tag_len(),
+        ));
+        for sample in audio.samples() {
+            data.extend_from_slice(&sample.to_le_bytes());
+        }
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(&header),
+            &mut *data,
+        )
+        .map_err(|_| DomainError::Encryption("Failed to encrypt recording".to_string()))?;
+
+        let mut file = header;
+        file.extend_from_slice(&nonce);
+        file.extend_from_slice(&data);
+
+        // Random rather than the timestamp, which two saves can share
+        let mut id_bytes = [0u8; ID_LEN];
+        self.rng
+            .fill(&mut id_bytes)
+            .map_err(|_| DomainError::Encryption("Failed to generate ID".to_string()))?;
+        let id = encode_hex(&id_bytes).to_string();
+
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(&id)?, file)?;
+
+        info!(id, samples = sample_count, "Recording stored encrypted");
+        Ok(recording_info(id, audio.sample_rate(), timestamp_ms, sample_count))
+    }
+
+    fn list(&self) -> Result<Vec<StoredRecording>, DomainError> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut recordings = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            let Some(id) = path.file_stem().and_then(|s| s.to_str()).map(String::from) else {
+                continue;
+            };
+
+            let mut header = [0u8; HEADER_LEN];
+            let parsed = File::open(&path)
+                .and_then(|mut f| f.read_exact(&mut header))
+                .ok()
+                .and_then(|_| parse_header(&header));
+            match parsed {
+                Some((sample_rate, timestamp_ms, samples)) => {
+                    recordings.push(recording_info(id, sample_rate, timestamp_ms, samples));
+                }
+                None => warn!(path = ?path, "Skipping unreadable stored recording"),
+            }
+        }
+
+        recordings.sort_by_key(|r| std::cmp::Reverse(r.timestamp_ms));
+        debug!(count = recordings.len(), "Listed stored recordings");
+        Ok(recordings)
+    }
+
+    fn load(&self, id: &str) -> Result<AudioBuffer, DomainError> {
+        let path = self.path(id)?;
+        if !path.exists() {
+            return Err(DomainError::Io(format!("Stored recording not found: {}", id)));
+        }
+
+        let mut file = Zeroizing::new(fs::read(&path)?);
+        let corrupt = || DomainError::Encryption(format!("Stored recording is corrupt: {}", id));
+        if file.len() < HEADER_LEN + NONCE_LEN {
+            return Err(corrupt());
+        }
+        let (header, rest) = file.split_at_mut(HEADER_LEN);
+        let (sample_rate, _, sample_count) = parse_header(header).ok_or_else(corrupt)?;
+        let (nonce, ciphertext) = rest.split_at_mut(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| corrupt())?;
+
+        let plaintext = self
+            .key()?
+            .open_in_place(nonce, Aad::from(&*header), ciphertext)
+            .map_err(|_| {
+                DomainError::Encryption(format!("Failed to decrypt stored recording: {}", id))
+            })?;
+        if plaintext.len() as u64 != sample_count * 2 {
+            return Err(corrupt());
+        }
+
+        let samples: Zeroizing<Vec<i16>> = Zeroizing::new(
+            plaintext
+                .chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]))
+                .collect(),
+        );
+        let mut buffer = AudioBuffer::with_capacity(sample_rate, samples.len());
+        buffer.push_samples(&samples);
+        Ok(buffer)
+    }
+
+    fn delete(&self, id: &str) -> Result<(), DomainError> {
+        let path = self.path(id)?;
+        if !path.exists() {
+            return Err(DomainError::Io(format!("Stored recording not found: {}", id)));
+        }
+        fs::remove_file(&path)?;
+        info!(id, "Stored recording deleted");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn test_store(dir: &std::path::Path, secret: &[u8]) -> EncryptedRecordingStore {
+        let store = EncryptedRecordingStore::new(dir.to_path_buf());
+        let _ = store.secret.set(Zeroizing::new(secret.to_vec()));
+        store
+    }
+
+    #[test]
+    fn test_roundtrip_and_tamper_detection() {
+        let temp_dir = env::temp_dir().join("opensay_recording_store_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let store = test_store(&temp_dir, &[7u8; 32]);
+        assert!(store.list().unwrap().is_empty());
+
+        let mut audio = AudioBuffer::new(16000);
+        audio.push_samples(&[1, -2, 300, i16::MIN, i16::MAX]);
+        let saved = store.save(&audio).unwrap();
+
+        // Audio is not stored in the clear
+        let on_disk = fs::read(store.path(&saved.id).unwrap()).unwrap();
+        assert!(!on_disk.windows(4).any(|w| w == [0x2c, 0x01, 0x00, 0x80]));
+
+        assert_eq!(store.list().unwrap(), vec![saved.clone()]);
+        assert_eq!(store.load(&saved.id).unwrap().samples(), audio.samples());
+
+        // Saves in the same millisecond get distinct IDs
+        let again = store.save(&audio).unwrap();
+        assert_ne!(again.id, saved.id);
+        store.delete(&again.id).unwrap();
+        assert!(store.delete(&again.id).is_err());
+        assert_eq!(store.list().unwrap(), vec![saved.clone()]);
+
+        // A different key can't decrypt it
+        let other = test_store(&temp_dir, &[8u8; 32]);
+        assert!(matches!(other.load(&saved.id), Err(DomainError::Encryption(_))));
+
+        // Any modified byte is detected
+        let mut tampered = on_disk.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        fs::write(store.path(&saved.id).unwrap(), tampered).unwrap();
+        assert!(store.load(&saved.id).is_err());
+
+        assert!(store.load("../config").is_err());
+
+        // Cleanup
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let bytes = [0u8, 1, 0xab, 0xff];
+        let encoded = encode_hex(&bytes);
+        assert_eq!(encoded.as_str(), "0001abff");
+        assert_eq!(decode_hex(&encoded).unwrap().as_slice(), &bytes);
+        assert!(decode_hex("abc").is_err());
+        assert!(decode_hex("zz").is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use std::fs;
+use std::path::PathBuf;
+
+use tracing::debug;
+
+use crate::domain::{BenchmarkCache, DomainError, UsageStats};
+use crate::ports::{BenchmarkStore, UsageStatsStore};
+
+/// TOML-based usage statistics store in the application data directory.
+pub struct TomlUsageStatsStore {
+    data_dir: PathBuf,
+}
+
+impl TomlUsageStatsStore {
+    /// Create a new TomlUsageStatsStore.
+    pub fn new(data_dir: PathBuf) -> Self {
+        Self { data_dir }
+    }
+}
+
+impl UsageStatsStore for TomlUsageStatsStore {
+    fn load(&self) -> Result<UsageStats, DomainError> {
+        let path = self.stats_path();
+
+        if !path.exists() {
+            return Ok(UsageStats::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let stats: UsageStats = toml::from_str(&content)?;
+        debug!(path = ?path, sessions = stats.sessions, "Usage stats loaded");
+        Ok(stats)
+    }
+
+    fn save(&self, stats: &UsageStats) -> Result<(), DomainError> {
+        fs::create_dir_all(&self.data_dir)?;
+
+        let content = toml::to_string_pretty(stats)?;
+        fs::write(self.stats_path(), content)?;
+        Ok(())
+    }
+
+    fn stats_path(&self) -> PathBuf {
+        self.data_dir.join("usage_stats.toml")
+    }
+}
+
+/// TOML-based model benchmark cache in the application data directory.
+pub struct TomlBenchmarkStore {
+    data_dir: PathBuf,
+}
+
+impl TomlBenchmarkStore {
+    /// Create a new TomlBenchmarkStore.
+    pub fn new(data_dir: PathBuf) -> Self {
+        Self { data_dir }
+    }
+
+    fn path(&self) -> PathBuf {
+        self.data_dir.join("benchmarks.toml")
+    }
+}
+
+impl BenchmarkStore for TomlBenchmarkStore {
+    fn load(&self) -> Result<BenchmarkCache, DomainError> {
+        let path = self.path();
+
+        if !path.exists() {
+            return Ok(BenchmarkCache::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let cache: BenchmarkCache = toml::from_str(&content)?;
+        debug!(path = ?path, results = cache.results.len(), "Benchmark cache loaded");
+        Ok(cache)
+    }
+
+    fn save(&self, cache: &BenchmarkCache) -> Result<(), DomainError> {
+        fs::create_dir_all(&self.data_dir)?;
+
+        let content = toml::to_string_pretty(cache)?;
+        fs::write(self.path(), content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{BenchmarkResult, DictationSummary, Quantization};
+    use std::env;
+
+    #[test]
+    fn test_usage_stats_roundtrip() {
+        let temp_dir = env::temp_dir().join("opensay_stats_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let store = TomlUsageStatsStore::new(temp_dir.clone());
+
+        // Missing file yields empty stats
+        assert_eq!(store.load().unwrap(), UsageStats::default());
+
+        let mut stats = UsageStats::default();
+        stats.record(&DictationSummary::new(
+            "one two three",
+            3.0,
+            100,
+            Some("fr".to_string()),
+            "whisper-base".to_string(),
+        ));
+        store.save(&stats).unwrap();
+
+        assert_eq!(store.load().unwrap(), stats);
+
+        // Cleanup
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_benchmark_cache_roundtrip() {
+        let temp_dir = env::temp_dir().join("opensay_benchmark_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        let store = TomlBenchmarkStore::new(temp_dir.clone());
+        assert_eq!(store.load().unwrap(), BenchmarkCache::default());
+
+        let mut cache = BenchmarkCache::default();
+        cache.invalidate_unless("arm64-8c8t-16gb-macOS-neon");
+        cache.insert(BenchmarkResult {
+            model: "whisper-small".to_string(),
+            quant: Quantization::Q5_1,
+            language: None,
+            realtime_factor: 0.25,
+            measured_at: 1_700_000_000_000,
+        });
+        cache.insert(BenchmarkResult {
+            model: "whisper-small".to_string(),
+            quant: Quantization::Q5_1,
+            language: Some("fr".to_string()),
+            realtime_factor: 0.3,
+            measured_at: 1_700_000_000_000,
+        });
+        store.save(&cache).unwrap();
+
+        assert_eq!(store.load().unwrap(), cache);
+
+        // Cleanup
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+}
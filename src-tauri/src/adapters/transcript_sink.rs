@@ -0,0 +1,88 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use parking_lot::Mutex;
+
+use crate::domain::{DomainError, TranscriptRecord};
+use crate::ports::TranscriptSink;
+
+/// Append-only JSON Lines file sink.
+#[derive(Default)]
+pub struct JsonlTranscriptSink {
+    /// Serializes writers so concurrent records never interleave.
+    write_lock: Mutex<()>,
+}
+
+impl JsonlTranscriptSink {
+    /// Create a new JsonlTranscriptSink.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn open(path: &Path) -> Result<File, DomainError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(OpenOptions::new().create(true).append(true).open(path)?)
+    }
+}
+
+impl TranscriptSink for JsonlTranscriptSink {
+    fn prepare(&self, path: &Path) -> Result<(), DomainError> {
+        Self::open(path).map(|_| ())
+    }
+
+    fn append(&self, path: &Path, record: &TranscriptRecord) -> Result<(), DomainError> {
+        self.append_text(path, &serde_json::to_string(record)?)
+    }
+
+    fn append_text(&self, path: &Path, text: &str) -> Result<(), DomainError> {
+        let mut line = text.trim_end_matches('\n').to_string();
+        line.push('\n');
+
+        let _guard = self.write_lock.lock();
+        let mut file = Self::open(path)?;
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_appends_one_json_line_per_record() {
+        let temp_dir = env::temp_dir().join("opensay_sink_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let path = temp_dir.join("nested").join("transcripts.jsonl");
+
+        let sink = JsonlTranscriptSink::new();
+        sink.prepare(&path).unwrap();
+        assert!(path.exists());
+
+        let record = TranscriptRecord {
+            timestamp: 1_700_000_000_000,
+            text: "hello\nworld".to_string(),
+            language: Some("en".to_string()),
+            duration_ms: 1500,
+            model: "whisper-small".to_string(),
+        };
+        sink.append(&path, &record).unwrap();
+        sink.append(&path, &record).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            r#"{"timestamp":1700000000000,"text":"hello\nworld","language":"en","duration_ms":1500,"model":"whisper-small"}"#
+        );
+
+        // Cleanup
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+}
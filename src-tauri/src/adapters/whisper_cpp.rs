@@ -1,18 +1,180 @@
-use std::path::Path;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use parking_lot::RwLock;
-use tracing::{debug, info};
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use parking_lot::{Mutex, RwLock};
+use tracing::{debug, info, warn};
+use whisper_rs::{
+    FullParams, SamplingStrategy, SegmentCallbackData, WhisperContext, WhisperContextParameters,
+};
 
-use crate::domain::{AudioBuffer, DomainError};
-use crate::ports::{BackendCapabilities, TranscribeConfig, Transcriber, TranscriptionResult};
+use crate::adapters::detect_cpu_threads;
+use crate::domain::{calculate_rms, AudioBuffer, DomainError, SamplingMode, TranscriptSegment};
+use crate::ports::{
+    BackendCapabilities, BackendStatus, SegmentListener, TranscribeConfig, Transcriber,
+    TranscriptionResult, WordTiming,
+};
+
+/// Length of the windows `transcribe_streaming` decodes one at a time.
+const STREAMING_CHUNK_SECS: usize = 10;
+
+/// How far each window reaches back into the previous one, so words cut at
+/// the seam are heard whole by one of them.
+const STREAMING_OVERLAP_SECS: usize = 1;
+
+/// Longest initial prompt whisper considers, in tokens (half its text
+/// context); anything before that would be cut off by whisper anyway.
+const MAX_PROMPT_TOKENS: usize = 224;
+
+/// Most words matched when joining the texts of overlapping windows.
+const MAX_SEAM_WORDS: usize = 8;
+
+/// Serializes model loads so concurrent calls for the same path build only one context.
+#[derive(Default)]
+struct LoadGuard {
+    /// Path loaded by the last successful load.
+    loaded_path: tokio::sync::Mutex<Option<PathBuf>>,
+}
+
+impl LoadGuard {
+    /// Run `load` while holding the guard, unless `path` is already loaded.
+    ///
+    /// A call that waited on a concurrent load of the same path becomes a no-op.
+    /// Returns whether `load` ran.
+    async fn run<F, Fut>(
+        &self,
+        path: &Path,
+        is_loaded: impl Fn() -> bool,
+        load: F,
+    ) -> Result<bool, DomainError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<(), DomainError>>,
+    {
+        let mut loaded_path = self.loaded_path.lock().await;
+        if loaded_path.as_deref() == Some(path) && is_loaded() {
+            debug!(path = ?path, "Model already loaded, skipping");
+            return Ok(false);
+        }
+
+        load().await?;
+        *loaded_path = Some(path.to_path_buf());
+        Ok(true)
+    }
+}
+
+/// Holds the current model context.
+///
+/// A transcription checks out its own `Arc` for the whole run, so replacing
+/// or clearing the slot only affects later transcriptions: an in-flight one
+/// finishes against its original context, which is freed once it completes.
+struct ContextSlot<T> {
+    current: RwLock<Option<Arc<T>>>,
+    /// Bumped on every replace/clear, to tell whether a checkout is stale.
+    generation: AtomicU64,
+}
+
+impl<T> Default for ContextSlot<T> {
+    fn default() -> Self {
+        Self {
+            current: RwLock::new(None),
+            generation: AtomicU64::new(0),
+        }
+    }
+}
+
+impl<T> ContextSlot<T> {
+    /// Get the current context and its generation.
+    fn checkout(&self) -> Option<(Arc<T>, u64)> {
+        let current = self.current.read();
+        current
+            .clone()
+            .map(|ctx| (ctx, self.generation.load(Ordering::Acquire)))
+    }
+
+    /// Make `ctx` current for subsequent checkouts.
+    fn replace(&self, ctx: T) {
+        let mut current = self.current.write();
+        *current = Some(Arc::new(ctx));
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Drop the current context. Returns whether one was set.
+    fn clear(&self) -> bool {
+        let mut current = self.current.write();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        current.take().is_some()
+    }
+
+    /// Check whether a checkout of `generation` is still current.
+    fn is_current(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::Acquire) == generation
+    }
+
+    fn is_set(&self) -> bool {
+        self.current.read().is_some()
+    }
+}
+
+/// Abort flags of running decodes, polled by whisper's abort callback.
+#[derive(Default)]
+struct AbortRegistry {
+    running: Mutex<Vec<Arc<AtomicBool>>>,
+}
+
+impl AbortRegistry {
+    /// Register a decode, stopped once `flag` is set, until the returned
+    /// handle is dropped.
+    fn register(&self, flag: Arc<AtomicBool>) -> AbortHandle<'_> {
+        self.running.lock().push(Arc::clone(&flag));
+        AbortHandle {
+            registry: self,
+            flag,
+        }
+    }
+
+    /// Ask every running decode to stop. Returns how many there were.
+    fn abort_all(&self) -> usize {
+        let running = self.running.lock();
+        for flag in running.iter() {
+            flag.store(true, Ordering::Relaxed);
+        }
+        running.len()
+    }
+}
+
+/// A registered decode's abort flag.
+struct AbortHandle<'a> {
+    registry: &'a AbortRegistry,
+    flag: Arc<AtomicBool>,
+}
+
+impl AbortHandle<'_> {
+    /// Whisper abort callback, true once `abort_all` was called.
+    fn callback(&self) -> impl FnMut() -> bool + Send + 'static {
+        let flag = Arc::clone(&self.flag);
+        move || flag.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for AbortHandle<'_> {
+    fn drop(&mut self) {
+        self.registry
+            .running
+            .lock()
+            .retain(|flag| !Arc::ptr_eq(flag, &self.flag));
+    }
+}
 
 /// Transcriber implementation using whisper.cpp via whisper-rs.
 pub struct WhisperCppTranscriber {
-    context: RwLock<Option<Arc<WhisperContext>>>,
-    threads: u32,
+    context: ContextSlot<WhisperContext>,
+    load_guard: LoadGuard,
+    threads: AtomicU32,
+    aborts: AbortRegistry,
+    segment_listener: RwLock<Option<SegmentListener>>,
 }
 
 impl WhisperCppTranscriber {
@@ -22,9 +184,7 @@ impl WhisperCppTranscriber {
     /// 0 means auto-detect (cores - 1).
     pub fn new(threads: u32) -> Self {
         let actual_threads = if threads == 0 {
-            std::thread::available_parallelism()
-                .map(|p| std::cmp::max(1, p.get() as u32 - 1))
-                .unwrap_or(1)
+            detect_cpu_threads().saturating_sub(1).max(1)
         } else {
             threads
         };
@@ -32,15 +192,206 @@ impl WhisperCppTranscriber {
         info!(threads = actual_threads, "WhisperCppTranscriber created");
 
         Self {
-            context: RwLock::new(None),
-            threads: actual_threads,
+            context: ContextSlot::default(),
+            load_guard: LoadGuard::default(),
+            threads: AtomicU32::new(actual_threads),
+            aborts: AbortRegistry::default(),
+            segment_listener: RwLock::new(None),
+        }
+    }
+
+    /// Get the default number of threads used for transcription.
+    pub fn threads(&self) -> u32 {
+        self.threads.load(Ordering::Relaxed)
+    }
+
+    /// Change the default thread count for subsequent transcriptions.
+    pub fn set_threads(&self, threads: u32) {
+        let threads = threads.max(1);
+        self.threads.store(threads, Ordering::Relaxed);
+        info!(threads, "Transcriber thread count updated");
+    }
+
+    /// Build a whisper context from a model file and make it current.
+    async fn load_context(&self, path: &Path) -> Result<(), DomainError> {
+        info!(path = ?path, "Loading whisper model");
+
+        let path_str = path.to_string_lossy().to_string();
+
+        // Load model in blocking task (I/O bound)
+        let ctx = tokio::task::spawn_blocking(move || {
+            WhisperContext::new_with_params(&path_str, WhisperContextParameters::default())
+                .map_err(|e| DomainError::Model(format!("Failed to load model: {}", e)))
+        })
+        .await
+        .map_err(|e| DomainError::Model(format!("Task join error: {}", e)))??;
+
+        self.context.replace(ctx);
+
+        info!(path = ?path, "Whisper model loaded successfully");
+        Ok(())
+    }
+
+    /// Map the configured sampling mode onto whisper's, clamping the
+    /// candidate count.
+    fn sampling_strategy(mode: SamplingMode) -> SamplingStrategy {
+        if mode.clamped() != mode {
+            warn!(?mode, "Sampling candidates out of range, clamping");
+        }
+        match mode.clamped() {
+            SamplingMode::Greedy { best_of } => SamplingStrategy::Greedy {
+                best_of: best_of as i32,
+            },
+            SamplingMode::Beam { beam_size } => SamplingStrategy::BeamSearch {
+                beam_size: beam_size as i32,
+                // Not implemented by whisper.cpp; -1 means its default
+                patience: -1.0,
+            },
         }
     }
 
+    /// Drop words from the front of `prompt` until `count_tokens` says it
+    /// fits in `max_tokens`; the end of a prompt matters most to whisper.
+    /// Returns the fitted prompt, empty if not even its last word fits.
+    fn fit_prompt(prompt: &str, max_tokens: usize, count_tokens: impl Fn(&str) -> usize) -> &str {
+        let mut rest = prompt;
+        while !rest.is_empty() && count_tokens(rest) > max_tokens {
+            rest = rest
+                .split_once(char::is_whitespace)
+                .map_or("", |(_, tail)| tail.trim_start());
+        }
+        rest
+    }
+
     /// Convert i16 samples to f32 (whisper expects f32 samples in range [-1, 1]).
     fn convert_samples(samples: &[i16]) -> Vec<f32> {
         samples.iter().map(|&s| s as f32 / 32768.0).collect()
     }
+
+    /// Concatenate segment texts, dropping segments below `min_confidence`.
+    fn assemble_text(segments: &[(String, f32)], min_confidence: f32) -> String {
+        segments
+            .iter()
+            .filter(|(_, confidence)| *confidence >= min_confidence)
+            .map(|(text, _)| text.as_str())
+            .collect()
+    }
+
+    /// Append the text of the next window to `text`, dropping the words at
+    /// its start that repeat the end of `text` (heard twice in the overlap).
+    /// Words are compared ignoring case and punctuation.
+    fn join_windows(text: &mut String, next: &str) {
+        let normalize = |word: &str| -> String {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect()
+        };
+        let tail: Vec<String> = text.split_whitespace().map(normalize).collect();
+        let head: Vec<&str> = next.split_whitespace().collect();
+        let longest = MAX_SEAM_WORDS.min(tail.len()).min(head.len());
+        let seam = (1..=longest)
+            .rev()
+            .find(|&n| {
+                tail[tail.len() - n..]
+                    .iter()
+                    .zip(&head[..n])
+                    .all(|(a, b)| *a == normalize(b))
+            })
+            .unwrap_or(0);
+
+        for word in &head[seam..] {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(word);
+        }
+    }
+
+    /// Build result segments from whisper's `(t0, t1, text)` (timestamps in
+    /// 10 ms units). Timestamps are clamped so each segment starts no
+    /// earlier than the previous one ended.
+    fn timed_segments(raw: &[(i64, i64, String)]) -> Vec<TranscriptSegment> {
+        let mut last_end = 0;
+        raw.iter()
+            .enumerate()
+            .map(|(index, (t0, t1, text))| {
+                let start_ms = (t0.max(&0) * 10) as u64;
+                let start_ms = start_ms.max(last_end);
+                let end_ms = ((t1.max(&0) * 10) as u64).max(start_ms);
+                last_end = end_ms;
+                TranscriptSegment {
+                    index: index as u32,
+                    start_ms,
+                    end_ms,
+                    text: text.trim().to_string(),
+                }
+            })
+            .collect()
+    }
+
+    /// Group text tokens `(text, t0, t1, probability)` into words (timestamps
+    /// in 10 ms units). A token starting with whitespace begins a new word;
+    /// others continue the current one.
+    fn words_from_tokens(tokens: &[(String, i64, i64, f32)]) -> Vec<WordTiming> {
+        let mut words: Vec<WordTiming> = Vec::new();
+        let mut token_counts: Vec<usize> = Vec::new();
+        for (text, t0, t1, probability) in tokens {
+            let start_ms = (t0.max(&0) * 10) as u64;
+            let end_ms = ((t1.max(&0) * 10) as u64).max(start_ms);
+            match words.last_mut() {
+                Some(word) if !text.starts_with(char::is_whitespace) => {
+                    word.text.push_str(text);
+                    word.end_ms = word.end_ms.max(end_ms);
+                    word.probability += probability;
+                    *token_counts.last_mut().unwrap() += 1;
+                }
+                _ if text.trim().is_empty() => {}
+                _ => {
+                    words.push(WordTiming {
+                        text: text.trim_start().to_string(),
+                        start_ms,
+                        end_ms,
+                        probability: *probability,
+                    });
+                    token_counts.push(1);
+                }
+            }
+        }
+        for (word, count) in words.iter_mut().zip(token_counts) {
+            word.probability /= count as f32;
+        }
+        words
+    }
+
+    /// Convert a whisper segment callback (timestamps in 10 ms units).
+    fn segment_from_callback(data: SegmentCallbackData) -> TranscriptSegment {
+        TranscriptSegment {
+            index: data.segment.max(0) as u32,
+            start_ms: data.start_timestamp.max(0) as u64 * 10,
+            end_ms: data.end_timestamp.max(0) as u64 * 10,
+            text: data.text.trim().to_string(),
+        }
+    }
+
+    /// Cut `text` to at most `max_chars` characters, at the last whitespace
+    /// if there is one (text without spaces, e.g. CJK, is cut mid-run).
+    /// Returns whether it was truncated; 0 means unlimited.
+    fn truncate_output(text: &mut String, max_chars: usize) -> bool {
+        if max_chars == 0 {
+            return false;
+        }
+        let Some((cut, _)) = text.char_indices().nth(max_chars) else {
+            return false;
+        };
+        let end = match text[..cut].rfind(char::is_whitespace) {
+            Some(space) if space > 0 => space,
+            _ => cut,
+        };
+        text.truncate(end);
+        text.truncate(text.trim_end().len());
+        true
+    }
 }
 
 #[async_trait]
@@ -50,8 +401,20 @@ impl Transcriber for WhisperCppTranscriber {
         audio: &AudioBuffer,
         config: &TranscribeConfig,
     ) -> Result<TranscriptionResult, DomainError> {
-        let context = self.context.read().clone();
-        let ctx = context.ok_or_else(|| DomainError::Whisper("No model loaded".to_string()))?;
+        self.transcribe_cancellable(audio, config, Arc::default()).await
+    }
+
+    async fn transcribe_cancellable(
+        &self,
+        audio: &AudioBuffer,
+        config: &TranscribeConfig,
+        cancel: Arc<AtomicBool>,
+    ) -> Result<TranscriptionResult, DomainError> {
+        // Loads and unloads while this runs don't affect it (see `ContextSlot`)
+        let (ctx, generation) = self
+            .context
+            .checkout()
+            .ok_or_else(|| DomainError::Whisper("No model loaded".to_string()))?;
 
         // Validate sample rate
         if audio.sample_rate() != 16000 {
@@ -61,11 +424,21 @@ impl Transcriber for WhisperCppTranscriber {
             )));
         }
 
-        if audio.is_empty() {
+        // Without VAD, whisper decodes silence in full; skip it up front
+        let silent = !config.vad_enabled
+            && config.skip_silent_buffers
+            && calculate_rms(audio.samples()) < config.silence_rms_threshold;
+        if audio.is_empty() || silent {
+            if silent {
+                debug!("Buffer is silent, skipping transcription");
+            }
             return Ok(TranscriptionResult {
                 text: String::new(),
                 detected_language: None,
                 duration_ms: 0,
+                truncated: false,
+                segments: Vec::new(),
+                words: Vec::new(),
             });
         }
 
@@ -77,7 +450,7 @@ impl Transcriber for WhisperCppTranscriber {
         let threads = if config.threads > 0 {
             config.threads
         } else {
-            self.threads
+            self.threads()
         };
 
         debug!(
@@ -94,8 +467,51 @@ impl Transcriber for WhisperCppTranscriber {
         let vad_enabled = config.vad_enabled;
         let vad_no_speech = config.vad_no_speech_threshold;
         let vad_entropy = config.vad_entropy_threshold;
+        let min_confidence = config.min_segment_confidence;
+        let word_timestamps = config.word_timestamps;
+        let translate = config.translate;
+        let sampling = Self::sampling_strategy(config.sampling);
+        let initial_prompt = config.prompt().map(String::from);
+        let segment_listener = config
+            .stream_segments
+            .then(|| self.segment_listener.read().clone())
+            .flatten();
+        let abort = self.aborts.register(cancel);
+        let aborted = Arc::clone(&abort.flag);
+        let abort_callback = abort.callback();
         let result = tokio::task::spawn_blocking(move || {
-            let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+            let mut params = FullParams::new(sampling);
+
+            // Lets `cancel_running` stop the decode between steps
+            params.set_abort_callback_safe(abort_callback);
+
+            // Report segments as they finalize, during the single full() pass
+            if let Some(listener) = segment_listener {
+                params.set_segment_callback_safe(move |data: SegmentCallbackData| {
+                    listener(Self::segment_from_callback(data));
+                });
+            }
+
+            params.set_token_timestamps(word_timestamps);
+            // Whisper only translates into English
+            params.set_translate(translate);
+            if let Some(prompt) = initial_prompt {
+                let count_tokens = |text: &str| {
+                    ctx.tokenize(text, text.len() + 1).map_or(usize::MAX, |tokens| tokens.len())
+                };
+                let fitted = Self::fit_prompt(&prompt, MAX_PROMPT_TOKENS, count_tokens);
+                if fitted.len() < prompt.len() {
+                    info!(
+                        max_tokens = MAX_PROMPT_TOKENS,
+                        kept_chars = fitted.len(),
+                        dropped_chars = prompt.len() - fitted.len(),
+                        "Initial prompt too long, dropped its beginning"
+                    );
+                }
+                if !fitted.is_empty() {
+                    params.set_initial_prompt(fitted);
+                }
+            }
 
             params.set_n_threads(threads as i32);
             params.set_print_progress(false);
@@ -116,25 +532,67 @@ impl Transcriber for WhisperCppTranscriber {
 
             // Create state for this transcription
             let mut state = ctx.create_state().map_err(|e| {
-                DomainError::Whisper(format!("Failed to create whisper state: {}", e))
+                DomainError::Transcription(format!("Failed to create whisper state: {}", e))
             })?;
 
             // Run inference
-            state.full(params, &samples).map_err(|e| {
-                DomainError::Whisper(format!("Transcription failed: {}", e))
+            let outcome = state.full(params, &samples);
+            if aborted.load(Ordering::Relaxed) {
+                return Err(DomainError::Transcription("Transcription cancelled".to_string()));
+            }
+            outcome.map_err(|e| {
+                DomainError::Transcription(format!("Inference failed: {}", e))
             })?;
 
             // Collect results
             let num_segments = state.full_n_segments().map_err(|e| {
-                DomainError::Whisper(format!("Failed to get segment count: {}", e))
+                DomainError::Transcription(format!("Failed to get segment count: {}", e))
             })?;
 
-            let mut text = String::new();
+            let token_eot = ctx.token_eot();
+            let mut segments = Vec::with_capacity(num_segments.max(0) as usize);
+            let mut timed = Vec::with_capacity(segments.capacity());
+            let mut tokens = Vec::new();
             for i in 0..num_segments {
                 if let Ok(segment_text) = state.full_get_segment_text(i) {
-                    text.push_str(&segment_text);
+                    // Confidence = mean probability of text tokens (special tokens excluded)
+                    let n_tokens = state.full_n_tokens(i).unwrap_or(0);
+                    let probs: Vec<f32> = (0..n_tokens)
+                        .filter(|&j| state.full_get_token_id(i, j).is_ok_and(|id| id < token_eot))
+                        .filter_map(|j| state.full_get_token_prob(i, j).ok())
+                        .collect();
+                    let confidence = if probs.is_empty() {
+                        1.0
+                    } else {
+                        probs.iter().sum::<f32>() / probs.len() as f32
+                    };
+
+                    if confidence < min_confidence {
+                        debug!(segment = i, confidence, "Dropping low-confidence segment");
+                    } else {
+                        let t0 = state.full_get_segment_t0(i).unwrap_or(0);
+                        let t1 = state.full_get_segment_t1(i).unwrap_or(t0);
+                        timed.push((t0, t1, segment_text.clone()));
+
+                        // Text tokens only; timestamp and other special tokens are skipped
+                        if word_timestamps {
+                            for j in 0..n_tokens {
+                                let Ok(data) = state.full_get_token_data(i, j) else {
+                                    continue;
+                                };
+                                if data.id >= token_eot {
+                                    continue;
+                                }
+                                if let Ok(token_text) = state.full_get_token_text(i, j) {
+                                    tokens.push((token_text, data.t0, data.t1, data.p));
+                                }
+                            }
+                        }
+                    }
+                    segments.push((segment_text, confidence));
                 }
             }
+            let text = Self::assemble_text(&segments, min_confidence);
 
             // Get detected language (if available)
             let detected_language = state
@@ -142,27 +600,148 @@ impl Transcriber for WhisperCppTranscriber {
                 .ok()
                 .and_then(|id| whisper_rs::get_lang_str(id).map(|s| s.to_string()));
 
-            Ok::<(String, Option<String>), DomainError>((text.trim().to_string(), detected_language))
+            Ok::<_, DomainError>((
+                text.trim().to_string(),
+                detected_language,
+                Self::timed_segments(&timed),
+                Self::words_from_tokens(&tokens),
+            ))
         })
         .await
-        .map_err(|e| DomainError::Whisper(format!("Task join error: {}", e)))??;
+        .map_err(|e| DomainError::Transcription(format!("Task join error: {}", e)))??;
+        drop(abort);
+
+        let (mut text, detected_language, segments, words) = result;
+        let truncated = Self::truncate_output(&mut text, config.max_output_chars);
+        if truncated {
+            warn!(
+                max_chars = config.max_output_chars,
+                "Transcription exceeded the output limit and was truncated"
+            );
+        }
 
         let duration_ms = start.elapsed().as_millis() as u64;
+        if !self.context.is_current(generation) {
+            warn!("Model changed during transcription; result is from the previous model");
+        }
 
         info!(
-            text_len = result.0.len(),
+            text_len = text.len(),
             duration_ms = duration_ms,
-            detected_language = ?result.1,
+            detected_language = ?detected_language,
             "Transcription complete"
         );
 
         Ok(TranscriptionResult {
-            text: result.0,
-            detected_language: result.1,
+            text,
+            detected_language,
             duration_ms,
+            truncated,
+            segments,
+            words,
         })
     }
 
+    /// Decodes `STREAMING_CHUNK_SECS` windows overlapping by
+    /// `STREAMING_OVERLAP_SECS`, reporting the joined text after each. The
+    /// language detected in the first window is kept for the rest. Segment
+    /// streaming is off for the windows, whose timestamps would restart at 0.
+    async fn transcribe_streaming(
+        &self,
+        audio: &AudioBuffer,
+        config: &TranscribeConfig,
+        on_partial: &(dyn for<'t> Fn(&'t str) + Send + Sync),
+    ) -> Result<TranscriptionResult, DomainError> {
+        let rate = audio.sample_rate() as usize;
+        let chunk = STREAMING_CHUNK_SECS * rate;
+        if audio.samples().len() <= chunk {
+            let result = self.transcribe(audio, config).await?;
+            on_partial(&result.text);
+            return Ok(result);
+        }
+
+        let mut window_config = TranscribeConfig {
+            stream_segments: false,
+            max_output_chars: 0,
+            ..config.clone()
+        };
+        let overlap = STREAMING_OVERLAP_SECS * rate;
+        let mut text = String::new();
+        let mut segments: Vec<TranscriptSegment> = Vec::new();
+        let mut words: Vec<WordTiming> = Vec::new();
+        let mut detected_language = None;
+        let mut duration_ms = 0;
+        let mut start = 0;
+        while start < audio.samples().len() {
+            let from = start.saturating_sub(overlap);
+            let to = (start + chunk).min(audio.samples().len());
+            let mut window = AudioBuffer::with_capacity(audio.sample_rate(), to - from);
+            window.push_samples(&audio.samples()[from..to]);
+
+            let result = self.transcribe(&window, &window_config).await?;
+            duration_ms += result.duration_ms;
+            if detected_language.is_none() {
+                window_config.language = window_config
+                    .language
+                    .take()
+                    .or_else(|| result.detected_language.clone());
+                detected_language = result.detected_language;
+            }
+            Self::join_windows(&mut text, &result.text);
+            on_partial(&text);
+
+            // Shift to recording time; segments ending in the overlap were
+            // already taken from the previous window
+            let offset_ms = (from * 1000 / rate) as u64;
+            let seam_ms = (start * 1000 / rate) as u64;
+            for mut segment in result.segments {
+                segment.start_ms += offset_ms;
+                segment.end_ms += offset_ms;
+                if start == 0 || segment.end_ms > seam_ms {
+                    segment.index = segments.len() as u32;
+                    segment.start_ms = segment
+                        .start_ms
+                        .max(segments.last().map_or(0, |s| s.end_ms));
+                    segment.end_ms = segment.end_ms.max(segment.start_ms);
+                    segments.push(segment);
+                }
+            }
+            for mut word in result.words {
+                word.start_ms += offset_ms;
+                word.end_ms += offset_ms;
+                if start == 0 || word.start_ms >= seam_ms {
+                    words.push(word);
+                }
+            }
+            start = to;
+        }
+
+        let truncated = Self::truncate_output(&mut text, config.max_output_chars);
+        if truncated {
+            on_partial(&text);
+        }
+        Ok(TranscriptionResult {
+            text,
+            detected_language,
+            duration_ms,
+            truncated,
+            segments,
+            words,
+        })
+    }
+
+    fn set_segment_listener(&self, listener: Option<SegmentListener>) {
+        *self.segment_listener.write() = listener;
+    }
+
+    fn cancel_running(&self) -> usize {
+        let count = self.aborts.abort_all();
+        if count > 0 {
+            info!(count, "Cancelling running transcriptions");
+        }
+        count
+    }
+
     fn capabilities(&self) -> BackendCapabilities {
         BackendCapabilities {
             languages: vec![
@@ -179,14 +758,24 @@ impl Transcriber for WhisperCppTranscriber {
                 "zh".to_string(),
                 "ko".to_string(),
             ],
-            streaming: false,
+            streaming: true,
             requires_network: false,
+            translation_target: Some("en".to_string()),
             name: "whisper.cpp".to_string(),
         }
     }
 
     fn is_available(&self) -> bool {
-        self.context.read().is_some()
+        self.context.is_set()
+    }
+
+    async fn check_available(&self) -> BackendStatus {
+        let available = self.is_available();
+        BackendStatus {
+            name: self.capabilities().name,
+            available,
+            reason: (!available).then(|| "No model loaded".to_string()),
+        }
     }
 
     async fn load_model(&self, path: &Path) -> Result<(), DomainError> {
@@ -196,35 +785,24 @@ impl Transcriber for WhisperCppTranscriber {
             ));
         }
 
-        info!(path = ?path, "Loading whisper model");
-
-        let path_str = path.to_string_lossy().to_string();
-
-        // Load model in blocking task (I/O bound)
-        let ctx = tokio::task::spawn_blocking(move || {
-            WhisperContext::new_with_params(&path_str, WhisperContextParameters::default())
-                .map_err(|e| DomainError::Whisper(format!("Failed to load model: {}", e)))
-        })
-        .await
-        .map_err(|e| DomainError::Whisper(format!("Task join error: {}", e)))??;
-
-        *self.context.write() = Some(Arc::new(ctx));
-
-        info!(path = ?path, "Whisper model loaded successfully");
+        self.load_guard
+            .run(path, || self.is_model_loaded(), || self.load_context(path))
+            .await?;
         Ok(())
     }
 
     fn unload_model(&self) {
-        let had_model = self.context.read().is_some();
-        *self.context.write() = None;
-
-        if had_model {
+        if self.context.clear() {
             info!("Whisper model unloaded");
         }
     }
 
     fn is_model_loaded(&self) -> bool {
-        self.context.read().is_some()
+        self.context.is_set()
+    }
+
+    fn is_multilingual(&self) -> Option<bool> {
+        self.context.checkout().map(|(ctx, _)| ctx.is_multilingual())
     }
 }
 
@@ -244,6 +822,263 @@ mod tests {
         assert!((converted[4] - -1.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_assemble_text_filters_low_confidence() {
+        let segments = vec![
+            (" Hello there.".to_string(), 0.92),
+            (" Thanks for watching!".to_string(), 0.21),
+            (" See you soon.".to_string(), 0.75),
+        ];
+
+        // Default keeps everything
+        assert_eq!(
+            WhisperCppTranscriber::assemble_text(&segments, 0.0),
+            " Hello there. Thanks for watching! See you soon."
+        );
+        assert_eq!(
+            WhisperCppTranscriber::assemble_text(&segments, 0.5),
+            " Hello there. See you soon."
+        );
+        assert_eq!(WhisperCppTranscriber::assemble_text(&segments, 0.95), "");
+    }
+
+    #[test]
+    fn test_abort_interrupts_running_decode() {
+        let registry = AbortRegistry::default();
+        assert_eq!(registry.abort_all(), 0);
+
+        // Poll the callback handed to whisper, as it does between decode steps
+        let handle = registry.register(Arc::default());
+        let mut should_abort = handle.callback();
+        assert!(!should_abort());
+        let decode = std::thread::spawn(move || {
+            let mut steps = 0u64;
+            while !should_abort() {
+                steps += 1;
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            steps
+        });
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert_eq!(registry.abort_all(), 1);
+        assert!(decode.join().unwrap() > 0);
+
+        // Finished decodes are unregistered
+        drop(handle);
+        assert_eq!(registry.abort_all(), 0);
+    }
+
+    #[test]
+    fn test_batch_cancel_spares_other_decodes() {
+        let registry = AbortRegistry::default();
+        let batch_cancel = Arc::new(AtomicBool::new(false));
+        let batch = registry.register(Arc::clone(&batch_cancel));
+        let dictation = registry.register(Arc::default());
+        let (mut batch_aborted, mut dictation_aborted) = (batch.callback(), dictation.callback());
+
+        // What `cancel_batch` does
+        batch_cancel.store(true, Ordering::Relaxed);
+        assert!(batch_aborted());
+        assert!(!dictation_aborted());
+
+        // `cancel_running` still stops everything
+        assert_eq!(registry.abort_all(), 2);
+        assert!(dictation_aborted());
+    }
+
+    #[test]
+    fn test_truncate_output_at_word_boundary() {
+        // A degenerate decode repeating itself
+        let mut text = "Thank you. ".repeat(1000);
+        assert!(WhisperCppTranscriber::truncate_output(&mut text, 25));
+        assert_eq!(text, "Thank you. Thank you.");
+
+        let mut text = "short enough".to_string();
+        assert!(!WhisperCppTranscriber::truncate_output(&mut text, 12));
+        assert!(!WhisperCppTranscriber::truncate_output(&mut text, 0));
+        assert_eq!(text, "short enough");
+
+        // No whitespace to cut at: cut on a char boundary
+        let mut text = "谢谢谢谢谢谢".to_string();
+        assert!(WhisperCppTranscriber::truncate_output(&mut text, 4));
+        assert_eq!(text, "谢谢谢谢");
+    }
+
+    #[test]
+    fn test_join_windows_drops_repeated_seam() {
+        let mut text = String::new();
+        WhisperCppTranscriber::join_windows(&mut text, "So the plan for today is");
+        assert_eq!(text, "So the plan for today is");
+
+        // The overlap heard "today is" again, with different punctuation
+        WhisperCppTranscriber::join_windows(&mut text, "Today is, to ship the release.");
+        assert_eq!(text, "So the plan for today is to ship the release.");
+
+        // Nothing in common: appended whole
+        WhisperCppTranscriber::join_windows(&mut text, "Questions?");
+        assert_eq!(text, "So the plan for today is to ship the release. Questions?");
+
+        // An empty window adds nothing
+        WhisperCppTranscriber::join_windows(&mut text, "");
+        assert_eq!(text, "So the plan for today is to ship the release. Questions?");
+    }
+
+    #[test]
+    fn test_timed_segments() {
+        let raw = vec![
+            (0, 150, " Hello there.".to_string()),
+            (150, 312, " General Kenobi.".to_string()),
+            // Overlapping timestamps from a sloppy decode
+            (300, 290, " You are a bold one.".to_string()),
+        ];
+        let segments = WhisperCppTranscriber::timed_segments(&raw);
+        assert_eq!(segments.len(), raw.len());
+        assert_eq!(segments[0].text, "Hello there.");
+        assert_eq!((segments[1].start_ms, segments[1].end_ms), (1500, 3120));
+        for (i, segment) in segments.iter().enumerate() {
+            assert_eq!(segment.index, i as u32);
+            assert!(segment.end_ms >= segment.start_ms);
+            if i > 0 {
+                assert!(segment.start_ms >= segments[i - 1].end_ms);
+            }
+        }
+        assert!(WhisperCppTranscriber::timed_segments(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_words_from_tokens() {
+        let tokens = vec![
+            (" Kub".to_string(), 0, 20, 0.8),
+            ("ern".to_string(), 20, 35, 0.6),
+            ("etes".to_string(), 35, 50, 0.7),
+            (" rocks".to_string(), 52, 80, 0.9),
+            ("!".to_string(), 80, 82, 0.5),
+            (" ".to_string(), 82, 82, 0.1),
+        ];
+        let words = WhisperCppTranscriber::words_from_tokens(&tokens);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "Kubernetes");
+        assert_eq!((words[0].start_ms, words[0].end_ms), (0, 500));
+        assert!((words[0].probability - 0.7).abs() < 1e-6);
+        assert_eq!(words[1].text, "rocks!");
+        assert_eq!((words[1].start_ms, words[1].end_ms), (520, 820));
+        assert!(WhisperCppTranscriber::words_from_tokens(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_sampling_strategy_mapping() {
+        let strategy = |mode| WhisperCppTranscriber::sampling_strategy(mode);
+        assert!(matches!(
+            strategy(SamplingMode::default()),
+            SamplingStrategy::Greedy { best_of: 1 }
+        ));
+        assert!(matches!(
+            strategy(SamplingMode::Greedy { best_of: 3 }),
+            SamplingStrategy::Greedy { best_of: 3 }
+        ));
+        assert!(matches!(
+            strategy(SamplingMode::Beam { beam_size: 5 }),
+            SamplingStrategy::BeamSearch { beam_size: 5, .. }
+        ));
+        // Absurd values are clamped
+        assert!(matches!(
+            strategy(SamplingMode::Beam { beam_size: 1000 }),
+            SamplingStrategy::BeamSearch { beam_size: 8, .. }
+        ));
+        assert!(matches!(
+            strategy(SamplingMode::Greedy { best_of: 0 }),
+            SamplingStrategy::Greedy { best_of: 1 }
+        ));
+    }
+
+    #[test]
+    fn test_fit_prompt_keeps_the_end() {
+        let words = |text: &str| text.split_whitespace().count();
+        let prompt = "Acme Corp, Kubernetes, gRPC, OpenSay";
+        assert_eq!(WhisperCppTranscriber::fit_prompt(prompt, 10, words), prompt);
+        assert_eq!(WhisperCppTranscriber::fit_prompt(prompt, 2, words), "gRPC, OpenSay");
+        assert_eq!(WhisperCppTranscriber::fit_prompt("Supercalifragilistic", 0, words), "");
+    }
+
+    #[test]
+    fn test_segment_from_callback() {
+        let segment = WhisperCppTranscriber::segment_from_callback(SegmentCallbackData {
+            segment: 2,
+            start_timestamp: 150,
+            end_timestamp: 312,
+            text: " Hello there.".to_string(),
+        });
+        assert_eq!(
+            segment,
+            TranscriptSegment {
+                index: 2,
+                start_ms: 1500,
+                end_ms: 3120,
+                text: "Hello there.".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_loads_build_one_context() {
+        use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+        let guard = LoadGuard::default();
+        let path = Path::new("/models/ggml-small-q5_1.bin");
+        let built = AtomicU32::new(0);
+        let loaded = AtomicBool::new(false);
+
+        let load = || async {
+            built.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            loaded.store(true, Ordering::SeqCst);
+            Ok(())
+        };
+        let is_loaded = || loaded.load(Ordering::SeqCst);
+
+        let (first, second) = tokio::join!(
+            guard.run(path, is_loaded, load),
+            guard.run(path, is_loaded, load)
+        );
+
+        assert_eq!(built.load(Ordering::SeqCst), 1);
+        // Exactly one of the two calls performed the load
+        assert!(first.unwrap() ^ second.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_load_during_transcription_keeps_original_context() {
+        let slot = Arc::new(ContextSlot::default());
+        slot.replace("small".to_string());
+
+        // Simulate an in-flight transcription holding its checkout
+        let (ctx, generation) = slot.checkout().unwrap();
+        let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+        let (resume_tx, resume_rx) = std::sync::mpsc::channel::<()>();
+        let task = tokio::task::spawn_blocking(move || {
+            let _ = started_tx.send(());
+            resume_rx.recv().unwrap();
+            ctx.to_uppercase()
+        });
+        started_rx.await.unwrap();
+
+        // Unload and load a different model mid-transcription
+        assert!(slot.clear());
+        assert!(!slot.is_set());
+        slot.replace("medium".to_string());
+        assert!(!slot.is_current(generation));
+
+        resume_tx.send(()).unwrap();
+        assert_eq!(task.await.unwrap(), "SMALL");
+
+        // Later transcriptions use the new context; the old one was freed
+        let (ctx, generation) = slot.checkout().unwrap();
+        assert_eq!(*ctx, "medium");
+        assert!(slot.is_current(generation));
+        assert_eq!(Arc::strong_count(&ctx), 2);
+    }
+
     #[test]
     fn test_transcriber_creation() {
         let transcriber = WhisperCppTranscriber::new(4);
@@ -258,7 +1093,8 @@ mod tests {
 
         assert_eq!(caps.name, "whisper.cpp");
         assert!(!caps.requires_network);
-        assert!(!caps.streaming);
+        assert!(caps.streaming);
+        assert_eq!(caps.translation_target.as_deref(), Some("en"));
         assert!(caps.languages.contains(&"en".to_string()));
     }
 }
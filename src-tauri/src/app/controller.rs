@@ -1,40 +1,225 @@
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use tokio::sync::broadcast;
-use tracing::info;
-use tracing_appender::non_blocking::WorkerGuard;
+use tracing::{debug, info, warn};
+use zeroize::Zeroizing;
 
 use crate::adapters::{
-    ClipboardOutputManager, CpalAudioManager, CpuHardwareDetector, LocalModelManager,
-    PrivacyGuard, TomlConfigStore, WhisperCppTranscriber,
+    ClipboardOutputManager, CpalAudioManager, CpuHardwareDetector, EncryptedRecordingStore,
+    JsonlTranscriptSink, LocalModelManager, PrivacyGuard, TomlBenchmarkStore, TomlConfigStore,
+    TomlUsageStatsStore, WhisperCppTranscriber,
 };
+use crate::app::{AppEvent, ModelLoadPhase, TelemetrySnapshot, TogglePhase};
+use crate::domain::transcription::WHISPER_SAMPLE_RATE;
 use crate::domain::{
-    AppConfig, AudioBuffer, AudioConfig, AudioDevice, AudioEvent, AudioState, DomainError,
-    DownloadProgress, HardwareProfile, InstalledModel, ModelCatalog, ModelRecommendation,
-    Quantization,
+    apply_agc, apply_noise_gate, normalize_language, process_output, AppConfig, AudioBuffer,
+    AudioConfig, AudioDevice, AudioEvent, AudioState, BenchmarkCache, BenchmarkResult,
+    CaptureRateMeasurement, DictationSummary, DomainError, DownloadProgress, DownloadQueueEntry,
+    HardwareProfile, InstalledModel, LanguageModelSuggestion, ModelCatalog, ModelRecommendation,
+    ModelsDirEntry, OutputMode, OutputSink, Quantization, SessionType, StoredRecording,
+    StreamInfo, ThreadUsage, TranscriptRecord, UsageStats,
 };
-use crate::infrastructure::init_logging;
+use crate::infrastructure::{init_logging, LogHandle};
 use crate::ports::{
-    AudioManager, ConfigStore, HardwareDetector, HttpClient, ModelManager, OutputManager,
-    TranscribeConfig, Transcriber, TranscriptionResult,
+    AudioManager, BackendStatus, BenchmarkStore, ConfigStore, HardwareDetector, HttpClient,
+    ModelManager, OutputManager, RecordingStore, TranscribeConfig, Transcriber, TranscriptSink,
+    TranscriptionResult, UsageStatsStore,
 };
 
+/// Cheapest authenticated OpenAI endpoint, used to check API keys.
+const OPENAI_MODELS_URL: &str = "https://api.openai.com/v1/models";
+
+/// Length of the synthetic clip transcribed by `benchmark_models`.
+const BENCHMARK_AUDIO_SECS: u32 = 10;
+
+/// The audio ring buffer may use at most 1/N of system RAM.
+const BUFFER_RAM_FRACTION: u64 = 16;
+
 /// Result of a toggle recording operation.
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(tag = "type")]
 pub enum ToggleResult {
     /// Recording started.
     Started,
+    /// Dictation is muted; nothing was recorded.
+    Muted,
+    /// The frontmost app is blocked (`privacy.blocked_apps` / `allowed_apps`);
+    /// nothing was recorded.
+    Blocked {
+        /// The detected app, or None if it couldn't be detected.
+        app: Option<String>,
+    },
     /// Recording stopped and transcription completed.
     Completed {
         /// The transcribed text, or None if no speech was detected.
         text: Option<String>,
+        /// Per-phase latency breakdown.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        timings: Option<ToggleTimings>,
+    },
+}
+
+/// Latency of each phase between stopping the recording and injecting text.
+#[derive(Debug, Clone, Copy, Default, serde::Serialize)]
+pub struct ToggleTimings {
+    /// Time to stop the stream and drain the buffer.
+    pub stop_ms: u64,
+    /// Time spent in transcription.
+    pub transcribe_ms: u64,
+    /// Time to inject the text (0 if nothing was injected).
+    pub inject_ms: u64,
+}
+
+/// Outcome of the first-run model setup.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type")]
+pub enum DefaultModelSetup {
+    /// A model was already installed; nothing was done.
+    AlreadyInstalled,
+    /// Onboarding hasn't been acknowledged yet.
+    NeedsAcknowledgment,
+    /// The model has to be downloaded but local-only mode is on.
+    BlockedByLocalOnly { model_id: String },
+    /// The model was installed, loaded and selected.
+    Installed {
+        model_id: String,
+        /// Installed from the app's bundled resources rather than downloaded.
+        bundled: bool,
     },
 }
 
+/// Outcome of one recording in `retranscribe_stored_batch`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BatchItemResult {
+    /// ID of the stored recording.
+    pub id: String,
+    /// The transcription, if it succeeded.
+    pub result: Option<TranscriptionResult>,
+    /// Why it failed; the item interrupted by `cancel_batch` fails as cancelled.
+    pub error: Option<String>,
+}
+
+/// A running `retranscribe_stored_batch`.
+struct Batch {
+    /// Recordings not yet started.
+    pending: VecDeque<String>,
+    /// Whether a recording was taken and may still be transcribing.
+    started: bool,
+    /// Aborts the batch's decodes only, not concurrent dictations.
+    cancel: Arc<AtomicBool>,
+}
+
+/// The running batch, if any.
+#[derive(Default)]
+struct BatchQueue(Mutex<Option<Batch>>);
+
+impl BatchQueue {
+    /// Start a batch, unless one is already running. Returns the flag that
+    /// cancels its decodes.
+    fn start(&self, ids: Vec<String>) -> Result<Arc<AtomicBool>, DomainError> {
+        let mut batch = self.0.lock();
+        if batch.is_some() {
+            return Err(DomainError::Transcription("A batch is already running".to_string()));
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+        *batch = Some(Batch {
+            pending: ids.into(),
+            started: false,
+            cancel: Arc::clone(&cancel),
+        });
+        Ok(cancel)
+    }
+
+    /// Take the next recording, ending the batch once none are left.
+    fn next(&self) -> Option<String> {
+        let mut slot = self.0.lock();
+        let batch = slot.as_mut()?;
+        let next = batch.pending.pop_front();
+        batch.started = true;
+        if next.is_none() {
+            *slot = None;
+        }
+        next
+    }
+
+    /// Drop the recordings not yet started and abort the current one.
+    /// Returns how many recordings that cancelled, or `None` if no batch is
+    /// running.
+    fn cancel(&self) -> Option<usize> {
+        self.0.lock().as_mut().map(|batch| {
+            batch.cancel.store(true, Ordering::Relaxed);
+            let skipped = batch.pending.len();
+            batch.pending.clear();
+            skipped + usize::from(batch.started)
+        })
+    }
+}
+
+/// A dictation held for review before injection.
+struct PendingInjection {
+    text: Zeroizing<String>,
+    language: Option<String>,
+    sink: Option<OutputSink>,
+    /// Counted and exported only if the text is injected.
+    summary: DictationSummary,
+    expires_at: Instant,
+}
+
+/// Slot of the dictation awaiting `confirm_inject`, shared with its expiry
+/// timer.
+#[derive(Clone, Default)]
+struct PendingSlot(Arc<Mutex<Option<PendingInjection>>>);
+
+impl PendingSlot {
+    /// Hold a dictation, replacing (and zeroizing) any earlier one.
+    fn hold(&self, pending: PendingInjection) {
+        *self.0.lock() = Some(pending);
+    }
+
+    /// Take the held dictation for `confirm_inject`: `Some` if it should be
+    /// injected, `None` if it was rejected. Fails if nothing is held or it
+    /// expired at `now`.
+    fn confirm(
+        &self,
+        approved: bool,
+        now: Instant,
+    ) -> Result<Option<PendingInjection>, DomainError> {
+        let pending = self.0.lock().take().ok_or(DomainError::NothingToConfirm)?;
+        if now >= pending.expires_at {
+            // The expiry timer hasn't run yet
+            info!("Held text expired before confirmation, discarded");
+            return Err(DomainError::NothingToConfirm);
+        }
+        if !approved {
+            info!("Held text discarded");
+            return Ok(None);
+        }
+        Ok(Some(pending))
+    }
+
+    /// Discard the dictation held until `expires_at`, unless it was already
+    /// confirmed or replaced. Returns whether it was discarded.
+    fn expire(&self, expires_at: Instant) -> bool {
+        let mut slot = self.0.lock();
+        if slot.as_ref().map(|pending| pending.expires_at) != Some(expires_at) {
+            return false;
+        }
+        // Zeroized on drop
+        slot.take();
+        true
+    }
+
+    /// Discard the held dictation, if any.
+    fn clear(&self) {
+        self.0.lock().take();
+    }
+}
+
 /// Application controller that orchestrates initialization and manages global state.
 pub struct AppController {
     config: RwLock<AppConfig>,
@@ -44,9 +229,38 @@ pub struct AppController {
     model_manager: Arc<LocalModelManager>,
     hardware_detector: Arc<CpuHardwareDetector>,
     output_manager: Arc<ClipboardOutputManager>,
+    stats_store: Arc<TomlUsageStatsStore>,
+    usage_stats: RwLock<UsageStats>,
+    benchmark_store: Arc<TomlBenchmarkStore>,
+    transcript_sink: Arc<JsonlTranscriptSink>,
+    /// Encrypted recordings, written only when `privacy.store_encrypted_audio` is on.
+    recording_store: Arc<EncryptedRecordingStore>,
+    /// Last recording, retained only when `privacy.retain_last_recording` is on.
+    last_recording: Mutex<Option<Arc<AudioBuffer>>>,
+    /// Text of the most recent transcription, for re-copying (zeroized on replace).
+    last_transcription: Mutex<Option<Zeroizing<String>>>,
+    /// Text accumulated across dictations in append mode.
+    pending_transcript: Mutex<Zeroizing<String>>,
+    event_tx: broadcast::Sender<AppEvent>,
+    /// ID of the active telemetry stream (0 = stopped).
+    telemetry_session: AtomicU64,
+    next_telemetry_session: AtomicU64,
+    /// Path of the model currently loaded in the transcriber.
+    loaded_model: Mutex<Option<PathBuf>>,
     /// Guard against concurrent toggle_recording calls (e.g., keyboard repeat)
     toggle_in_progress: AtomicBool,
-    _log_guard: Option<WorkerGuard>,
+    /// When set, toggle_recording refuses to start capture.
+    muted: AtomicBool,
+    /// Set once the app starts exiting; toggle_recording refuses new work.
+    shutting_down: AtomicBool,
+    /// Output target for the next completed dictation only, kept until a
+    /// dictation is delivered there.
+    next_output_override: Mutex<Option<OutputSink>>,
+    /// Dictation awaiting `confirm_inject` when `confirm_before_inject` is on.
+    pending_injection: PendingSlot,
+    /// Queue of `retranscribe_stored_batch`.
+    batch_queue: BatchQueue,
+    log_handle: Option<LogHandle>,
 }
 
 impl AppController {
@@ -57,10 +271,11 @@ impl AppController {
         let config_store = Arc::new(TomlConfigStore::new()?);
 
         // Step 2: Load configuration
-        let config = config_store.load()?;
+        let mut config = config_store.load()?;
+        config.privacy.enforce_ephemeral();
 
         // Step 3: Initialize logging
-        let log_guard = init_logging(
+        let log_handle = init_logging(
             &config_store.logs_dir(),
             &config.logging.level,
             config.logging.file_logging,
@@ -73,14 +288,19 @@ impl AppController {
             config.privacy.local_only,
             config.privacy.allowed_domains.clone(),
         );
+        PrivacyGuard::global()
+            .set_download_buffer_size(config.transcription.download_buffer_kb as usize * 1024);
 
         // Step 5: Initialize audio manager
-        let audio_manager = Arc::new(CpalAudioManager::new()?);
+        let audio_manager = Arc::new(CpalAudioManager::with_config(config.audio.clone())?);
 
         // Step 6: Initialize hardware detector
         let hardware_detector = Arc::new(CpuHardwareDetector::new());
         // Pre-detect hardware profile
         let _ = hardware_detector.detect();
+        if let Some(profile) = &config.hardware_override {
+            hardware_detector.set_override(Some(profile.clone()));
+        }
 
         // Step 7: Initialize model manager
         let model_manager = Arc::new(LocalModelManager::new(config_store.data_dir())?);
@@ -91,10 +311,33 @@ impl AppController {
             .map(|p| p.recommended_threads())
             .unwrap_or(1);
         let transcriber = Arc::new(WhisperCppTranscriber::new(threads));
+        if let Ok(profile) = hardware_detector.profile() {
+            warn_if_threads_underused(&profile.thread_usage(transcriber.threads()));
+        }
 
         // Step 9: Initialize output manager
         let output_manager = Arc::new(ClipboardOutputManager::new(config.output.clone())?);
 
+        // Step 10: Load usage statistics (counters only)
+        let stats_store = Arc::new(TomlUsageStatsStore::new(config_store.data_dir()));
+        let usage_stats = stats_store.load().unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to load usage stats, starting fresh");
+            UsageStats::default()
+        });
+
+        let benchmark_store = Arc::new(TomlBenchmarkStore::new(config_store.data_dir()));
+
+        // Step 11: Encrypted recording store (only written to when opted in)
+        let recording_store = Arc::new(EncryptedRecordingStore::new(config_store.data_dir()));
+
+        let (event_tx, _) = broadcast::channel(32);
+
+        // Relay live segments to the frontend
+        let segment_tx = event_tx.clone();
+        transcriber.set_segment_listener(Some(Arc::new(move |segment| {
+            let _ = segment_tx.send(AppEvent::TranscriptSegment(segment));
+        })));
+
         info!(
             local_only = config.privacy.local_only,
             transcriber_threads = threads,
@@ -109,8 +352,25 @@ impl AppController {
             model_manager,
             hardware_detector,
             output_manager,
+            stats_store,
+            usage_stats: RwLock::new(usage_stats),
+            benchmark_store,
+            transcript_sink: Arc::new(JsonlTranscriptSink::new()),
+            recording_store,
+            last_recording: Mutex::new(None),
+            last_transcription: Mutex::new(None),
+            pending_transcript: Mutex::new(Zeroizing::new(String::new())),
+            event_tx,
+            telemetry_session: AtomicU64::new(0),
+            next_telemetry_session: AtomicU64::new(1),
+            loaded_model: Mutex::new(None),
             toggle_in_progress: AtomicBool::new(false),
-            _log_guard: log_guard,
+            muted: AtomicBool::new(false),
+            shutting_down: AtomicBool::new(false),
+            next_output_override: Mutex::new(None),
+            pending_injection: PendingSlot::default(),
+            batch_queue: BatchQueue::default(),
+            log_handle,
         })
     }
 
@@ -119,28 +379,128 @@ impl AppController {
         self.config.read().clone()
     }
 
-    /// Update the configuration.
-    pub fn update_config(&self, config: AppConfig) -> Result<(), DomainError> {
+    /// Update the configuration. A locked input device is kept; it only
+    /// changes through `select_audio_device`.
+    pub fn update_config(&self, mut config: AppConfig) -> Result<(), DomainError> {
+        config.audio.keep_locked_device(&self.config.read().audio);
+        self.apply_config(config)
+    }
+
+    /// Validate, persist and apply a configuration.
+    fn apply_config(&self, mut config: AppConfig) -> Result<(), DomainError> {
+        config.validate()?;
+        if let Some(language) = normalize_language(&config.transcription.language) {
+            config.transcription.language = language;
+        }
+        config.transcription.language_model_map = std::mem::take(
+            &mut config.transcription.language_model_map,
+        )
+        .into_iter()
+        .map(|(language, model)| (normalize_language(&language).unwrap_or(language), model))
+        .collect();
+        config.privacy.enforce_ephemeral();
+        if let Some(path) = &config.output.jsonl_sink {
+            self.transcript_sink.prepare(path)?;
+        }
+
         // Update PrivacyGuard settings
         let guard = PrivacyGuard::global();
         guard.set_local_only(config.privacy.local_only);
         guard.set_allowed_domains(config.privacy.allowed_domains.clone());
+        guard.set_download_buffer_size(config.transcription.download_buffer_kb as usize * 1024);
 
         // Save to disk
         self.config_store.save(&config)?;
 
+        // Apply output and audio settings to the running adapters
+        self.output_manager.update_config(config.output.clone());
+        self.audio_manager.update_config(config.audio.clone());
+
+        if !config.privacy.retain_last_recording {
+            self.clear_last_recording();
+        }
+
         // Update in-memory config
-        *self.config.write() = config;
+        let previous = std::mem::replace(&mut *self.config.write(), config);
+
+        let hardware_override = self.config.read().hardware_override.clone();
+        if hardware_override != previous.hardware_override {
+            self.apply_hardware_override(hardware_override);
+        }
+
+        // Warn in settings when the new language needs a bigger model
+        let language = self.config.read().transcription.language.clone();
+        if language != previous.transcription.language {
+            if let Ok(Some(suggestion)) = self.suggest_model_for_language(&language) {
+                warn!("{}", suggestion.message);
+                self.emit(AppEvent::ModelSuggestion(suggestion));
+            }
+        }
 
         info!("Configuration updated");
         Ok(())
     }
 
+    /// Subscribe to application events.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<AppEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Broadcast an application event (no-op when nobody is subscribed).
+    fn emit(&self, event: AppEvent) {
+        let _ = self.event_tx.send(event);
+    }
+
+    // ==================== Telemetry Methods ====================
+
+    /// Start a telemetry stream and return its session ID.
+    /// Any previous stream stops at its next tick.
+    pub fn start_telemetry(&self) -> u64 {
+        let session = self.next_telemetry_session.fetch_add(1, Ordering::SeqCst);
+        self.telemetry_session.store(session, Ordering::SeqCst);
+        session
+    }
+
+    /// Stop the telemetry stream.
+    pub fn stop_telemetry(&self) {
+        self.telemetry_session.store(0, Ordering::SeqCst);
+    }
+
+    /// Check if the given telemetry stream is still the active one.
+    pub fn is_telemetry_active(&self, session: u64) -> bool {
+        self.telemetry_session.load(Ordering::SeqCst) == session
+    }
+
+    /// Emit a telemetry snapshot built from the current state.
+    pub fn publish_telemetry(&self) {
+        let recording_secs = self.recording_duration();
+        let buffer_secs = self.audio_config().buffer_duration_secs as f32;
+        self.emit(AppEvent::Telemetry(TelemetrySnapshot {
+            audio_state: self.audio_state(),
+            level: self.audio_level(),
+            recording_secs,
+            buffer_headroom_secs: (buffer_secs - recording_secs).max(0.0),
+            model_loaded: self.is_model_loaded(),
+            active_threads: self.transcriber.threads(),
+            threads_underused: self.thread_usage().is_ok_and(|usage| usage.underused),
+        }));
+    }
+
     /// Check if network is currently blocked.
     pub fn is_network_blocked(&self) -> bool {
         PrivacyGuard::global().is_network_blocked()
     }
 
+    /// Flush buffered log lines to the log file.
+    /// No-op when file logging is disabled.
+    pub fn flush_logs(&self) -> Result<(), DomainError> {
+        if let Some(handle) = &self.log_handle {
+            handle.flush()?;
+            debug!("Logs flushed");
+        }
+        Ok(())
+    }
+
     /// Get the data directory path.
     pub fn data_dir(&self) -> String {
         self.config_store.data_dir().to_string_lossy().to_string()
@@ -164,8 +524,35 @@ impl AppController {
     }
 
     /// Stop audio recording and return the captured buffer.
+    ///
+    /// The noise gate (`audio.noise_gate_enabled`) and AGC
+    /// (`audio.agc_enabled`) run here, off the realtime capture path. The
+    /// gate goes first so its thresholds apply to the level as captured.
     pub async fn stop_recording(&self) -> Result<AudioBuffer, DomainError> {
-        self.audio_manager.stop_recording().await
+        let mut buffer = self.audio_manager.stop_recording().await?;
+        let audio = self.audio_manager.config();
+        if audio.noise_gate_enabled {
+            let sample_rate = buffer.sample_rate();
+            apply_noise_gate(
+                buffer.samples_mut(),
+                sample_rate,
+                audio.noise_gate_dbfs,
+                audio.noise_gate_close_dbfs(),
+            );
+            debug!(threshold_dbfs = audio.noise_gate_dbfs, "Noise gate applied to recording");
+        }
+        if audio.agc_enabled {
+            let sample_rate = buffer.sample_rate();
+            apply_agc(
+                buffer.samples_mut(),
+                sample_rate,
+                audio.agc_target_dbfs,
+                audio.agc_attack_ms,
+                audio.agc_release_ms,
+            );
+            debug!(target_dbfs = audio.agc_target_dbfs, "AGC applied to recording");
+        }
+        Ok(buffer)
     }
 
     /// Get current audio state.
@@ -173,11 +560,21 @@ impl AppController {
         self.audio_manager.state()
     }
 
+    /// Check whether the audio thread is answering commands.
+    pub fn is_audio_responsive(&self) -> bool {
+        self.audio_manager.is_responsive()
+    }
+
     /// Get audio configuration.
     pub fn audio_config(&self) -> AudioConfig {
         self.audio_manager.config()
     }
 
+    /// Get the native format of the open input stream, if any.
+    pub fn audio_stream_info(&self) -> Option<StreamInfo> {
+        self.audio_manager.stream_info()
+    }
+
     /// List available audio input devices.
     pub fn list_audio_devices(&self) -> Result<Vec<AudioDevice>, DomainError> {
         self.audio_manager.list_input_devices()
@@ -185,7 +582,73 @@ impl AppController {
 
     /// Select an audio input device.
     pub fn select_audio_device(&self, device_id: Option<&str>) -> Result<(), DomainError> {
-        self.audio_manager.select_input_device(device_id)
+        self.audio_manager.select_input_device(device_id)?;
+
+        // Persist the choice so it survives restarts
+        let mut config = self.config();
+        config.audio.device_id = device_id.map(String::from);
+        self.apply_config(config)
+    }
+
+    /// Set the maximum recording duration in seconds.
+    ///
+    /// The ring buffer is resized on the next `start_recording`. Durations whose
+    /// buffer would exceed 1/`BUFFER_RAM_FRACTION` of system RAM are rejected.
+    pub fn set_buffer_duration(&self, secs: u32) -> Result<(), DomainError> {
+        let mut config = self.config();
+        config.audio.buffer_duration_secs = secs;
+        config.audio.validate()?;
+
+        if let Ok(profile) = self.hardware_detector.profile() {
+            let max_bytes = profile.ram_bytes / BUFFER_RAM_FRACTION;
+            if config.audio.buffer_bytes() > max_bytes {
+                return Err(DomainError::Config(format!(
+                    "A {}s buffer needs {} bytes, more than the {} byte limit for this system",
+                    secs,
+                    config.audio.buffer_bytes(),
+                    max_bytes
+                )));
+            }
+        }
+
+        self.update_config(config)
+    }
+
+    /// Check whether silent recordings skip decoding when VAD is off.
+    pub fn skip_silent_buffers(&self) -> bool {
+        self.config.read().transcription.skip_silent_buffers
+    }
+
+    /// Enable or disable skipping silent recordings when VAD is off.
+    /// Persisted to the config file.
+    pub fn set_skip_silent_buffers(&self, enabled: bool) -> Result<(), DomainError> {
+        let mut config = self.config();
+        config.transcription.skip_silent_buffers = enabled;
+        self.update_config(config)
+    }
+
+    /// Whether segments are emitted live while a dictation is decoded.
+    pub fn live_segments(&self) -> bool {
+        self.config.read().transcription.live_segments
+    }
+
+    /// Enable or disable live `transcript-segment` events.
+    /// Persisted to the config file.
+    pub fn set_live_segments(&self, enabled: bool) -> Result<(), DomainError> {
+        let mut config = self.config();
+        config.transcription.live_segments = enabled;
+        self.update_config(config)
+    }
+
+    /// Set the input gain in dB.
+    ///
+    /// Stored as a preset for the selected device, or as the global gain when
+    /// the system default device is in use. Persisted to the config file.
+    pub fn set_input_gain(&self, gain_db: f32) -> Result<(), DomainError> {
+        let mut config = self.config();
+        let device_id = self.audio_manager.selected_input_device();
+        config.audio.set_gain_db(device_id.as_deref(), gain_db);
+        self.update_config(config)
     }
 
     /// Subscribe to audio events.
@@ -193,11 +656,74 @@ impl AppController {
         self.audio_manager.subscribe()
     }
 
+    /// Relay audio events the frontend acts on as `AppEvent`s, and finish
+    /// recordings that reach `max_recording_secs` as if toggled off. Runs
+    /// until the audio manager shuts down.
+    pub async fn forward_audio_events(&self) {
+        let mut events = self.audio_manager.subscribe();
+        loop {
+            match events.recv().await {
+                Ok(AudioEvent::MaxDurationReached) => {
+                    let max_recording_secs = self.audio_config().recording_limit_secs();
+                    info!(max_recording_secs, "Maximum recording duration reached, stopping");
+                    self.emit(AppEvent::RecordingAutoStopped { max_recording_secs });
+                    if self.audio_state() == AudioState::Recording {
+                        if let Err(e) = self.toggle_recording().await {
+                            warn!(error = %e, "Failed to finish auto-stopped recording");
+                        }
+                    }
+                }
+                Ok(AudioEvent::SilenceTimeout { silence_ms }) => {
+                    info!(silence_ms, "Silence after speech, stopping");
+                    self.emit(AppEvent::RecordingSilenceStopped { silence_ms });
+                    if self.audio_state() == AudioState::Recording {
+                        if let Err(e) = self.toggle_recording().await {
+                            warn!(error = %e, "Failed to finish silence-stopped recording");
+                        }
+                    }
+                }
+                Ok(AudioEvent::BufferFull { dropped_samples }) => {
+                    warn!(dropped_samples, "Recording buffer full, audio dropped");
+                    self.emit(AppEvent::RecordingTruncated { dropped_samples });
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// Recover from device loss automatically when `audio.auto_recover` is
+    /// set. Runs until the audio event channel closes.
+    pub async fn watch_audio_device(&self) {
+        self.audio_manager.watch_device_loss().await;
+    }
+
     /// Attempt to recover from audio error state.
     pub async fn recover_audio(&self) -> Result<(), DomainError> {
         self.audio_manager.recover().await
     }
 
+    /// Cut a running recovery short. Returns false if none is in progress.
+    pub fn cancel_recovery(&self) -> bool {
+        self.audio_manager.cancel_recovery()
+    }
+
+    /// Measure the selected device's effective sample rate over `duration_secs`.
+    pub async fn measure_capture_rate(
+        &self,
+        duration_secs: u32,
+    ) -> Result<CaptureRateMeasurement, DomainError> {
+        if !(1..=60).contains(&duration_secs) {
+            return Err(DomainError::Audio(format!(
+                "Measurement duration must be 1-60 seconds, got {}",
+                duration_secs
+            )));
+        }
+        self.audio_manager
+            .measure_capture_rate(std::time::Duration::from_secs(duration_secs.into()))
+            .await
+    }
+
     /// Get current recording duration in seconds.
     pub fn recording_duration(&self) -> f32 {
         self.audio_manager.current_duration()
@@ -208,6 +734,16 @@ impl AppController {
         self.audio_manager.current_level()
     }
 
+    /// Get current audio input level in dBFS.
+    pub fn audio_level_db(&self) -> f32 {
+        self.audio_manager.current_level_db()
+    }
+
+    /// Get the current peak input level (0.0-1.0), for clipping warnings.
+    pub fn audio_peak(&self) -> f32 {
+        self.audio_manager.current_peak()
+    }
+
     /// Toggle recording: start if idle, stop + transcribe + inject if recording.
     ///
     /// This is the main entry point for the global shortcut flow.
@@ -216,34 +752,118 @@ impl AppController {
     ///
     /// Uses an atomic guard to prevent concurrent calls (e.g., from keyboard repeat).
     pub async fn toggle_recording(&self) -> Result<ToggleResult, DomainError> {
+        // The audio manager may be joining its thread in Drop
+        ensure_not_shutting_down(&self.shutting_down)?;
+
         // Guard against concurrent toggle calls (keyboard repeat, double-tap)
-        if self
-            .toggle_in_progress
-            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
-            .is_err()
-        {
+        let Some(_lock) = ToggleLock::acquire(&self.toggle_in_progress) else {
             return Err(DomainError::Audio(
                 "Toggle already in progress".to_string(),
             ));
+        };
+
+        // The lock is released on drop, even on error or cancellation
+        self.toggle_recording_inner().await
+    }
+
+    /// Mark the app as exiting so the shortcut can't start new dictations.
+    pub fn begin_shutdown(&self) {
+        if !self.shutting_down.swap(true, Ordering::SeqCst) {
+            info!("Shutting down, new dictations are refused");
         }
+        // Zeroized on drop
+        self.pending_injection.clear();
+    }
 
-        // Ensure we reset the flag when we're done, even on error
-        let result = self.toggle_recording_inner().await;
-        self.toggle_in_progress.store(false, Ordering::SeqCst);
-        result
+    /// Mute or unmute dictation.
+    ///
+    /// While muted, toggle_recording won't start capture. A recording that is
+    /// already running can still be stopped.
+    pub fn set_muted(&self, muted: bool) {
+        if self.muted.swap(muted, Ordering::SeqCst) != muted {
+            info!(muted, "Dictation mute changed");
+            self.emit(AppEvent::MuteChanged { muted });
+        }
+    }
+
+    /// Check whether dictation is muted.
+    pub fn is_muted(&self) -> bool {
+        self.muted.load(Ordering::SeqCst)
+    }
+
+    /// Route the next completed dictation to `sink` instead of the configured
+    /// output, or clear a pending override with `None`.
+    ///
+    /// Cleared once a dictation is delivered to `sink`; if delivery fails,
+    /// it applies to the next dictation too.
+    pub fn set_next_output(&self, sink: Option<OutputSink>) -> Result<(), DomainError> {
+        if let Some(OutputSink::File { path }) = &sink {
+            if self.config.read().privacy.ephemeral {
+                return Err(DomainError::Config(
+                    "File output is disabled in ephemeral mode".to_string(),
+                ));
+            }
+            if !path.is_absolute() {
+                return Err(DomainError::Config(format!(
+                    "Output file must be an absolute path, got {}",
+                    path.display()
+                )));
+            }
+        }
+        debug!(sink = ?sink, "Next output override set");
+        *self.next_output_override.lock() = sink;
+        Ok(())
     }
 
     /// Inner implementation of toggle_recording (without concurrency guard).
     async fn toggle_recording_inner(&self) -> Result<ToggleResult, DomainError> {
         match self.audio_state() {
+            AudioState::Idle if self.is_muted() => {
+                info!("Toggle: ignored, dictation is muted");
+                Ok(ToggleResult::Muted)
+            }
             AudioState::Idle => {
+                if self.config.read().privacy.filters_apps() {
+                    // Detection shells out (osascript, xdotool)
+                    let output_manager = Arc::clone(&self.output_manager);
+                    let app = tokio::task::spawn_blocking(move || output_manager.frontmost_app())
+                        .await
+                        .ok()
+                        .flatten();
+                    if !self.config.read().privacy.is_app_allowed(app.as_deref()) {
+                        info!(app = ?app, "Toggle: refused, frontmost app is blocked");
+                        return Ok(ToggleResult::Blocked { app });
+                    }
+                }
                 self.start_recording().await?;
                 info!("Toggle: recording started");
                 Ok(ToggleResult::Started)
             }
             AudioState::Recording => {
+                // Cleared by deliver_output once the dictation lands there
+                let output_override = self.next_output_override.lock().clone();
+
                 // Stop recording
-                let buffer = self.stop_recording().await?;
+                let stop_started = Instant::now();
+                let buffer = Arc::new(self.stop_recording().await?);
+                let mut timings = ToggleTimings {
+                    stop_ms: stop_started.elapsed().as_millis() as u64,
+                    ..Default::default()
+                };
+                let recording_secs = buffer.duration_secs();
+                if self.config.read().privacy.retain_last_recording {
+                    *self.last_recording.lock() = Some(Arc::clone(&buffer));
+                }
+                if self.config.read().privacy.store_encrypted_audio {
+                    // Keychain access and encryption block; don't hold up transcription
+                    let recording_store = Arc::clone(&self.recording_store);
+                    let stored = Arc::clone(&buffer);
+                    tokio::task::spawn_blocking(move || {
+                        if let Err(e) = recording_store.save(&stored) {
+                            warn!(error = %e, "Failed to store encrypted recording");
+                        }
+                    });
+                }
                 info!(
                     duration_secs = buffer.duration_secs(),
                     samples = buffer.samples().len(),
@@ -251,23 +871,35 @@ impl AppController {
                 );
 
                 // Transcribe with VAD settings from config
-                let config = {
-                    let app_config = self.config.read();
-                    TranscribeConfig {
-                        language: if app_config.transcription.language == "auto" {
-                            None
-                        } else {
-                            Some(app_config.transcription.language.clone())
-                        },
-                        vad_enabled: app_config.transcription.vad_enabled,
-                        vad_no_speech_threshold: app_config.transcription.vad_no_speech_threshold,
-                        vad_entropy_threshold: app_config.transcription.vad_entropy_threshold,
-                        threads: 0, // Use default
-                    }
-                };
+                let config = self.transcribe_config(None);
+                let model = self.config.read().transcription.model.clone();
 
-                let result = self.transcriber.transcribe(&buffer, &config).await?;
-                // buffer is dropped here and zeroized automatically
+                self.emit(AppEvent::TogglePhase {
+                    phase: TogglePhase::Transcribing,
+                });
+                let model = self
+                    .select_model_for_language(config.language.as_deref())
+                    .await
+                    .unwrap_or(model);
+                let model = self
+                    .ensure_language_supported(config.language.as_deref())
+                    .await?
+                    .unwrap_or(model);
+                let result = if self.config.read().transcription.partial_results {
+                    let on_partial = |text: &str| {
+                        self.emit(AppEvent::TranscribeProgress {
+                            text: text.to_string(),
+                        })
+                    };
+                    self.transcriber
+                        .transcribe_streaming(&buffer, &config, &on_partial)
+                        .await?
+                } else {
+                    self.transcriber.transcribe(&buffer, &config).await?
+                };
+                timings.transcribe_ms = result.duration_ms;
+                // buffer is zeroized once the last reference is dropped
+                drop(buffer);
 
                 info!(
                     text_len = result.text.len(),
@@ -275,15 +907,60 @@ impl AppController {
                     "Toggle: transcription complete"
                 );
 
+                let language = result
+                    .detected_language
+                    .clone()
+                    .or_else(|| config.language.clone());
+                let summary = DictationSummary::new(
+                    &result.text,
+                    recording_secs,
+                    result.duration_ms,
+                    language.clone(),
+                    model,
+                );
+
+                // Stats keep the spoken language; the text itself is English
+                let language = if config.translate {
+                    Some("en".to_string())
+                } else {
+                    language
+                };
+
                 // Inject text into active application (skip if empty)
                 let text = if result.text.is_empty() {
+                    self.record_usage(&summary);
                     None
                 } else {
-                    self.output_manager.inject_text(&result.text).await?;
+                    self.cache_transcription(&result.text);
+                    if self.config.read().output.confirm_before_inject {
+                        self.hold_for_confirmation(
+                            &result.text,
+                            language,
+                            output_override,
+                            summary.clone(),
+                        );
+                    } else {
+                        timings.inject_ms = self
+                            .deliver_output(&result.text, language.as_deref(), output_override)
+                            .await?;
+                        self.record_delivered(&summary, &result.text);
+                    }
                     Some(result.text)
                 };
+                // Once the text is where it goes (or held for confirmation)
+                self.emit(AppEvent::DictationComplete(summary));
+
+                info!(
+                    stop_ms = timings.stop_ms,
+                    transcribe_ms = timings.transcribe_ms,
+                    inject_ms = timings.inject_ms,
+                    "Toggle: complete"
+                );
 
-                Ok(ToggleResult::Completed { text })
+                Ok(ToggleResult::Completed {
+                    text,
+                    timings: Some(timings),
+                })
             }
             AudioState::DeviceLost | AudioState::Recovering => {
                 Err(DomainError::Audio(
@@ -298,6 +975,159 @@ impl AppController {
         }
     }
 
+    /// Deliver a dictation to `sink` (the active app when `None`).
+    /// Returns how long injection took, in ms.
+    async fn deliver_output(
+        &self,
+        text: &str,
+        language: Option<&str>,
+        sink: Option<OutputSink>,
+    ) -> Result<u64, DomainError> {
+        self.emit(AppEvent::TogglePhase {
+            phase: TogglePhase::Injecting,
+        });
+        // Only the active app gets the append-mode transcript
+        let appends = matches!(sink, None | Some(OutputSink::ActiveApp))
+            && self.config.read().output.append_mode;
+        let chunk = self.next_output_chunk(text, language, appends);
+        let inject_started = Instant::now();
+        let clipboard_only = match &sink {
+            None | Some(OutputSink::ActiveApp) => {
+                self.output_manager.inject_text(&chunk).await?;
+                if appends {
+                    self.append_to_transcript(&chunk);
+                }
+                self.config.read().output.mode == OutputMode::ClipboardOnly
+            }
+            Some(OutputSink::Clipboard) => {
+                self.output_manager.copy_to_clipboard(&chunk)?;
+                true
+            }
+            Some(OutputSink::File { path }) => {
+                self.transcript_sink.append_text(path, &chunk)?;
+                false
+            }
+        };
+        if sink.is_some() {
+            // Delivered: the override is used up, unless replaced meanwhile
+            let mut next = self.next_output_override.lock();
+            if *next == sink {
+                *next = None;
+            }
+        }
+        if clipboard_only {
+            self.emit(AppEvent::TextReady {
+                text: text.to_string(),
+            });
+        }
+        Ok(inject_started.elapsed().as_millis() as u64)
+    }
+
+    /// Hold a dictation until `confirm_inject`, replacing any earlier one.
+    fn hold_for_confirmation(
+        &self,
+        text: &str,
+        language: Option<String>,
+        sink: Option<OutputSink>,
+        summary: DictationSummary,
+    ) {
+        let timeout = self.config.read().output.confirm_timeout_secs;
+        let expires_at = Instant::now() + std::time::Duration::from_secs(timeout as u64);
+        self.pending_injection.hold(PendingInjection {
+            text: Zeroizing::new(text.to_string()),
+            language,
+            sink,
+            summary,
+            expires_at,
+        });
+        info!(timeout_secs = timeout, "Toggle: text held for confirmation");
+        self.emit(AppEvent::ConfirmInject {
+            text: text.to_string(),
+        });
+
+        // Don't keep unconfirmed text in memory past its timeout
+        let slot = self.pending_injection.clone();
+        let event_tx = self.event_tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep_until(expires_at.into()).await;
+            if slot.expire(expires_at) {
+                info!("Held text expired before confirmation, discarded");
+                let _ = event_tx.send(AppEvent::ConfirmInjectExpired {
+                    timeout_secs: timeout,
+                });
+            }
+        });
+    }
+
+    /// Inject the dictation held by `confirm_before_inject`, or discard it.
+    ///
+    /// Fails with `NothingToConfirm` if nothing is held or it timed out
+    /// (after `confirm_timeout_secs` it is discarded, not injected).
+    pub async fn confirm_inject(&self, approved: bool) -> Result<(), DomainError> {
+        let Some(pending) = self.pending_injection.confirm(approved, Instant::now())? else {
+            return Ok(());
+        };
+        self.deliver_output(&pending.text, pending.language.as_deref(), pending.sink)
+            .await?;
+        self.record_delivered(&pending.summary, &pending.text);
+        info!("Held text injected after confirmation");
+        Ok(())
+    }
+
+    // ==================== Usage Stats Methods ====================
+
+    /// Get aggregate usage statistics.
+    pub fn usage_stats(&self) -> UsageStats {
+        self.usage_stats.read().clone()
+    }
+
+    /// Reset usage statistics to zero.
+    pub fn reset_usage_stats(&self) -> Result<(), DomainError> {
+        let mut stats = self.usage_stats.write();
+        *stats = UsageStats::default();
+        self.stats_store.save(&stats)?;
+        info!("Usage stats reset");
+        Ok(())
+    }
+
+    /// Add a dictation to the usage stats and persist them, if enabled.
+    fn record_usage(&self, summary: &DictationSummary) {
+        if !self.config.read().privacy.usage_stats {
+            return;
+        }
+
+        let mut stats = self.usage_stats.write();
+        stats.record(summary);
+        if let Err(e) = self.stats_store.save(&stats) {
+            warn!(error = %e, "Failed to save usage stats");
+        }
+    }
+
+    /// Count and export a dictation once its text was delivered.
+    fn record_delivered(&self, summary: &DictationSummary, text: &str) {
+        self.record_usage(summary);
+        self.export_transcript(summary, text);
+    }
+
+    /// Append a dictation to the JSON Lines sink, if one is configured.
+    fn export_transcript(&self, summary: &DictationSummary, text: &str) {
+        let path = {
+            let config = self.config.read();
+            if config.privacy.ephemeral {
+                return;
+            }
+            match &config.output.jsonl_sink {
+                Some(path) => path.clone(),
+                None => return,
+            }
+        };
+
+        let record = TranscriptRecord::new(summary, text);
+        if let Err(e) = self.transcript_sink.append(&path, &record) {
+            warn!(path = ?path, error = %e, "Failed to write transcript to sink");
+        }
+    }
+
     // ==================== Transcription Methods ====================
 
     /// Transcribe an audio buffer to text.
@@ -307,69 +1137,859 @@ impl AppController {
         config: Option<TranscribeConfig>,
     ) -> Result<TranscriptionResult, DomainError> {
         let config = config.unwrap_or_default();
+        self.ensure_language_supported(config.language.as_deref()).await?;
         self.transcriber.transcribe(&audio, &config).await
     }
 
-    /// Load a transcription model from the specified path.
-    pub async fn load_model(&self, path: PathBuf) -> Result<(), DomainError> {
-        self.transcriber.load_model(&path).await
-    }
-
-    /// Check if a transcription model is loaded.
-    pub fn is_model_loaded(&self) -> bool {
-        self.transcriber.is_model_loaded()
-    }
-
-    /// Unload the current transcription model.
-    pub fn unload_model(&self) {
-        self.transcriber.unload_model();
+    /// Re-transcribe the retained last recording with different settings.
+    ///
+    /// To use another model, load it first with `load_model`.
+    pub async fn retranscribe_last(
+        &self,
+        config: Option<TranscribeConfig>,
+    ) -> Result<TranscriptionResult, DomainError> {
+        let buffer = self
+            .last_recording
+            .lock()
+            .clone()
+            .ok_or_else(|| DomainError::Transcription("No retained recording".to_string()))?;
+        let config = config.unwrap_or_default();
+        self.ensure_language_supported(config.language.as_deref()).await?;
+        let result = self.transcriber.transcribe(&buffer, &config).await?;
+        if !result.text.is_empty() {
+            self.cache_transcription(&result.text);
+        }
+        Ok(result)
     }
 
-    // ==================== Model Management Methods ====================
+    /// Write the retained last recording to `path` as a 16-bit mono WAV, to
+    /// debug transcription accuracy. Requires `privacy.retain_last_recording`.
+    pub fn export_last_recording(&self, path: &Path) -> Result<(), DomainError> {
+        if !self.config.read().privacy.retain_last_recording {
+            return Err(DomainError::Config(
+                "Recordings are not retained; enable privacy.retain_last_recording to export"
+                    .to_string(),
+            ));
+        }
+        let buffer = self
+            .last_recording
+            .lock()
+            .clone()
+            .ok_or_else(|| DomainError::Audio("No retained recording".to_string()))?;
 
-    /// Get the model catalog.
-    pub fn model_catalog(&self) -> ModelCatalog {
-        self.model_manager.catalog().clone()
+        let wav = Zeroizing::new(buffer.to_wav_bytes());
+        std::fs::write(path, &*wav)?;
+        info!(path = ?path, duration_secs = buffer.duration_secs(), "Last recording exported");
+        Ok(())
     }
 
-    /// List installed models.
-    pub fn list_installed_models(&self) -> Result<Vec<InstalledModel>, DomainError> {
-        self.model_manager.list_installed()
+    /// Abort running transcriptions instead of waiting for them to finish.
+    /// Returns how many were running.
+    pub fn cancel_transcription(&self) -> usize {
+        self.transcriber.cancel_running()
     }
 
-    /// Check if a model is installed.
-    pub fn is_model_installed(&self, model_id: &str, quant: Quantization) -> bool {
-        self.model_manager.is_installed(model_id, quant)
+    /// List recordings stored encrypted on disk, newest first.
+    pub fn list_stored_recordings(&self) -> Result<Vec<StoredRecording>, DomainError> {
+        self.recording_store.list()
     }
 
-    /// Get the path to an installed model.
-    pub fn model_path(&self, model_id: &str, quant: Quantization) -> Option<PathBuf> {
-        self.model_manager.model_path(model_id, quant)
+    /// Delete a recording stored encrypted on disk.
+    pub fn delete_stored_recording(&self, id: &str) -> Result<(), DomainError> {
+        self.recording_store.delete(id)
     }
 
-    /// Download a model.
-    pub async fn download_model(
+    /// Decrypt a stored recording in memory and transcribe it again.
+    pub async fn retranscribe_stored(
         &self,
-        model_id: &str,
-        quant: Quantization,
-        progress: Option<Box<dyn Fn(DownloadProgress) + Send + Sync>>,
-    ) -> Result<InstalledModel, DomainError> {
-        self.model_manager.download(model_id, quant, progress).await
+        id: &str,
+        config: Option<TranscribeConfig>,
+    ) -> Result<TranscriptionResult, DomainError> {
+        self.retranscribe_stored_cancellable(id, config, Arc::default()).await
     }
 
-    /// Verify a model's integrity.
-    pub fn verify_model(&self, model_id: &str, quant: Quantization) -> Result<bool, DomainError> {
-        self.model_manager.verify(model_id, quant)
+    /// `retranscribe_stored`, aborted once `cancel` is set.
+    async fn retranscribe_stored_cancellable(
+        &self,
+        id: &str,
+        config: Option<TranscribeConfig>,
+        cancel: Arc<AtomicBool>,
+    ) -> Result<TranscriptionResult, DomainError> {
+        // Decrypted samples are zeroized when the buffer is dropped
+        let recording_store = Arc::clone(&self.recording_store);
+        let id = id.to_string();
+        let buffer = tokio::task::spawn_blocking(move || recording_store.load(&id))
+            .await
+            .map_err(|e| DomainError::Encryption(e.to_string()))
+            .and_then(|result| result)?;
+        let config = config.unwrap_or_default();
+        self.ensure_language_supported(config.language.as_deref()).await?;
+        let result = self
+            .transcriber
+            .transcribe_cancellable(&buffer, &config, cancel)
+            .await?;
+        if !result.text.is_empty() {
+            self.cache_transcription(&result.text);
+        }
+        Ok(result)
     }
 
-    /// Delete an installed model.
-    pub fn delete_model(&self, model_id: &str, quant: Quantization) -> Result<(), DomainError> {
-        self.model_manager.delete(model_id, quant)
+    /// Re-transcribe stored recordings one after another. Failures are
+    /// reported per recording; only one batch runs at a time.
+    pub async fn retranscribe_stored_batch(
+        &self,
+        ids: Vec<String>,
+        config: Option<TranscribeConfig>,
+    ) -> Result<Vec<BatchItemResult>, DomainError> {
+        let cancel = self.batch_queue.start(ids)?;
+        let mut results = Vec::new();
+        while let Some(id) = self.batch_queue.next() {
+            let outcome = self
+                .retranscribe_stored_cancellable(&id, config.clone(), Arc::clone(&cancel))
+                .await;
+            let (result, error) = match outcome {
+                Ok(result) => (Some(result), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            results.push(BatchItemResult { id, result, error });
+        }
+        info!(count = results.len(), "Batch transcription finished");
+        Ok(results)
     }
 
-    /// Get the models directory path.
-    pub fn models_dir(&self) -> PathBuf {
-        self.model_manager.models_dir()
+    /// Cancel the running batch: recordings not yet started are skipped and
+    /// the current one's decode is aborted instead of running to the end.
+    /// Dictations running meanwhile are unaffected. Returns how many
+    /// recordings were cancelled.
+    pub fn cancel_batch(&self) -> usize {
+        let cancelled = self.batch_queue.cancel().unwrap_or(0);
+        if cancelled > 0 {
+            info!(cancelled, "Batch transcription cancelled");
+        }
+        cancelled
+    }
+
+    /// Transcribe mono PCM captured outside the audio pipeline, e.g. by the
+    /// frontend. Resampled to 16kHz; the input is zeroized afterwards.
+    pub async fn transcribe_pcm(
+        &self,
+        samples: Vec<i16>,
+        sample_rate: u32,
+        config: Option<TranscribeConfig>,
+    ) -> Result<TranscriptionResult, DomainError> {
+        let samples = Zeroizing::new(samples);
+        let buffer = AudioBuffer::from_pcm(&samples, sample_rate)?;
+        drop(samples);
+
+        let config = config.unwrap_or_default();
+        self.ensure_language_supported(config.language.as_deref()).await?;
+        let result = self.transcriber.transcribe(&buffer, &config).await?;
+        if !result.text.is_empty() {
+            self.cache_transcription(&result.text);
+        }
+        Ok(result)
+    }
+
+    /// Copy the most recent transcription to the clipboard (no paste).
+    pub fn copy_last_transcription(&self) -> Result<(), DomainError> {
+        let last = self.last_transcription.lock();
+        let text = last
+            .as_ref()
+            .ok_or_else(|| DomainError::Clipboard("No transcription to copy".to_string()))?;
+        self.output_manager.copy_to_clipboard(text)
+    }
+
+    /// Get the text accumulated in append mode.
+    pub fn pending_transcript(&self) -> String {
+        self.pending_transcript.lock().to_string()
+    }
+
+    /// Finalize append mode: return the accumulated text and clear it.
+    pub fn finalize_transcript(&self) -> String {
+        let mut pending = self.pending_transcript.lock();
+        let text = pending.to_string();
+        // Replacing zeroizes the previous buffer on drop
+        *pending = Zeroizing::new(String::new());
+        info!(chars = text.len(), "Pending transcript finalized");
+        text
+    }
+
+    /// Text to inject for a new transcription; with `append` (append mode)
+    /// this is the chunk spaced for `language` to follow the pending transcript.
+    fn next_output_chunk(&self, text: &str, language: Option<&str>, append: bool) -> String {
+        let output = self.config.read().output.clone();
+        if !append {
+            return process_output(&output, "", text, language);
+        }
+        let pending = self.pending_transcript.lock();
+        process_output(&output, &pending, text, language)
+    }
+
+    /// Add an injected chunk to the pending transcript.
+    fn append_to_transcript(&self, chunk: &str) {
+        let mut pending = self.pending_transcript.lock();
+        // Sized up front: growing in place could reallocate and leave
+        // copies of the text behind that are never zeroized
+        let mut text = Zeroizing::new(String::with_capacity(pending.len() + chunk.len()));
+        text.push_str(&pending);
+        text.push_str(chunk);
+        // Replacing zeroizes the previous buffer on drop
+        *pending = text;
+    }
+
+    /// Preview what the current output settings would make of `input`,
+    /// as if it were the next dictation. Nothing is recorded or injected.
+    ///
+    /// Uses the configured language (none when auto-detecting) and, in
+    /// append mode, the current pending transcript without modifying it.
+    pub fn preview_text_processing(&self, input: &str) -> String {
+        let (output, language) = {
+            let config = self.config.read();
+            let language = normalize_language(&config.transcription.language)
+                .filter(|l| l != "auto");
+            (config.output.clone(), language)
+        };
+        let pending = self.pending_transcript.lock();
+        process_output(&output, &pending, input, language.as_deref())
+    }
+
+    /// Remember the latest transcription text for `copy_last_transcription`.
+    fn cache_transcription(&self, text: &str) {
+        *self.last_transcription.lock() = Some(Zeroizing::new(text.to_string()));
+    }
+
+    /// Check if a recording is retained for re-transcription.
+    pub fn has_last_recording(&self) -> bool {
+        self.last_recording.lock().is_some()
+    }
+
+    /// Drop the retained recording (zeroized once no transcription is using it).
+    pub fn clear_last_recording(&self) {
+        if self.last_recording.lock().take().is_some() {
+            info!("Retained recording cleared");
+        }
+    }
+
+    /// Check whether the transcription backend can run, with a reason if not.
+    pub async fn check_backend_available(&self) -> BackendStatus {
+        let capabilities = self.transcriber.capabilities();
+        if capabilities.requires_network && self.is_network_blocked() {
+            return BackendStatus {
+                name: capabilities.name,
+                available: false,
+                reason: Some("Network blocked by local-only mode".to_string()),
+            };
+        }
+        self.transcriber.check_available().await
+    }
+
+    /// Check an OpenAI API key with a minimal authenticated request (list
+    /// models) before saving it. Fails with `NetworkBlocked` in local-only mode.
+    pub async fn validate_api_key(&self, key: &str) -> Result<bool, DomainError> {
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(DomainError::Config("API key is empty".to_string()));
+        }
+        let valid = PrivacyGuard::global()
+            .check_bearer_token(OPENAI_MODELS_URL, key)
+            .await?;
+        info!(valid, "API key checked");
+        Ok(valid)
+    }
+
+    /// Load a transcription model from the specified path.
+    ///
+    /// If an installed model fails to load, its checksum is verified; a
+    /// mismatch marks it as corrupt and emits `ModelCorrupt`.
+    pub async fn load_model(&self, path: PathBuf) -> Result<(), DomainError> {
+        if let Err(e) = self.transcriber.load_model(&path).await {
+            self.check_failed_model(&path).await;
+            return Err(e);
+        }
+        *self.loaded_model.lock() = Some(path);
+        Ok(())
+    }
+
+    /// Verify an installed model that failed to load.
+    async fn check_failed_model(&self, path: &Path) {
+        if let Some(model) = verify_after_load_failure(&self.model_manager, path).await {
+            self.emit(AppEvent::ModelCorrupt {
+                model_id: model.id,
+                quantization: model.quantization,
+            });
+        }
+    }
+
+    /// Get the highest-quality installed variant of a model.
+    fn best_installed_variant(&self, model_id: &str) -> Option<Quantization> {
+        match self.model_manager.list_installed() {
+            Ok(installed) => installed
+                .into_iter()
+                .filter(|m| m.id == model_id)
+                .map(|m| m.quantization)
+                .max_by_key(|q| q.rank()),
+            Err(e) => {
+                warn!(error = %e, "Failed to list installed models");
+                None
+            }
+        }
+    }
+
+    /// Transcription settings from config for a dictation in `language`, or
+    /// the configured language when `None`.
+    fn transcribe_config(&self, language: Option<&str>) -> TranscribeConfig {
+        let config = self.config.read();
+        let transcription = &config.transcription;
+        TranscribeConfig {
+            language: normalize_language(language.unwrap_or(&transcription.language))
+                .filter(|language| language != "auto"),
+            vad_enabled: transcription.vad_enabled,
+            vad_no_speech_threshold: transcription.vad_no_speech_threshold,
+            vad_entropy_threshold: transcription.vad_entropy_threshold,
+            threads: 0, // Use default
+            min_segment_confidence: transcription.min_segment_confidence,
+            skip_silent_buffers: transcription.skip_silent_buffers,
+            stream_segments: transcription.live_segments,
+            translate: transcription.translate,
+            sampling: transcription.sampling,
+            initial_prompt: transcription.initial_prompt.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// The transcription settings the next toggle would use for `language`
+    /// (the configured language when `None`), with `threads` resolved to the
+    /// count the transcriber actually uses.
+    pub fn effective_transcribe_config(
+        &self,
+        language: Option<&str>,
+    ) -> Result<TranscribeConfig, DomainError> {
+        if let Some(language) = language {
+            normalize_language(language)
+                .ok_or_else(|| DomainError::Config(format!("Unknown language: {}", language)))?;
+        }
+        let mut config = self.transcribe_config(language);
+        config.threads = self.transcriber.threads();
+        Ok(config)
+    }
+
+    /// Switch to the model mapped to `language` in `language_model_map`, or
+    /// back to the default model, before transcribing.
+    ///
+    /// Returns the ID of the model in use, or `None` when no mapping is
+    /// configured (the loaded model is then left alone).
+    async fn select_model_for_language(&self, language: Option<&str>) -> Option<String> {
+        let (default_model, mapped) = {
+            let config = self.config.read();
+            let transcription = &config.transcription;
+            if transcription.language_model_map.is_empty() {
+                return None;
+            }
+            let mapped = language.and_then(|l| transcription.language_model_map.get(l).cloned());
+            (transcription.model.clone(), mapped)
+        };
+
+        for model_id in mapped.into_iter().chain(std::iter::once(default_model)) {
+            let Some(quant) = self.best_installed_variant(&model_id) else {
+                debug!(model_id, "Model for language not installed, trying fallback");
+                continue;
+            };
+
+            let already_loaded = self.transcriber.is_model_loaded()
+                && *self.loaded_model.lock() == self.model_path(&model_id, quant);
+            if !already_loaded {
+                if let Err(e) = self.load_model_with_events(&model_id, quant).await {
+                    warn!(model_id, error = %e, "Failed to switch model for language");
+                    continue;
+                }
+            }
+
+            info!(model_id, language, "Model selected for language");
+            self.emit(AppEvent::ModelSelected {
+                model: model_id.clone(),
+                language: language.map(String::from),
+            });
+            return Some(model_id);
+        }
+        None
+    }
+
+    /// Make sure the loaded model can transcribe `language` before using it.
+    ///
+    /// English-only models can't handle any other forced language: switch to
+    /// an installed multilingual model, or fail with `LanguageUnsupported`
+    /// when there is none. Either way a `LanguageUnsupported` event warns the
+    /// user. Returns the ID of the model switched to.
+    async fn ensure_language_supported(
+        &self,
+        language: Option<&str>,
+    ) -> Result<Option<String>, DomainError> {
+        let Some(language) = language.filter(|language| *language != "en") else {
+            return Ok(None);
+        };
+        if self.transcriber.is_multilingual() != Some(false) {
+            return Ok(None);
+        }
+
+        let installed = self.list_installed_models()?;
+        let loaded = self.loaded_model.lock().clone();
+        let model = installed
+            .iter()
+            .find(|m| Some(&m.path) == loaded.as_ref())
+            .map_or_else(|| self.config.read().transcription.model.clone(), |m| m.id.clone());
+        let fallback = self
+            .model_manager
+            .catalog()
+            .multilingual_fallback(&installed)
+            .map(|(info, quant)| (info.id.clone(), quant));
+
+        let switched_to = match fallback {
+            Some((model_id, quant)) => match self.load_model_with_events(&model_id, quant).await {
+                Ok(()) => Some(model_id),
+                Err(e) => {
+                    warn!(model_id, error = %e, "Failed to load multilingual model");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        warn!(language, model, switched_to, "Loaded model is English-only");
+        self.emit(AppEvent::LanguageUnsupported {
+            language: language.to_string(),
+            model: model.clone(),
+            switched_to: switched_to.clone(),
+        });
+        match switched_to {
+            Some(model_id) => Ok(Some(model_id)),
+            None => Err(DomainError::LanguageUnsupported {
+                language: language.to_string(),
+                model,
+            }),
+        }
+    }
+
+    /// Load the configured model if it is installed, reporting progress via
+    /// `ModelLoad` events. Failures are logged and never propagated.
+    pub async fn preload_model(&self) {
+        let model_id = self.config.read().transcription.model.clone();
+
+        // Prefer the highest-quality installed variant
+        let Some(quant) = self.best_installed_variant(&model_id) else {
+            debug!(model_id, "Configured model not installed, skipping preload");
+            return;
+        };
+
+        match self.load_model_with_events(&model_id, quant).await {
+            Ok(()) => info!(model_id, quantization = %quant, "Model preloaded"),
+            Err(e) => warn!(model_id, error = %e, "Model preload failed"),
+        }
+    }
+
+    /// Check at startup that the user has a path to a working model.
+    ///
+    /// Emits `NoModelsAvailable` with the catalog when nothing is installed
+    /// and no recommendation can be made, so the UI can prompt a download.
+    pub fn check_models_available(&self) {
+        let catalog = self.model_manager.catalog();
+        let model_id = self.config.read().transcription.model.clone();
+        if catalog.get(&model_id).is_none() {
+            warn!(model_id, "Configured model is not in the catalog");
+        }
+
+        if let Some(reason) = self.models_unavailable_reason() {
+            warn!(reason, models = catalog.models.len(), "No models available");
+            self.emit(AppEvent::NoModelsAvailable {
+                catalog: catalog.clone(),
+                reason,
+            });
+        }
+    }
+
+    /// Why no model is installed or can be recommended, or `None` when there
+    /// is a path to a working model. Lets the UI check what the startup
+    /// `NoModelsAvailable` event reported, as it fires before the UI listens.
+    pub fn models_unavailable_reason(&self) -> Option<String> {
+        let installed = match self.model_manager.list_installed() {
+            Ok(installed) => installed,
+            Err(e) => {
+                warn!(error = %e, "Failed to list installed models");
+                Vec::new()
+            }
+        };
+        if !installed.is_empty() {
+            return None;
+        }
+
+        match self.recommended_model() {
+            Ok(recommendation) => {
+                info!(
+                    model_id = %recommendation.model_id,
+                    "No models installed, recommendation available"
+                );
+                None
+            }
+            Err(e) => Some(e.to_string()),
+        }
+    }
+
+    /// Download (if needed), load and select the hardware-recommended model.
+    ///
+    /// Idempotent: installed models are not re-downloaded and an already
+    /// loaded model is not reloaded. Progress is reported via `ModelDownload`
+    /// and `ModelLoad` events.
+    pub async fn setup_recommended_model(&self) -> Result<ModelRecommendation, DomainError> {
+        let recommendation = self.recommended_model()?;
+        let model_id = recommendation.model_id.as_str();
+        let quant = recommendation.quantization;
+
+        if !self.is_model_installed(model_id, quant) {
+            info!(model_id, quantization = %quant, "Downloading recommended model");
+            let event_tx = self.event_tx.clone();
+            let progress = Box::new(move |progress| {
+                let _ = event_tx.send(AppEvent::ModelDownload(progress));
+            });
+            self.download_model(model_id, quant, Some(progress)).await?;
+        }
+
+        self.select_installed_model(model_id, quant).await?;
+
+        info!(model_id, quantization = %quant, "Recommended model ready");
+        Ok(recommendation)
+    }
+
+    /// Make sure a fresh install has a model to dictate with.
+    ///
+    /// Does nothing until onboarding is acknowledged or when any model is
+    /// installed. Otherwise installs a model shipped in `bundled_dir`
+    /// (preferring the recommended one), or downloads the recommended model
+    /// unless local-only mode blocks it; then emits `DefaultModelBlocked` so
+    /// the UI can ask the user to allow the download.
+    pub async fn ensure_default_model(
+        &self,
+        bundled_dir: Option<&Path>,
+    ) -> Result<DefaultModelSetup, DomainError> {
+        if !self.model_manager.list_installed()?.is_empty() {
+            self.mark_default_model_setup_done()?;
+            return Ok(DefaultModelSetup::AlreadyInstalled);
+        }
+        if !self.config.read().ui.onboarding_acknowledged {
+            return Ok(DefaultModelSetup::NeedsAcknowledgment);
+        }
+
+        let recommendation = self.recommended_model()?;
+        if let Some((model_id, quant, source)) = bundled_dir
+            .and_then(|dir| bundled_model(self.model_manager.catalog(), &recommendation, dir))
+        {
+            self.model_manager.install_from_file(&model_id, quant, &source)?;
+            self.select_installed_model(&model_id, quant).await?;
+            self.mark_default_model_setup_done()?;
+            info!(model_id, quantization = %quant, "Bundled model installed");
+            return Ok(DefaultModelSetup::Installed {
+                model_id,
+                bundled: true,
+            });
+        }
+
+        let model_id = recommendation.model_id;
+        if self.config.read().privacy.local_only {
+            info!(model_id, "First-run model download blocked by local-only mode");
+            self.emit(AppEvent::DefaultModelBlocked {
+                model_id: model_id.clone(),
+            });
+            return Ok(DefaultModelSetup::BlockedByLocalOnly { model_id });
+        }
+
+        self.setup_recommended_model().await?;
+        self.mark_default_model_setup_done()?;
+        Ok(DefaultModelSetup::Installed {
+            model_id,
+            bundled: false,
+        })
+    }
+
+    /// Record that the first-run model setup is over.
+    fn mark_default_model_setup_done(&self) -> Result<(), DomainError> {
+        if self.config.read().ui.default_model_setup_done {
+            return Ok(());
+        }
+        let mut config = self.config();
+        config.ui.default_model_setup_done = true;
+        self.update_config(config)
+    }
+
+    /// Record that the user completed onboarding.
+    pub fn acknowledge_onboarding(&self) -> Result<(), DomainError> {
+        if self.config.read().ui.onboarding_acknowledged {
+            return Ok(());
+        }
+        let mut config = self.config();
+        config.ui.onboarding_acknowledged = true;
+        self.update_config(config)
+    }
+
+    /// Load an installed model and make it the configured one.
+    async fn select_installed_model(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+    ) -> Result<(), DomainError> {
+        self.load_model_with_events(model_id, quant).await?;
+
+        if self.config.read().transcription.model != model_id {
+            let mut config = self.config();
+            config.transcription.model = model_id.to_string();
+            self.update_config(config)?;
+        }
+        Ok(())
+    }
+
+    /// Load an installed model, reporting progress via `ModelLoad` events.
+    async fn load_model_with_events(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+    ) -> Result<(), DomainError> {
+        let emit_phase = |phase, error| {
+            self.emit(AppEvent::ModelLoad {
+                model: model_id.to_string(),
+                phase,
+                error,
+            })
+        };
+
+        emit_phase(ModelLoadPhase::Loading, None);
+        let result = match self.model_path(model_id, quant) {
+            Some(path) => self.load_model(path).await,
+            None => Err(DomainError::ModelNotFound(format!("{}-{}", model_id, quant))),
+        };
+        match &result {
+            Ok(()) => emit_phase(ModelLoadPhase::Loaded, None),
+            Err(e) => emit_phase(ModelLoadPhase::Failed, Some(e.to_string())),
+        }
+        result
+    }
+
+    /// Check if a transcription model is loaded.
+    pub fn is_model_loaded(&self) -> bool {
+        self.transcriber.is_model_loaded()
+    }
+
+    /// Unload the current transcription model.
+    pub fn unload_model(&self) {
+        self.transcriber.unload_model();
+        *self.loaded_model.lock() = None;
+    }
+
+    // ==================== Model Management Methods ====================
+
+    /// Check whether the model used for `language` (from `language_model_map`,
+    /// else the configured model) is too small for it.
+    ///
+    /// Returns `None` for auto-detection or when the model is big enough.
+    pub fn suggest_model_for_language(
+        &self,
+        language: &str,
+    ) -> Result<Option<LanguageModelSuggestion>, DomainError> {
+        let language = normalize_language(language)
+            .ok_or_else(|| DomainError::Config(format!("Unknown language: {}", language)))?;
+        if language == "auto" {
+            return Ok(None);
+        }
+
+        let model = {
+            let config = self.config.read();
+            let transcription = &config.transcription;
+            transcription
+                .language_model_map
+                .get(&language)
+                .unwrap_or(&transcription.model)
+                .clone()
+        };
+        Ok(self
+            .model_manager
+            .catalog()
+            .suggest_model_for_language(&model, &language))
+    }
+
+    /// Get the model catalog.
+    pub fn model_catalog(&self) -> ModelCatalog {
+        self.model_manager.catalog().clone()
+    }
+
+    /// Serialize the current model catalog (URLs and checksums) as pretty JSON.
+    pub fn catalog_json(&self) -> Result<String, DomainError> {
+        Ok(serde_json::to_string_pretty(self.model_manager.catalog())?)
+    }
+
+    /// Write the current model catalog to a JSON file.
+    pub fn export_catalog(&self, path: &Path) -> Result<(), DomainError> {
+        std::fs::write(path, self.catalog_json()?)?;
+        info!(path = ?path, "Model catalog exported");
+        Ok(())
+    }
+
+    /// List installed models.
+    pub fn list_installed_models(&self) -> Result<Vec<InstalledModel>, DomainError> {
+        self.model_manager.list_installed()
+    }
+
+    /// Check if a model is installed.
+    pub fn is_model_installed(&self, model_id: &str, quant: Quantization) -> bool {
+        self.model_manager.is_installed(model_id, quant)
+    }
+
+    /// Get the path to an installed model.
+    pub fn model_path(&self, model_id: &str, quant: Quantization) -> Option<PathBuf> {
+        self.model_manager.model_path(model_id, quant)
+    }
+
+    /// Get the path of an installed model, falling back to the closest
+    /// installed quantization when the requested one is absent.
+    pub fn resolve_model_path(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+    ) -> Result<PathBuf, DomainError> {
+        let quant = self.resolve_installed_variant(model_id, quant)?;
+        self.model_path(model_id, quant)
+            .ok_or_else(|| DomainError::ModelNotFound(format!("{}-{}", model_id, quant)))
+    }
+
+    /// Get the installed quantization to use for a request: the requested one,
+    /// or the closest installed variant.
+    fn resolve_installed_variant(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+    ) -> Result<Quantization, DomainError> {
+        if self.model_manager.is_installed(model_id, quant) {
+            return Ok(quant);
+        }
+
+        let not_installed = || DomainError::ModelNotFound(format!("{}-{}", model_id, quant));
+        let info = self.model_manager.catalog().get(model_id).ok_or_else(not_installed)?;
+        let installed = self.model_manager.list_installed()?;
+        let nearest = info
+            .nearest_variant(quant, &installed)
+            .ok_or_else(not_installed)?;
+
+        warn!(
+            model_id,
+            requested = %quant,
+            using = %nearest,
+            "Requested quantization not installed, using closest installed variant"
+        );
+        Ok(nearest)
+    }
+
+    /// Load an installed model by ID, refusing (unless `force`) when it needs
+    /// more memory than is currently available.
+    pub async fn load_model_by_id(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+        force: bool,
+    ) -> Result<(), DomainError> {
+        let quant = self.resolve_installed_variant(model_id, quant)?;
+        if !force {
+            self.check_model_fits(model_id, quant)?;
+        }
+
+        let path = self.resolve_model_path(model_id, quant)?;
+        self.load_model(path).await
+    }
+
+    /// Check a model variant's estimated memory use against available memory.
+    fn check_model_fits(&self, model_id: &str, quant: Quantization) -> Result<(), DomainError> {
+        const GIB: f64 = (1024 * 1024 * 1024) as f64;
+
+        let Some(required) = self
+            .model_manager
+            .catalog()
+            .get(model_id)
+            .and_then(|info| info.estimated_ram_bytes(quant))
+        else {
+            return Ok(());
+        };
+
+        let available = match self.hardware_detector.available_memory() {
+            Ok(available) => available,
+            Err(e) => {
+                warn!(error = %e, "Could not measure available memory, skipping RAM check");
+                return Ok(());
+            }
+        };
+
+        if required > available {
+            return Err(DomainError::Model(format!(
+                "{}-{} requires ~{:.1} GB but only {:.1} GB available",
+                model_id,
+                quant,
+                required as f64 / GIB,
+                available as f64 / GIB
+            )));
+        }
+        Ok(())
+    }
+
+    /// Download a model.
+    pub async fn download_model(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+        progress: Option<Box<dyn Fn(DownloadProgress) + Send + Sync>>,
+    ) -> Result<InstalledModel, DomainError> {
+        self.model_manager.download(model_id, quant, progress).await
+    }
+
+    /// Get active and queued model downloads.
+    pub fn download_queue(&self) -> Vec<DownloadQueueEntry> {
+        self.model_manager.download_queue()
+    }
+
+    /// Move a queued download to a new position.
+    pub fn reorder_download(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+        position: usize,
+    ) -> Result<(), DomainError> {
+        self.model_manager.reorder_download(model_id, quant, position)
+    }
+
+    /// Remove a download that hasn't started yet.
+    pub fn remove_from_queue(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+    ) -> Result<(), DomainError> {
+        self.model_manager.remove_from_queue(model_id, quant)
+    }
+
+    /// Verify a model's integrity.
+    pub fn verify_model(&self, model_id: &str, quant: Quantization) -> Result<bool, DomainError> {
+        self.model_manager.verify(model_id, quant)
+    }
+
+    /// Delete an installed model.
+    pub fn delete_model(&self, model_id: &str, quant: Quantization) -> Result<(), DomainError> {
+        self.model_manager.delete(model_id, quant)
+    }
+
+    /// Get the models directory path.
+    pub fn models_dir(&self) -> PathBuf {
+        self.model_manager.models_dir()
+    }
+
+    /// List every file in the models directory, classified.
+    pub fn models_dir_contents(&self) -> Result<Vec<ModelsDirEntry>, DomainError> {
+        self.model_manager.list_dir_contents()
+    }
+
+    /// Delete a file from the models directory by name.
+    pub fn delete_models_dir_file(&self, name: &str) -> Result<(), DomainError> {
+        self.model_manager.delete_dir_file(name)
     }
 
     // ==================== Hardware Methods ====================
@@ -384,4 +2004,433 @@ impl AppController {
         self.hardware_detector
             .recommend_model(self.model_manager.catalog())
     }
+
+    /// Use `profile` instead of the detected hardware for recommendations
+    /// and the transcriber thread count.
+    ///
+    /// With `persist`, the override is saved to config and survives restarts.
+    pub fn set_hardware_override(
+        &self,
+        profile: HardwareProfile,
+        persist: bool,
+    ) -> Result<(), DomainError> {
+        profile.validate()?;
+        if persist {
+            let mut config = self.config();
+            config.hardware_override = Some(profile);
+            return self.update_config(config);
+        }
+        self.apply_hardware_override(Some(profile));
+        Ok(())
+    }
+
+    /// Go back to the detected hardware profile, removing any saved override.
+    pub fn clear_hardware_override(&self) -> Result<(), DomainError> {
+        let mut config = self.config();
+        if config.hardware_override.take().is_some() {
+            return self.update_config(config);
+        }
+        self.apply_hardware_override(None);
+        Ok(())
+    }
+
+    /// Compare the transcription thread count against the hardware profile.
+    pub fn thread_usage(&self) -> Result<ThreadUsage, DomainError> {
+        Ok(self
+            .hardware_detector
+            .profile()?
+            .thread_usage(self.transcriber.threads()))
+    }
+
+    /// Measure the transcription speed of the best installed variant of
+    /// every model, for `language` (auto-detect when `None`).
+    ///
+    /// Results are cached per hardware fingerprint: cached ones are returned
+    /// unless `force` is set or the profile (including any override) changed.
+    /// Dictation is blocked while models are swapped in; the previously
+    /// loaded model is restored afterwards.
+    pub async fn benchmark_models(
+        &self,
+        language: Option<&str>,
+        force: bool,
+    ) -> Result<Vec<BenchmarkResult>, DomainError> {
+        let language = match language {
+            Some(language) => normalize_language(language)
+                .ok_or_else(|| DomainError::Config(format!("Unknown language: {}", language)))?,
+            None => "auto".to_string(),
+        };
+        let language = Some(language).filter(|language| language != "auto");
+
+        let mut cache = self.benchmark_store.load().unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to load benchmark cache, starting fresh");
+            BenchmarkCache::default()
+        });
+        cache.invalidate_unless(&self.hardware_detector.profile()?.fingerprint());
+
+        let mut model_ids: Vec<String> = self
+            .model_manager
+            .list_installed()?
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        model_ids.sort();
+        model_ids.dedup();
+        let variants: Vec<(String, Quantization)> = model_ids
+            .into_iter()
+            .filter_map(|id| self.best_installed_variant(&id).map(|quant| (id, quant)))
+            .collect();
+
+        let stale: Vec<&(String, Quantization)> = variants
+            .iter()
+            .filter(|(id, quant)| force || cache.get(id, *quant, language.as_deref()).is_none())
+            .collect();
+        if !stale.is_empty() {
+            let lock = match self.audio_state() {
+                AudioState::Idle => ToggleLock::acquire(&self.toggle_in_progress),
+                _ => None,
+            };
+            let Some(lock) = lock else {
+                return Err(DomainError::Audio(
+                    "Can't benchmark models during a dictation".to_string(),
+                ));
+            };
+            let measured = self.run_benchmarks(&stale, language.as_deref()).await;
+            drop(lock);
+
+            for result in measured {
+                cache.insert(result);
+            }
+            if let Err(e) = self.benchmark_store.save(&cache) {
+                warn!(error = %e, "Failed to save benchmark cache");
+            }
+        }
+
+        Ok(variants
+            .iter()
+            .filter_map(|(id, quant)| cache.get(id, *quant, language.as_deref()).cloned())
+            .collect())
+    }
+
+    /// Load each variant in turn and time the transcription of a synthetic
+    /// clip. Variants that fail to load or transcribe are skipped.
+    async fn run_benchmarks(
+        &self,
+        variants: &[&(String, Quantization)],
+        language: Option<&str>,
+    ) -> Vec<BenchmarkResult> {
+        let previous = self.loaded_model.lock().clone();
+        let audio = benchmark_audio();
+        let config = TranscribeConfig {
+            language: language.map(String::from),
+            vad_enabled: false,
+            skip_silent_buffers: false,
+            ..Default::default()
+        };
+
+        let mut results = Vec::new();
+        for (model_id, quant) in variants {
+            let measured = async {
+                self.load_model(self.resolve_model_path(model_id, *quant)?).await?;
+                let started = Instant::now();
+                self.transcriber.transcribe(&audio, &config).await?;
+                Ok::<_, DomainError>(started.elapsed().as_secs_f32())
+            }
+            .await;
+
+            match measured {
+                Ok(elapsed) => {
+                    let realtime_factor = elapsed / audio.duration_secs();
+                    info!(model_id, %quant, realtime_factor, "Model benchmarked");
+                    results.push(BenchmarkResult {
+                        model: model_id.clone(),
+                        quant: *quant,
+                        language: language.map(String::from),
+                        realtime_factor,
+                        measured_at: SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .map(|d| d.as_millis() as u64)
+                            .unwrap_or(0),
+                    });
+                }
+                Err(e) => warn!(model_id, %quant, error = %e, "Model benchmark failed"),
+            }
+        }
+
+        match previous {
+            Some(path) => {
+                if let Err(e) = self.load_model(path).await {
+                    warn!(error = %e, "Failed to reload model after benchmark");
+                }
+            }
+            None => self.unload_model(),
+        }
+        results
+    }
+
+    fn apply_hardware_override(&self, profile: Option<HardwareProfile>) {
+        self.hardware_detector.set_override(profile);
+        if let Ok(profile) = self.hardware_detector.profile() {
+            self.transcriber.set_threads(profile.recommended_threads());
+            warn_if_threads_underused(&profile.thread_usage(self.transcriber.threads()));
+        }
+    }
+
+    /// Get the graphical session type used for text injection.
+    pub fn session_type(&self) -> SessionType {
+        self.output_manager.session_type()
+    }
+}
+
+/// Find a catalog model file in `dir`, preferring the recommended variant,
+/// then the first (smallest) catalog entry present.
+fn bundled_model(
+    catalog: &ModelCatalog,
+    recommendation: &ModelRecommendation,
+    dir: &Path,
+) -> Option<(String, Quantization, PathBuf)> {
+    let file = |model_id: &str, quant: Quantization| {
+        let path = dir.join(format!("{}-{}.bin", model_id, quant.suffix()));
+        path.is_file().then(|| (model_id.to_string(), quant, path))
+    };
+
+    file(&recommendation.model_id, recommendation.quantization).or_else(|| {
+        catalog.models.iter().find_map(|model| {
+            model
+                .variants
+                .iter()
+                .find_map(|variant| file(&model.id, variant.quantization))
+        })
+    })
+}
+
+/// Synthetic benchmark clip: a quiet tone with a slow pitch sweep, so the
+/// decoder does real work rather than skipping silence.
+fn benchmark_audio() -> AudioBuffer {
+    let rate = WHISPER_SAMPLE_RATE;
+    let samples: Vec<i16> = (0..rate * BENCHMARK_AUDIO_SECS)
+        .map(|i| {
+            let t = i as f32 / rate as f32;
+            let freq = 200.0 + 20.0 * t;
+            ((t * freq * std::f32::consts::TAU).sin() * 4000.0) as i16
+        })
+        .collect();
+    let mut buffer = AudioBuffer::with_capacity(rate, samples.len());
+    buffer.push_samples(&samples);
+    buffer
+}
+
+/// Log a warning when transcription runs on far fewer threads than cores.
+fn warn_if_threads_underused(usage: &ThreadUsage) {
+    if usage.underused {
+        warn!(
+            threads = usage.threads,
+            cores = usage.cores,
+            recommended = usage.recommended,
+            "Transcription uses far fewer threads than available cores"
+        );
+    }
+}
+
+/// Checksum the installed model at `path` after it failed to load, marking
+/// it as failed verification if it is corrupt. Returns the corrupt model.
+async fn verify_after_load_failure<M: ModelManager + 'static>(
+    model_manager: &Arc<M>,
+    path: &Path,
+) -> Option<InstalledModel> {
+    let model = model_manager
+        .list_installed()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|m| m.path == path)?;
+
+    // Hashing a multi-GB file is blocking work
+    let manager = Arc::clone(model_manager);
+    let (model_id, quant) = (model.id.clone(), model.quantization);
+    let verified = tokio::task::spawn_blocking(move || manager.verify(&model_id, quant))
+        .await
+        .map_err(|e| DomainError::Model(e.to_string()))
+        .and_then(|result| result);
+
+    match verified {
+        Ok(true) => None,
+        Ok(false) => {
+            warn!(model_id = %model.id, quant = %model.quantization, "Model file is corrupt");
+            model_manager.mark_verification_failed(&model.id, model.quantization);
+            Some(model)
+        }
+        Err(e) => {
+            warn!(error = %e, "Failed to verify model after load failure");
+            None
+        }
+    }
+}
+
+/// Fail with `ShuttingDown` once `begin_shutdown` was called.
+fn ensure_not_shutting_down(flag: &AtomicBool) -> Result<(), DomainError> {
+    if flag.load(Ordering::SeqCst) {
+        return Err(DomainError::ShuttingDown);
+    }
+    Ok(())
+}
+
+/// Holds `toggle_in_progress` until dropped, so the flag is released on
+/// every exit path, including panics and cancelled futures.
+struct ToggleLock<'a>(&'a AtomicBool);
+
+impl<'a> ToggleLock<'a> {
+    /// Take the lock, or `None` if a toggle or benchmark already holds it.
+    fn acquire(flag: &'a AtomicBool) -> Option<Self> {
+        flag.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .ok()
+            .map(|_| Self(flag))
+    }
+}
+
+impl Drop for ToggleLock<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_load_failure_marks_corrupt_model() {
+        let dir = std::env::temp_dir().join("opensay_load_failure_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("models")).unwrap();
+        let path = dir.join("models").join("whisper-base-q5_1.bin");
+        // A truncated file whisper can't load
+        std::fs::write(&path, b"lmgg\x01\x00").unwrap();
+
+        let manager = Arc::new(LocalModelManager::new(dir.clone()).unwrap());
+        let corrupt = verify_after_load_failure(&manager, &path).await.unwrap();
+        assert_eq!(corrupt.id, "whisper-base");
+        assert!(manager.list_installed().unwrap()[0].verification_failed);
+
+        // Not an installed model: nothing to verify
+        assert!(verify_after_load_failure(&manager, &dir.join("other.bin")).await.is_none());
+
+        // Cleanup
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_pending_injection_confirm() {
+        let slot = PendingSlot::default();
+        let now = Instant::now();
+        let hold = |text: &str, expires_at| {
+            slot.hold(PendingInjection {
+                text: Zeroizing::new(text.to_string()),
+                language: None,
+                sink: None,
+                summary: DictationSummary::new(text, 1.0, 100, None, String::new()),
+                expires_at,
+            })
+        };
+        let later = now + std::time::Duration::from_secs(60);
+        assert!(matches!(slot.confirm(true, now), Err(DomainError::NothingToConfirm)));
+
+        // Approved: handed over for injection, once
+        hold("approve me", later);
+        let pending = slot.confirm(true, now).unwrap().unwrap();
+        assert_eq!(pending.text.as_str(), "approve me");
+        assert!(slot.confirm(true, now).is_err());
+
+        // Rejected: discarded without injecting
+        hold("reject me", later);
+        assert!(slot.confirm(false, now).unwrap().is_none());
+        assert!(slot.confirm(true, now).is_err());
+
+        // Expired before the timer ran
+        hold("too late", later);
+        assert!(matches!(slot.confirm(true, later), Err(DomainError::NothingToConfirm)));
+
+        // Expired by the timer; a replaced dictation's timer does nothing
+        hold("first", now);
+        hold("second", later);
+        assert!(!slot.expire(now));
+        assert!(slot.expire(later));
+        assert!(!slot.expire(later));
+        assert!(matches!(slot.confirm(true, now), Err(DomainError::NothingToConfirm)));
+    }
+
+    #[test]
+    fn test_batch_queue_cancel() {
+        let queue = BatchQueue::default();
+        assert_eq!(queue.cancel(), None);
+
+        let cancel = queue.start(vec!["a".to_string(), "b".to_string(), "c".to_string()]).unwrap();
+        assert!(queue.start(vec!["d".to_string()]).is_err());
+        assert_eq!(queue.next().as_deref(), Some("a"));
+
+        // "a" is aborted, the rest skipped, and the batch ends at the next step
+        assert_eq!(queue.cancel(), Some(3));
+        assert!(cancel.load(Ordering::Relaxed));
+        assert_eq!(queue.next(), None);
+        assert_eq!(queue.cancel(), None);
+
+        queue.start(vec!["d".to_string()]).unwrap();
+        assert_eq!(queue.next().as_deref(), Some("d"));
+        assert_eq!(queue.next(), None);
+    }
+
+    #[test]
+    fn test_shutdown_flag_refuses_new_work() {
+        let flag = AtomicBool::new(false);
+        assert!(ensure_not_shutting_down(&flag).is_ok());
+
+        flag.store(true, Ordering::SeqCst);
+        assert!(matches!(
+            ensure_not_shutting_down(&flag),
+            Err(DomainError::ShuttingDown)
+        ));
+    }
+
+    #[test]
+    fn test_toggle_lock_released_on_drop() {
+        let flag = AtomicBool::new(false);
+        let lock = ToggleLock::acquire(&flag).unwrap();
+        assert!(ToggleLock::acquire(&flag).is_none());
+        drop(lock);
+        assert!(!flag.load(Ordering::SeqCst));
+
+        // A panic while holding the lock still releases it
+        let result = std::panic::catch_unwind(|| {
+            let _lock = ToggleLock::acquire(&flag).unwrap();
+            panic!("benchmark failed");
+        });
+        assert!(result.is_err());
+        assert!(ToggleLock::acquire(&flag).is_some());
+    }
+
+    #[test]
+    fn test_bundled_model_prefers_recommendation() {
+        let catalog: ModelCatalog =
+            serde_json::from_str(include_str!("../../resources/model_catalog.json")).unwrap();
+        let dir = std::env::temp_dir().join("opensay_bundled_model_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let recommendation = ModelRecommendation {
+            model_id: "whisper-small".to_string(),
+            quantization: Quantization::Q5_1,
+            reason: String::new(),
+        };
+        assert!(bundled_model(&catalog, &recommendation, &dir).is_none());
+
+        std::fs::write(dir.join("whisper-base-q5_1.bin"), b"").unwrap();
+        let (model_id, quant, _) = bundled_model(&catalog, &recommendation, &dir).unwrap();
+        assert_eq!((model_id.as_str(), quant), ("whisper-base", Quantization::Q5_1));
+
+        std::fs::write(dir.join("whisper-small-q5_1.bin"), b"").unwrap();
+        let (model_id, _, path) = bundled_model(&catalog, &recommendation, &dir).unwrap();
+        assert_eq!(model_id, "whisper-small");
+        assert_eq!(path, dir.join("whisper-small-q5_1.bin"));
+
+        // Cleanup
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
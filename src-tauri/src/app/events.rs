@@ -0,0 +1,236 @@
+use serde::Serialize;
+
+use crate::domain::{
+    AudioState, DictationSummary, DownloadProgress, LanguageModelSuggestion, ModelCatalog,
+    Quantization, TranscriptSegment,
+};
+
+/// Stage of the toggle flow after recording stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TogglePhase {
+    /// Running speech-to-text on the captured audio.
+    Transcribing,
+    /// Delivering the text to the clipboard / active application.
+    Injecting,
+}
+
+/// Stage of a background model load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelLoadPhase {
+    Loading,
+    Loaded,
+    Failed,
+}
+
+/// Periodic snapshot for the live diagnostics panel.
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetrySnapshot {
+    pub audio_state: AudioState,
+    /// Input level (0.0-1.0).
+    pub level: f32,
+    /// Current recording duration in seconds.
+    pub recording_secs: f32,
+    /// Seconds left before the ring buffer is full.
+    pub buffer_headroom_secs: f32,
+    pub model_loaded: bool,
+    /// Threads used for transcription.
+    pub active_threads: u32,
+    /// Whether `active_threads` is well below the physical core count.
+    pub threads_underused: bool,
+}
+
+/// Events emitted by the application controller for the frontend.
+///
+/// The payload is serialized as-is; the Tauri event name comes from [`AppEvent::name`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum AppEvent {
+    /// Transcribed text was copied to the clipboard without simulating paste.
+    TextReady {
+        text: String,
+    },
+    /// A segment of a running decode (`transcription.live_segments`).
+    TranscriptSegment(TranscriptSegment),
+    /// A dictation finished (counters only, no text).
+    DictationComplete(DictationSummary),
+    /// The toggle flow entered a new phase.
+    TogglePhase {
+        phase: TogglePhase,
+    },
+    /// Diagnostics telemetry tick.
+    Telemetry(TelemetrySnapshot),
+    /// Progress of a model download.
+    ModelDownload(DownloadProgress),
+    /// Progress of a background model load.
+    ModelLoad {
+        model: String,
+        phase: ModelLoadPhase,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// The model used for a dictation was chosen from `language_model_map`.
+    ModelSelected {
+        model: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        language: Option<String>,
+    },
+    /// The selected model is too small for the newly configured language.
+    ModelSuggestion(LanguageModelSuggestion),
+    /// A dictation is held until the user approves it (`confirm_before_inject`).
+    ConfirmInject {
+        text: String,
+    },
+    /// The held dictation wasn't confirmed within `confirm_timeout_secs`
+    /// and was discarded.
+    ConfirmInjectExpired {
+        timeout_secs: u32,
+    },
+    /// Transcript so far of a dictation being transcribed with
+    /// `transcription.partial_results`.
+    TranscribeProgress {
+        text: String,
+    },
+    /// The recording reached `audio.max_recording_secs` and was stopped.
+    RecordingAutoStopped {
+        max_recording_secs: u32,
+    },
+    /// The recording was stopped after `audio.silence_timeout_ms` of
+    /// silence following speech.
+    RecordingSilenceStopped {
+        silence_ms: u32,
+    },
+    /// The recording outgrew the audio buffer and its tail was dropped.
+    RecordingTruncated {
+        dropped_samples: usize,
+    },
+    /// Dictation was muted or unmuted.
+    MuteChanged {
+        muted: bool,
+    },
+    /// An installed model failed to load and its checksum doesn't match;
+    /// it should be downloaded again.
+    ModelCorrupt {
+        model_id: String,
+        quantization: Quantization,
+    },
+    /// The language to transcribe isn't supported by the loaded
+    /// (English-only) model. `switched_to` names the multilingual model used
+    /// instead; when `None` the dictation failed.
+    LanguageUnsupported {
+        language: String,
+        model: String,
+        switched_to: Option<String>,
+    },
+    /// First-run model setup needs a download but local-only mode blocks it.
+    DefaultModelBlocked {
+        model_id: String,
+    },
+    /// No model is installed and none could be recommended at startup.
+    NoModelsAvailable {
+        /// Full catalog so the UI can offer a download.
+        catalog: ModelCatalog,
+        /// Why no recommendation was possible.
+        reason: String,
+    },
+}
+
+impl AppEvent {
+    /// Tauri event name for this event.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::TextReady { .. } => "text-ready",
+            AppEvent::TranscriptSegment(_) => "transcript-segment",
+            AppEvent::DictationComplete(_) => "dictation-complete",
+            AppEvent::TogglePhase { .. } => "toggle-phase",
+            AppEvent::Telemetry(_) => "telemetry",
+            AppEvent::ModelDownload(_) => "model-download",
+            AppEvent::ModelLoad { .. } => "model-load",
+            AppEvent::ModelSelected { .. } => "model-selected",
+            AppEvent::ModelSuggestion(_) => "model-suggestion",
+            AppEvent::ConfirmInject { .. } => "confirm-inject",
+            AppEvent::ConfirmInjectExpired { .. } => "confirm-inject-expired",
+            AppEvent::TranscribeProgress { .. } => "transcribe-progress",
+            AppEvent::RecordingAutoStopped { .. } => "recording-auto-stopped",
+            AppEvent::RecordingSilenceStopped { .. } => "recording-silence-stopped",
+            AppEvent::RecordingTruncated { .. } => "recording-truncated",
+            AppEvent::MuteChanged { .. } => "mute-changed",
+            AppEvent::ModelCorrupt { .. } => "model-corrupt",
+            AppEvent::LanguageUnsupported { .. } => "language-unsupported",
+            AppEvent::DefaultModelBlocked { .. } => "default-model-blocked",
+            AppEvent::NoModelsAvailable { .. } => "no-models-available",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_phase_payload() {
+        let event = AppEvent::TogglePhase {
+            phase: TogglePhase::Transcribing,
+        };
+        assert_eq!(event.name(), "toggle-phase");
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"phase":"transcribing"}"#
+        );
+    }
+
+    #[test]
+    fn test_model_load_payload() {
+        let event = AppEvent::ModelLoad {
+            model: "whisper-small".to_string(),
+            phase: ModelLoadPhase::Loaded,
+            error: None,
+        };
+        assert_eq!(event.name(), "model-load");
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"model":"whisper-small","phase":"loaded"}"#
+        );
+    }
+
+    #[test]
+    fn test_confirm_inject_payload() {
+        let event = AppEvent::ConfirmInject {
+            text: "Hello".to_string(),
+        };
+        assert_eq!(event.name(), "confirm-inject");
+        assert_eq!(serde_json::to_string(&event).unwrap(), r#"{"text":"Hello"}"#);
+
+        let event = AppEvent::ConfirmInjectExpired { timeout_secs: 60 };
+        assert_eq!(event.name(), "confirm-inject-expired");
+        assert_eq!(serde_json::to_string(&event).unwrap(), r#"{"timeout_secs":60}"#);
+    }
+
+    #[test]
+    fn test_model_corrupt_payload() {
+        let event = AppEvent::ModelCorrupt {
+            model_id: "whisper-base".to_string(),
+            quantization: Quantization::Q5_1,
+        };
+        assert_eq!(event.name(), "model-corrupt");
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"model_id":"whisper-base","quantization":"Q5_1"}"#
+        );
+    }
+
+    #[test]
+    fn test_language_unsupported_payload() {
+        let event = AppEvent::LanguageUnsupported {
+            language: "fr".to_string(),
+            model: "whisper-small".to_string(),
+            switched_to: Some("whisper-large-v3".to_string()),
+        };
+        assert_eq!(event.name(), "language-unsupported");
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"language":"fr","model":"whisper-small","switched_to":"whisper-large-v3"}"#
+        );
+    }
+}
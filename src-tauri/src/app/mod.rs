@@ -1,3 +1,5 @@
 pub mod controller;
+pub mod events;
 
-pub use controller::{AppController, ToggleResult};
+pub use controller::{AppController, BatchItemResult, DefaultModelSetup, ToggleResult};
+pub use events::{AppEvent, ModelLoadPhase, TelemetrySnapshot, TogglePhase};
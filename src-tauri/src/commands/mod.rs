@@ -1,13 +1,19 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
+use zeroize::Zeroizing;
 
-use crate::app::{AppController, ToggleResult};
+use crate::app::{AppController, BatchItemResult, DefaultModelSetup, ToggleResult};
 use crate::domain::{
-    AppConfig, AudioConfig, AudioDevice, AudioState, HardwareProfile, InstalledModel,
-    ModelCatalog, ModelRecommendation, Quantization,
+    normalize_language, AppConfig, AudioConfig, AudioDevice, AudioState, BenchmarkResult,
+    CaptureRateMeasurement, ChannelMode, DownloadQueueEntry, HardwareProfile, InstalledModel,
+    LanguageModelSuggestion, ModelCatalog, ModelRecommendation, ModelsDirEntry, OsType, OutputSink,
+    Quantization, SessionType, StoredRecording, StreamInfo, ThreadUsage, UsageStats, VadDefaults,
 };
-use crate::ports::{TranscribeConfig, TranscriptionResult};
+use crate::infrastructure::{
+    parse_shortcut, ShortcutAvailability, ShortcutRegistry, ShortcutStatus,
+};
+use crate::ports::{BackendStatus, TranscribeConfig, TranscriptionResult};
 
 /// Get the current application configuration.
 #[tauri::command]
@@ -26,12 +32,30 @@ pub fn update_config(
         .map_err(|e| e.to_string())
 }
 
+/// Get the recommended VAD thresholds and their valid ranges.
+#[tauri::command]
+pub fn get_vad_defaults() -> VadDefaults {
+    VadDefaults::default()
+}
+
+/// Normalize a language code or name to a Whisper language code ("auto" allowed).
+#[tauri::command]
+pub fn validate_language(input: String) -> Result<String, String> {
+    normalize_language(&input).ok_or_else(|| format!("Unknown language: {}", input))
+}
+
 /// Check if network requests are currently blocked.
 #[tauri::command]
 pub fn is_network_blocked(controller: State<'_, AppController>) -> bool {
     controller.is_network_blocked()
 }
 
+/// Force buffered log lines to be written to the log file.
+#[tauri::command]
+pub fn flush_logs(controller: State<'_, AppController>) -> Result<(), String> {
+    controller.flush_logs().map_err(|e| e.to_string())
+}
+
 /// Get application paths information.
 #[tauri::command]
 pub fn get_paths(controller: State<'_, AppController>) -> AppPaths {
@@ -50,6 +74,44 @@ pub struct AppPaths {
     pub config_path: String,
 }
 
+// ==================== Usage Stats Commands ====================
+
+/// Get aggregate usage statistics (counters only, no transcribed content).
+#[tauri::command]
+pub fn get_usage_stats(controller: State<'_, AppController>) -> UsageStats {
+    controller.usage_stats()
+}
+
+/// Reset usage statistics.
+#[tauri::command]
+pub fn reset_usage_stats(controller: State<'_, AppController>) -> Result<(), String> {
+    controller.reset_usage_stats().map_err(|e| e.to_string())
+}
+
+// ==================== Telemetry Commands ====================
+
+/// Interval between telemetry events.
+const TELEMETRY_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Start emitting "telemetry" events until `stop_telemetry` is called.
+#[tauri::command]
+pub fn start_telemetry(app: AppHandle, controller: State<'_, AppController>) {
+    let session = controller.start_telemetry();
+    tauri::async_runtime::spawn(async move {
+        let controller = app.state::<AppController>();
+        while controller.is_telemetry_active(session) {
+            controller.publish_telemetry();
+            tokio::time::sleep(TELEMETRY_INTERVAL).await;
+        }
+    });
+}
+
+/// Stop emitting telemetry events.
+#[tauri::command]
+pub fn stop_telemetry(controller: State<'_, AppController>) {
+    controller.stop_telemetry();
+}
+
 // ==================== Audio Commands ====================
 
 /// Start audio recording.
@@ -119,12 +181,40 @@ pub fn get_recording_duration(controller: State<'_, AppController>) -> f32 {
     controller.recording_duration()
 }
 
+/// Set the maximum recording duration (applied on the next recording).
+#[tauri::command]
+pub fn set_buffer_duration(controller: State<'_, AppController>, secs: u32) -> Result<(), String> {
+    controller
+        .set_buffer_duration(secs)
+        .map_err(|e| e.to_string())
+}
+
+/// Set the input gain in dB for the selected device.
+#[tauri::command]
+pub fn set_input_gain(controller: State<'_, AppController>, gain_db: f32) -> Result<(), String> {
+    controller
+        .set_input_gain(gain_db)
+        .map_err(|e| e.to_string())
+}
+
 /// Get current audio input level.
 #[tauri::command]
 pub fn get_audio_level(controller: State<'_, AppController>) -> f32 {
     controller.audio_level()
 }
 
+/// Get current audio input level in dBFS, for a level meter.
+#[tauri::command]
+pub fn get_audio_level_db(controller: State<'_, AppController>) -> f32 {
+    controller.audio_level_db()
+}
+
+/// Get the current peak input level (0.0-1.0); near 1.0 the mic is clipping.
+#[tauri::command]
+pub fn get_audio_peak(controller: State<'_, AppController>) -> f32 {
+    controller.audio_peak()
+}
+
 /// Attempt to recover from audio error state.
 #[tauri::command]
 pub async fn recover_audio(controller: State<'_, AppController>) -> Result<(), String> {
@@ -134,6 +224,47 @@ pub async fn recover_audio(controller: State<'_, AppController>) -> Result<(), S
         .map_err(|e| e.to_string())
 }
 
+/// Cancel a running audio recovery. Returns false if none is in progress.
+#[tauri::command]
+pub fn cancel_recovery(controller: State<'_, AppController>) -> bool {
+    controller.cancel_recovery()
+}
+
+/// Route the next dictation to a one-off output target (`None` clears it).
+#[tauri::command]
+pub fn set_next_output(
+    controller: State<'_, AppController>,
+    sink: Option<OutputSink>,
+) -> Result<(), String> {
+    controller
+        .set_next_output(sink)
+        .map_err(|e| e.to_string())
+}
+
+/// Mute or unmute dictation (the toggle shortcut won't start recording while muted).
+#[tauri::command]
+pub fn set_muted(controller: State<'_, AppController>, muted: bool) {
+    controller.set_muted(muted);
+}
+
+/// Check whether dictation is muted.
+#[tauri::command]
+pub fn is_muted(controller: State<'_, AppController>) -> bool {
+    controller.is_muted()
+}
+
+/// Measure the selected microphone's actual sample rate against its reported one.
+#[tauri::command]
+pub async fn measure_capture_rate(
+    controller: State<'_, AppController>,
+    duration_secs: u32,
+) -> Result<CaptureRateMeasurement, String> {
+    controller
+        .measure_capture_rate(duration_secs)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Toggle recording: start if idle, stop + transcribe + inject if recording.
 ///
 /// This is the main entry point for the global shortcut flow (Option+Space).
@@ -146,6 +277,18 @@ pub async fn toggle_recording(controller: State<'_, AppController>) -> Result<To
         .map_err(|e| e.to_string())
 }
 
+/// Inject (`approved`) or discard the dictation held by `confirm_before_inject`.
+#[tauri::command]
+pub async fn confirm_inject(
+    controller: State<'_, AppController>,
+    approved: bool,
+) -> Result<(), String> {
+    controller
+        .confirm_inject(approved)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // ==================== Transcription Commands ====================
 
 /// Transcribe recorded audio.
@@ -162,8 +305,14 @@ pub async fn transcribe(
         .map_err(|e| e.to_string())?;
 
     // Transcribe
+    let language = match language {
+        Some(input) => {
+            normalize_language(&input).ok_or_else(|| format!("Unknown language: {}", input))?
+        }
+        None => "auto".to_string(),
+    };
     let config = TranscribeConfig {
-        language,
+        language: (language != "auto").then_some(language),
         ..Default::default()
     };
 
@@ -173,6 +322,157 @@ pub async fn transcribe(
         .map_err(|e| e.to_string())
 }
 
+/// Re-transcribe the last retained recording with different settings.
+/// Requires `privacy.retain_last_recording`; load another model first to switch models.
+#[tauri::command]
+pub async fn retranscribe_last(
+    controller: State<'_, AppController>,
+    config: Option<TranscribeConfig>,
+) -> Result<TranscriptionResult, String> {
+    controller
+        .retranscribe_last(config)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Write the last retained recording to a WAV file for debugging.
+/// Fails unless `privacy.retain_last_recording` is on.
+#[tauri::command]
+pub fn export_last_recording(
+    controller: State<'_, AppController>,
+    path: String,
+) -> Result<(), String> {
+    controller
+        .export_last_recording(Path::new(&path))
+        .map_err(|e| e.to_string())
+}
+
+/// Transcribe mono 16-bit PCM captured by the frontend (e.g. a web worker).
+/// Resampled to 16kHz if needed; input is capped at `MAX_PCM_INPUT_SECS`.
+#[tauri::command]
+pub async fn transcribe_pcm(
+    controller: State<'_, AppController>,
+    samples: Vec<i16>,
+    sample_rate: u32,
+    config: Option<TranscribeConfig>,
+) -> Result<TranscriptionResult, String> {
+    controller
+        .transcribe_pcm(samples, sample_rate, config)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Abort running transcriptions; they fail with a "cancelled" error.
+/// Returns how many were running.
+#[tauri::command]
+pub fn cancel_transcription(controller: State<'_, AppController>) -> usize {
+    controller.cancel_transcription()
+}
+
+/// List recordings stored encrypted on disk (requires `privacy.store_encrypted_audio`).
+#[tauri::command]
+pub fn list_stored_recordings(
+    controller: State<'_, AppController>,
+) -> Result<Vec<StoredRecording>, String> {
+    controller
+        .list_stored_recordings()
+        .map_err(|e| e.to_string())
+}
+
+/// Delete an encrypted stored recording.
+#[tauri::command]
+pub fn delete_stored_recording(
+    controller: State<'_, AppController>,
+    id: String,
+) -> Result<(), String> {
+    controller
+        .delete_stored_recording(&id)
+        .map_err(|e| e.to_string())
+}
+
+/// Re-transcribe an encrypted stored recording with different settings.
+#[tauri::command]
+pub async fn retranscribe_stored(
+    controller: State<'_, AppController>,
+    id: String,
+    config: Option<TranscribeConfig>,
+) -> Result<TranscriptionResult, String> {
+    controller
+        .retranscribe_stored(&id, config)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Re-transcribe several stored recordings in order, with per-recording results.
+#[tauri::command]
+pub async fn retranscribe_stored_batch(
+    controller: State<'_, AppController>,
+    ids: Vec<String>,
+    config: Option<TranscribeConfig>,
+) -> Result<Vec<BatchItemResult>, String> {
+    controller
+        .retranscribe_stored_batch(ids, config)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Cancel the running batch, aborting its current transcription.
+/// Returns how many recordings were cancelled.
+#[tauri::command]
+pub fn cancel_batch(controller: State<'_, AppController>) -> usize {
+    controller.cancel_batch()
+}
+
+/// Check if a recording is retained for re-transcription.
+#[tauri::command]
+pub fn has_last_recording(controller: State<'_, AppController>) -> bool {
+    controller.has_last_recording()
+}
+
+/// Clear the retained recording.
+#[tauri::command]
+pub fn clear_last_recording(controller: State<'_, AppController>) {
+    controller.clear_last_recording();
+}
+
+/// Copy the most recent transcription to the clipboard without pasting.
+#[tauri::command]
+pub fn copy_last_transcription(controller: State<'_, AppController>) -> Result<(), String> {
+    controller
+        .copy_last_transcription()
+        .map_err(|e| e.to_string())
+}
+
+/// Get the text accumulated in append mode.
+#[tauri::command]
+pub fn get_pending_transcript(controller: State<'_, AppController>) -> String {
+    controller.pending_transcript()
+}
+
+/// Finalize append mode: return the accumulated text and clear it.
+#[tauri::command]
+pub fn finalize_transcript(controller: State<'_, AppController>) -> String {
+    controller.finalize_transcript()
+}
+
+/// Preview the output transforms on sample text without recording anything.
+#[tauri::command]
+pub fn preview_text_processing(controller: State<'_, AppController>, input: String) -> String {
+    controller.preview_text_processing(&input)
+}
+
+/// Get the transcription settings the next toggle would use for `language`
+/// (the configured language when omitted).
+#[tauri::command]
+pub fn get_effective_transcribe_config(
+    controller: State<'_, AppController>,
+    language: Option<String>,
+) -> Result<TranscribeConfig, String> {
+    controller
+        .effective_transcribe_config(language.as_deref())
+        .map_err(|e| e.to_string())
+}
+
 /// Load a transcription model.
 #[tauri::command]
 pub async fn load_model(
@@ -186,21 +486,42 @@ pub async fn load_model(
 }
 
 /// Load a model by ID (uses installed model path).
+///
+/// Fails if the model needs more memory than is available, unless `force` is set.
 #[tauri::command]
 pub async fn load_model_by_id(
     controller: State<'_, AppController>,
     model_id: String,
     quantization: String,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let quant = Quantization::from_suffix(&quantization)
         .ok_or_else(|| format!("Invalid quantization: {}", quantization))?;
 
-    let path = controller
-        .model_path(&model_id, quant)
-        .ok_or_else(|| format!("Model not installed: {}-{}", model_id, quantization))?;
+    controller
+        .load_model_by_id(&model_id, quant, force.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Check whether the transcription backend can run, with a reason if not.
+#[tauri::command]
+pub async fn check_backend_available(
+    controller: State<'_, AppController>,
+) -> Result<BackendStatus, String> {
+    Ok(controller.check_backend_available().await)
+}
 
+/// Check an OpenAI API key before saving it. Returns whether it was accepted;
+/// errors if the network is blocked (local-only mode) or unreachable.
+#[tauri::command]
+pub async fn validate_api_key(
+    controller: State<'_, AppController>,
+    key: String,
+) -> Result<bool, String> {
+    let key = Zeroizing::new(key);
     controller
-        .load_model(path)
+        .validate_api_key(&key)
         .await
         .map_err(|e| e.to_string())
 }
@@ -217,6 +538,40 @@ pub fn unload_model(controller: State<'_, AppController>) {
     controller.unload_model();
 }
 
+/// Check whether silent recordings skip decoding when VAD is disabled.
+#[tauri::command]
+pub fn get_skip_silent_buffers(controller: State<'_, AppController>) -> bool {
+    controller.skip_silent_buffers()
+}
+
+/// Enable or disable skipping silent recordings when VAD is disabled.
+#[tauri::command]
+pub fn set_skip_silent_buffers(
+    controller: State<'_, AppController>,
+    enabled: bool,
+) -> Result<(), String> {
+    controller
+        .set_skip_silent_buffers(enabled)
+        .map_err(|e| e.to_string())
+}
+
+/// Check whether decoded segments are streamed as `transcript-segment` events.
+#[tauri::command]
+pub fn get_live_segments(controller: State<'_, AppController>) -> bool {
+    controller.live_segments()
+}
+
+/// Enable or disable streaming decoded segments as `transcript-segment` events.
+#[tauri::command]
+pub fn set_live_segments(
+    controller: State<'_, AppController>,
+    enabled: bool,
+) -> Result<(), String> {
+    controller
+        .set_live_segments(enabled)
+        .map_err(|e| e.to_string())
+}
+
 // ==================== Model Management Commands ====================
 
 /// Get the model catalog.
@@ -225,6 +580,80 @@ pub fn get_model_catalog(controller: State<'_, AppController>) -> ModelCatalog {
     controller.model_catalog()
 }
 
+/// Why no model is installed or can be recommended, `None` if there is a
+/// usable one. Same check as the startup "no-models-available" event.
+#[tauri::command]
+pub fn get_models_unavailable_reason(controller: State<'_, AppController>) -> Option<String> {
+    controller.models_unavailable_reason()
+}
+
+/// Check whether the configured model is too small for `language`, so
+/// settings can warn when the user picks it.
+#[tauri::command]
+pub fn suggest_model_for_language(
+    controller: State<'_, AppController>,
+    language: String,
+) -> Result<Option<LanguageModelSuggestion>, String> {
+    controller
+        .suggest_model_for_language(&language)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the model catalog serialized as JSON.
+#[tauri::command]
+pub fn get_catalog_json(controller: State<'_, AppController>) -> Result<String, String> {
+    controller.catalog_json().map_err(|e| e.to_string())
+}
+
+/// Export the model catalog to a JSON file.
+#[tauri::command]
+pub fn export_catalog(controller: State<'_, AppController>, path: String) -> Result<(), String> {
+    controller
+        .export_catalog(Path::new(&path))
+        .map_err(|e| e.to_string())
+}
+
+/// Download (if needed), load and select the recommended model.
+#[tauri::command]
+pub async fn setup_recommended_model(
+    controller: State<'_, AppController>,
+) -> Result<ModelRecommendation, String> {
+    controller
+        .setup_recommended_model()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Mark onboarding as done and run the first-run model setup.
+#[tauri::command]
+pub async fn acknowledge_onboarding(
+    app: AppHandle,
+    controller: State<'_, AppController>,
+) -> Result<DefaultModelSetup, String> {
+    controller
+        .acknowledge_onboarding()
+        .map_err(|e| e.to_string())?;
+    setup_default_model(app, controller).await
+}
+
+/// Install a default model if none is installed (bundled, else downloaded).
+#[tauri::command]
+pub async fn setup_default_model(
+    app: AppHandle,
+    controller: State<'_, AppController>,
+) -> Result<DefaultModelSetup, String> {
+    controller
+        .ensure_default_model(bundled_models_dir(&app).as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Directory of models shipped as app resources, if the bundle has one.
+pub fn bundled_models_dir(app: &AppHandle) -> Option<PathBuf> {
+    let dir = app.path().resource_dir().ok()?.join("models");
+    dir.is_dir().then_some(dir)
+}
+
 /// List installed models.
 #[tauri::command]
 pub fn list_installed_models(
@@ -264,6 +693,43 @@ pub async fn download_model(
         .map_err(|e| e.to_string())
 }
 
+/// Get active and queued model downloads.
+#[tauri::command]
+pub fn get_download_queue(controller: State<'_, AppController>) -> Vec<DownloadQueueEntry> {
+    controller.download_queue()
+}
+
+/// Move a queued download to a new position (0 = front).
+#[tauri::command]
+pub fn reorder_download(
+    controller: State<'_, AppController>,
+    model_id: String,
+    quantization: String,
+    position: usize,
+) -> Result<(), String> {
+    let quant = Quantization::from_suffix(&quantization)
+        .ok_or_else(|| format!("Invalid quantization: {}", quantization))?;
+
+    controller
+        .reorder_download(&model_id, quant, position)
+        .map_err(|e| e.to_string())
+}
+
+/// Remove a download that hasn't started yet.
+#[tauri::command]
+pub fn remove_from_queue(
+    controller: State<'_, AppController>,
+    model_id: String,
+    quantization: String,
+) -> Result<(), String> {
+    let quant = Quantization::from_suffix(&quantization)
+        .ok_or_else(|| format!("Invalid quantization: {}", quantization))?;
+
+    controller
+        .remove_from_queue(&model_id, quant)
+        .map_err(|e| e.to_string())
+}
+
 /// Delete an installed model.
 #[tauri::command]
 pub fn delete_model(
@@ -285,6 +751,26 @@ pub fn get_models_dir(controller: State<'_, AppController>) -> String {
     controller.models_dir().to_string_lossy().to_string()
 }
 
+/// List every file in the models directory, including orphaned and
+/// partially downloaded ones.
+#[tauri::command]
+pub fn list_models_dir_contents(
+    controller: State<'_, AppController>,
+) -> Result<Vec<ModelsDirEntry>, String> {
+    controller.models_dir_contents().map_err(|e| e.to_string())
+}
+
+/// Delete a file from the models directory by name.
+#[tauri::command]
+pub fn delete_models_dir_file(
+    controller: State<'_, AppController>,
+    name: String,
+) -> Result<(), String> {
+    controller
+        .delete_models_dir_file(&name)
+        .map_err(|e| e.to_string())
+}
+
 // ==================== Hardware Commands ====================
 
 /// Get the hardware profile.
@@ -302,3 +788,142 @@ pub fn get_recommended_model(
 ) -> Result<ModelRecommendation, String> {
     controller.recommended_model().map_err(|e| e.to_string())
 }
+
+/// Use `profile` instead of the detected hardware; `persist` saves it to config.
+#[tauri::command]
+pub fn set_hardware_override(
+    controller: State<'_, AppController>,
+    profile: HardwareProfile,
+    persist: bool,
+) -> Result<(), String> {
+    controller
+        .set_hardware_override(profile, persist)
+        .map_err(|e| e.to_string())
+}
+
+/// Go back to the detected hardware profile.
+#[tauri::command]
+pub fn clear_hardware_override(controller: State<'_, AppController>) -> Result<(), String> {
+    controller
+        .clear_hardware_override()
+        .map_err(|e| e.to_string())
+}
+
+/// Measure how fast each installed model transcribes on this hardware.
+///
+/// Cached results are returned unless `force` is set or the hardware changed.
+#[tauri::command]
+pub async fn benchmark_models(
+    controller: State<'_, AppController>,
+    language: Option<String>,
+    force: bool,
+) -> Result<Vec<BenchmarkResult>, String> {
+    controller
+        .benchmark_models(language.as_deref(), force)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the audio pipeline settings in one place, for support requests.
+#[tauri::command]
+pub fn get_audio_pipeline_info(controller: State<'_, AppController>) -> AudioPipelineInfo {
+    let audio = controller.audio_config();
+    let transcription = controller.config().transcription;
+    AudioPipelineInfo {
+        gain_db: audio.gain_db_for(audio.device_id.as_deref()),
+        max_recording_secs: audio.recording_limit_secs(),
+        device_id: audio.device_id,
+        stream: controller.audio_stream_info(),
+        target_sample_rate: audio.sample_rate,
+        channel_mode: audio.channel_mode,
+        buffer_duration_secs: audio.buffer_duration_secs,
+        keep_device_warm: audio.keep_device_warm,
+        vad_enabled: transcription.vad_enabled,
+        skip_silent_buffers: transcription.skip_silent_buffers,
+    }
+}
+
+/// Audio pipeline summary, from the device to what the transcriber receives.
+#[derive(serde::Serialize)]
+pub struct AudioPipelineInfo {
+    /// Selected input device, `None` for the system default.
+    pub device_id: Option<String>,
+    /// Native format of the open stream; `None` when no stream is open.
+    pub stream: Option<StreamInfo>,
+    /// Rate samples are resampled to, in Hz.
+    pub target_sample_rate: u32,
+    pub channel_mode: ChannelMode,
+    /// Gain applied to the selected device, in dB.
+    pub gain_db: f32,
+    pub buffer_duration_secs: u32,
+    /// Effective auto-stop limit, in seconds.
+    pub max_recording_secs: u32,
+    pub keep_device_warm: bool,
+    pub vad_enabled: bool,
+    /// Whether silent recordings are skipped when VAD is off.
+    pub skip_silent_buffers: bool,
+}
+
+/// Get diagnostics information for bug reports.
+#[tauri::command]
+pub fn get_diagnostics(controller: State<'_, AppController>) -> Diagnostics {
+    Diagnostics {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: OsType::detect(),
+        session_type: controller.session_type(),
+        audio_state: controller.audio_state(),
+        audio_responsive: controller.is_audio_responsive(),
+        model_loaded: controller.is_model_loaded(),
+        network_blocked: controller.is_network_blocked(),
+        thread_usage: controller.thread_usage().ok(),
+    }
+}
+
+/// Diagnostics information.
+#[derive(serde::Serialize)]
+pub struct Diagnostics {
+    pub app_version: String,
+    pub os: OsType,
+    pub session_type: SessionType,
+    pub audio_state: AudioState,
+    pub audio_responsive: bool,
+    pub model_loaded: bool,
+    pub network_blocked: bool,
+    /// Transcription threads vs cores, `None` if hardware detection failed.
+    pub thread_usage: Option<ThreadUsage>,
+}
+
+// ==================== Shortcut Commands ====================
+
+/// Get the active global shortcut and whether it registered successfully.
+///
+/// Returns `None` if registration has not been attempted yet.
+#[tauri::command]
+pub fn get_active_shortcut(registry: State<'_, ShortcutRegistry>) -> Option<ShortcutStatus> {
+    registry.status()
+}
+
+/// Register `combo` (e.g. "Ctrl+Shift+R") as the toggle shortcut.
+///
+/// On conflict the previous shortcut stays active and an error is returned,
+/// so the settings UI can validate a combo before saving it.
+#[tauri::command]
+pub fn try_register_shortcut(
+    app: AppHandle,
+    registry: State<'_, ShortcutRegistry>,
+    combo: String,
+) -> Result<ShortcutStatus, String> {
+    let shortcut = parse_shortcut(&combo).map_err(|e| e.to_string())?;
+    registry.register(&app, shortcut).map_err(|e| e.to_string())
+}
+
+/// Check whether `combo` parses and could be registered, without changing
+/// the active shortcut. For recording a new shortcut in settings.
+#[tauri::command]
+pub fn is_shortcut_available(
+    app: AppHandle,
+    registry: State<'_, ShortcutRegistry>,
+    combo: String,
+) -> ShortcutAvailability {
+    registry.probe(&app, &combo)
+}
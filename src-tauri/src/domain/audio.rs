@@ -1,19 +1,67 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU8, Ordering};
 
+use super::error::DomainError;
+
+/// Maximum input gain magnitude in dB (applies to boost and cut).
+pub const MAX_INPUT_GAIN_DB: f32 = 30.0;
+
+/// Minimum ring buffer duration in seconds.
+pub const MIN_BUFFER_DURATION_SECS: u32 = 5;
+
+/// Maximum ring buffer duration in seconds.
+pub const MAX_BUFFER_DURATION_SECS: u32 = 600;
+
+/// Lowest allowed `AudioConfig::agc_target_dbfs`.
+pub const MIN_AGC_TARGET_DBFS: f32 = -40.0;
+
+/// Most AGC may boost a quiet passage, in dB.
+pub const MAX_AGC_GAIN_DB: f32 = 30.0;
+
+/// Longest allowed `AudioConfig::preroll_ms`.
+pub const MAX_PREROLL_MS: u32 = 2_000;
+
+/// Lowest level reported in dBFS; silence reads as this floor.
+pub const LEVEL_FLOOR_DBFS: f32 = -60.0;
+
+/// Largest allowed `AudioConfig::noise_gate_hysteresis_db`.
+pub const MAX_NOISE_GATE_HYSTERESIS_DB: f32 = 20.0;
+
+/// How far the noise gate turns down audio while closed, in dB.
+const NOISE_GATE_ATTENUATION_DB: f32 = 40.0;
+
+/// Window over which the noise gate measures the short-term level.
+const NOISE_GATE_WINDOW_MS: u32 = 10;
+
+/// Interpolation kernel length used by `resample` for the sinc path.
+pub const DEFAULT_SINC_TAPS: usize = 32;
+
+/// Rate ratios (larger over smaller) below this resample linearly: clock
+/// drift corrections and the like, where aliasing isn't a concern.
+const SINC_MIN_RATIO: f64 = 1.1;
+
+/// Low-pass cutoff before downsampling, as a fraction of the target Nyquist,
+/// leaving room for the filter's transition band.
+const ANTI_ALIAS_ROLLOFF: f64 = 0.9;
+
 /// Audio capture state machine.
 ///
 /// State transitions:
 /// - Idle -> Recording (start_recording)
 /// - Recording -> Idle (stop_recording, returns AudioBuffer)
 /// - Recording -> DeviceLost (device disconnected, automatic)
-/// - DeviceLost -> Recovering -> Idle (recover, user-initiated)
+/// - DeviceLost -> Recovering -> Idle (recover, user-initiated, or automatic
+///   with `auto_recover`)
 /// - Recovering -> Error (after max_recovery_attempts failures)
 /// - Error -> Recovering -> Idle (recover, user-initiated)
 ///
 /// Note: Recovery always transitions to Idle, not back to Recording.
 /// This is intentional - the user must explicitly restart recording
 /// after a device loss to avoid unexpected audio capture.
+///
+/// The table is encoded in [`AudioState::can_transition_to`]; every state
+/// change goes through [`AtomicAudioState::transition`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum AudioState {
@@ -30,10 +78,26 @@ pub enum AudioState {
 }
 
 impl AudioState {
+    /// Check whether moving from this state to `next` is a legal transition.
+    #[must_use]
+    pub fn can_transition_to(&self, next: AudioState) -> bool {
+        use AudioState::*;
+        matches!(
+            (self, next),
+            (Idle, Recording)
+                | (Recording, Idle)
+                | (Recording, DeviceLost)
+                | (DeviceLost, Recovering)
+                | (Recovering, Idle)
+                | (Recovering, Error)
+                | (Error, Recovering)
+        )
+    }
+
     /// Check if recording can be started from this state.
     #[must_use]
     pub fn can_start_recording(&self) -> bool {
-        matches!(self, AudioState::Idle)
+        self.can_transition_to(AudioState::Recording)
     }
 
     /// Check if recording can be stopped from this state.
@@ -46,7 +110,7 @@ impl AudioState {
     /// Recovery is allowed from DeviceLost and Error states.
     #[must_use]
     pub fn can_recover(&self) -> bool {
-        matches!(self, AudioState::DeviceLost | AudioState::Error)
+        self.can_transition_to(AudioState::Recovering)
     }
 }
 
@@ -82,10 +146,28 @@ impl AtomicAudioState {
         self.0.load(Ordering::Acquire).into()
     }
 
+    /// Set the state without validating the transition (tests only).
+    #[cfg(test)]
     pub fn store(&self, state: AudioState) {
         self.0.store(state.into(), Ordering::Release);
     }
 
+    /// Move to `to` if that is a legal transition from the current state.
+    ///
+    /// Returns the previous state, or `AudioStateTransition` if illegal.
+    pub fn transition(&self, to: AudioState) -> Result<AudioState, DomainError> {
+        let mut current = self.load();
+        loop {
+            if !current.can_transition_to(to) {
+                return Err(DomainError::AudioStateTransition { from: current, to });
+            }
+            if self.compare_exchange(current, to) {
+                return Ok(current);
+            }
+            current = self.load();
+        }
+    }
+
     /// Compare and swap, returns true if successful.
     pub fn compare_exchange(&self, current: AudioState, new: AudioState) -> bool {
         self.0
@@ -100,23 +182,161 @@ impl Default for AtomicAudioState {
     }
 }
 
+/// How multi-channel input is reduced to mono.
+///
+/// `average` and `index` are accepted as spellings of `mix` and `channel`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelMode {
+    /// Average all channels.
+    #[default]
+    #[serde(alias = "average")]
+    Mix,
+    /// Use only the first channel.
+    Left,
+    /// Use only the second channel.
+    Right,
+    /// Use only the channel at this 0-based index.
+    #[serde(alias = "index")]
+    Channel(u16),
+}
+
+impl ChannelMode {
+    /// Index of the single channel to keep out of `channels`, or `None` to
+    /// average them. Falls back to the first channel when the selected one
+    /// doesn't exist.
+    pub fn channel_index(&self, channels: usize) -> Option<usize> {
+        let index = match self {
+            ChannelMode::Mix => return None,
+            ChannelMode::Left => 0,
+            ChannelMode::Right => 1,
+            ChannelMode::Channel(index) => *index as usize,
+        };
+        Some(if index < channels { index } else { 0 })
+    }
+
+    /// Whether the selected channel exists in input with `channels`.
+    pub fn fits(&self, channels: usize) -> bool {
+        match self {
+            ChannelMode::Mix | ChannelMode::Left => true,
+            ChannelMode::Right => channels >= 2,
+            ChannelMode::Channel(index) => (*index as usize) < channels,
+        }
+    }
+}
+
 /// Audio capture configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AudioConfig {
     /// Maximum recording duration in seconds (ring buffer size).
     pub buffer_duration_secs: u32,
+    /// Recordings stop on their own after this many seconds and, in toggle
+    /// mode, are transcribed. Capped at `buffer_duration_secs` so the stop
+    /// comes before the buffer overflows; unset stops at the buffer duration.
+    pub max_recording_secs: Option<u32>,
     /// Target sample rate in Hz.
     pub sample_rate: u32,
+    /// Recover automatically when the device is lost (e.g. a USB mic is
+    /// unplugged), instead of waiting for the user to trigger it.
+    pub auto_recover: bool,
     /// Maximum recovery attempts before transitioning to Error state.
     pub max_recovery_attempts: u32,
+    /// Grace period in ms before the first recovery attempt (lets wireless
+    /// devices reappear on their own).
+    pub recovery_initial_delay_ms: u64,
+    /// Base delay in ms for exponential recovery backoff (base, 2x, 4x, ...).
+    pub recovery_backoff_base_ms: u64,
+    /// Input gain in dB, used when the active device has no preset.
+    pub input_gain_db: f32,
+    /// Per-device input gain presets in dB, keyed by device ID.
+    pub device_gains: HashMap<String, f32>,
+    /// Persisted input device ID, or None for the system default.
+    pub device_id: Option<String>,
+    /// Never fall back to the default device when `device_id` is missing;
+    /// fail instead so recording doesn't silently use another mic.
+    pub lock_device: bool,
+    /// List virtual loopback/aggregate devices in the device picker.
+    pub show_virtual_devices: bool,
+    /// Capacity of the audio event broadcast; slow subscribers that fall
+    /// further behind skip the oldest events. Applied at startup.
+    pub event_capacity: usize,
+    /// Capacity of the audio thread command queue. Applied at startup.
+    pub command_capacity: usize,
+    /// Channel selection for multi-channel devices, e.g. a mic on the left
+    /// channel only. Applied on the next `start_recording`.
+    pub channel_mode: ChannelMode,
+    /// Keep the input stream open between dictations so recording starts on
+    /// an already-running device, skipping its ramp-up. Keeps the OS mic
+    /// indicator on until `warm_idle_timeout_secs` without a recording.
+    pub keep_device_warm: bool,
+    /// Seconds a warm stream may stay idle before the device is released.
+    pub warm_idle_timeout_secs: u32,
+    /// End a recording on its own after this many ms of silence, once speech
+    /// has been heard; silence before the first words doesn't count. 0
+    /// disables it.
+    pub silence_timeout_ms: u32,
+    /// Level in dBFS below which audio counts as silence for
+    /// `silence_timeout_ms`.
+    pub silence_threshold_dbfs: f32,
+    /// Audio from just before a recording starts, in ms, prepended to it so
+    /// speech that begins ahead of the shortcut isn't clipped. Captured only
+    /// while a warm stream is open (`keep_device_warm`), and never older
+    /// than this window. 0 disables it.
+    pub preroll_ms: u32,
+    /// Level the recording with automatic gain control before transcription,
+    /// for speakers who move closer to and away from the mic. Applied after
+    /// capture, on top of the static input gain.
+    pub agc_enabled: bool,
+    /// AGC target level in dBFS.
+    pub agc_target_dbfs: f32,
+    /// How fast AGC turns the gain down when the level rises, in ms.
+    pub agc_attack_ms: u32,
+    /// How fast AGC turns the gain back up when the level falls, in ms.
+    pub agc_release_ms: u32,
+    /// Turn down near-silent stretches (fans, hum, room noise) before
+    /// transcription, so Whisper doesn't hallucinate words from them.
+    /// Applied after capture, ahead of AGC.
+    pub noise_gate_enabled: bool,
+    /// Level in dBFS at which the gate opens, on the same scale as the
+    /// input level meter.
+    pub noise_gate_dbfs: f32,
+    /// How far below `noise_gate_dbfs` the level must fall before the gate
+    /// closes again, in dB, so speech hovering near the threshold doesn't
+    /// chatter.
+    pub noise_gate_hysteresis_db: f32,
 }
 
 impl Default for AudioConfig {
     fn default() -> Self {
         Self {
             buffer_duration_secs: 60, // 60 second ring buffer
+            max_recording_secs: None,
             sample_rate: 16_000,      // 16kHz for Whisper
+            auto_recover: false,
             max_recovery_attempts: 3,
+            recovery_initial_delay_ms: 0,
+            recovery_backoff_base_ms: 500,
+            input_gain_db: 0.0,
+            device_gains: HashMap::new(),
+            device_id: None,
+            lock_device: false,
+            show_virtual_devices: false,
+            event_capacity: 64,
+            command_capacity: 16,
+            channel_mode: ChannelMode::Mix,
+            keep_device_warm: false,
+            warm_idle_timeout_secs: 120,
+            silence_timeout_ms: 0,
+            silence_threshold_dbfs: -45.0,
+            preroll_ms: 300,
+            agc_enabled: false,
+            agc_target_dbfs: -20.0,
+            agc_attack_ms: 10,
+            agc_release_ms: 400,
+            noise_gate_enabled: false,
+            noise_gate_dbfs: -50.0,
+            noise_gate_hysteresis_db: 6.0,
         }
     }
 }
@@ -126,6 +346,141 @@ impl AudioConfig {
     pub fn buffer_capacity(&self) -> usize {
         self.buffer_duration_secs as usize * self.sample_rate as usize
     }
+
+    /// Seconds after which a recording is stopped automatically.
+    pub fn recording_limit_secs(&self) -> u32 {
+        self.max_recording_secs
+            .map_or(self.buffer_duration_secs, |max| max.min(self.buffer_duration_secs))
+    }
+
+    /// `recording_limit_secs` in samples.
+    pub fn max_recording_samples(&self) -> usize {
+        self.recording_limit_secs() as usize * self.sample_rate as usize
+    }
+
+    /// Pre-roll length in samples; 0 unless the device is kept warm.
+    pub fn preroll_samples(&self) -> usize {
+        if !self.keep_device_warm {
+            return 0;
+        }
+        self.preroll_ms as usize * self.sample_rate as usize / 1000
+    }
+
+    /// Calculate the ring buffer size in bytes (16-bit samples).
+    pub fn buffer_bytes(&self) -> u64 {
+        self.buffer_capacity() as u64 * std::mem::size_of::<i16>() as u64
+    }
+
+    /// Level in dBFS below which the noise gate closes.
+    pub fn noise_gate_close_dbfs(&self) -> f32 {
+        self.noise_gate_dbfs - self.noise_gate_hysteresis_db
+    }
+
+    /// Backoff delay in ms before recovery attempt `attempt` (1-based).
+    pub fn recovery_backoff_ms(&self, attempt: u32) -> u64 {
+        let factor = 1u64.checked_shl(attempt.saturating_sub(1)).unwrap_or(u64::MAX);
+        self.recovery_backoff_base_ms.saturating_mul(factor)
+    }
+
+    /// Get the input gain in dB for a device, falling back to `input_gain_db`.
+    pub fn gain_db_for(&self, device_id: Option<&str>) -> f32 {
+        device_id
+            .and_then(|id| self.device_gains.get(id).copied())
+            .unwrap_or(self.input_gain_db)
+    }
+
+    /// Set the input gain in dB, as a preset for `device_id` when a device is selected.
+    pub fn set_gain_db(&mut self, device_id: Option<&str>, gain_db: f32) {
+        match device_id {
+            Some(id) => {
+                self.device_gains.insert(id.to_string(), gain_db);
+            }
+            None => self.input_gain_db = gain_db,
+        }
+    }
+
+    /// Keep the device of `current` if it is locked there and still locked
+    /// here: a locked device changes only when explicitly selected, not when
+    /// other settings are saved.
+    pub fn keep_locked_device(&mut self, current: &AudioConfig) {
+        if current.lock_device && self.lock_device {
+            self.device_id = current.device_id.clone();
+        }
+    }
+
+    /// Validate buffer duration and gain values.
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if !(MIN_BUFFER_DURATION_SECS..=MAX_BUFFER_DURATION_SECS)
+            .contains(&self.buffer_duration_secs)
+        {
+            return Err(DomainError::Config(format!(
+                "buffer_duration_secs must be between {} and {}, got {}",
+                MIN_BUFFER_DURATION_SECS, MAX_BUFFER_DURATION_SECS, self.buffer_duration_secs
+            )));
+        }
+
+        if self.max_recording_secs == Some(0) {
+            return Err(DomainError::Config(
+                "max_recording_secs must be at least 1".to_string(),
+            ));
+        }
+
+        if !(LEVEL_FLOOR_DBFS..=0.0).contains(&self.silence_threshold_dbfs) {
+            return Err(DomainError::Config(format!(
+                "silence_threshold_dbfs must be between {} and 0, got {}",
+                LEVEL_FLOOR_DBFS, self.silence_threshold_dbfs
+            )));
+        }
+
+        if self.preroll_ms > MAX_PREROLL_MS {
+            return Err(DomainError::Config(format!(
+                "preroll_ms must be at most {}, got {}",
+                MAX_PREROLL_MS, self.preroll_ms
+            )));
+        }
+
+        if self.event_capacity == 0 || self.command_capacity == 0 {
+            return Err(DomainError::Config(
+                "event_capacity and command_capacity must be at least 1".to_string(),
+            ));
+        }
+
+        if !(MIN_AGC_TARGET_DBFS..=0.0).contains(&self.agc_target_dbfs) {
+            return Err(DomainError::Config(format!(
+                "agc_target_dbfs must be between {} and 0, got {}",
+                MIN_AGC_TARGET_DBFS, self.agc_target_dbfs
+            )));
+        }
+        if self.agc_attack_ms == 0 || self.agc_release_ms == 0 {
+            return Err(DomainError::Config(
+                "agc_attack_ms and agc_release_ms must be at least 1".to_string(),
+            ));
+        }
+
+        if !(LEVEL_FLOOR_DBFS..=0.0).contains(&self.noise_gate_dbfs) {
+            return Err(DomainError::Config(format!(
+                "noise_gate_dbfs must be between {} and 0, got {}",
+                LEVEL_FLOOR_DBFS, self.noise_gate_dbfs
+            )));
+        }
+        if !(0.0..=MAX_NOISE_GATE_HYSTERESIS_DB).contains(&self.noise_gate_hysteresis_db) {
+            return Err(DomainError::Config(format!(
+                "noise_gate_hysteresis_db must be between 0 and {}, got {}",
+                MAX_NOISE_GATE_HYSTERESIS_DB, self.noise_gate_hysteresis_db
+            )));
+        }
+
+        let gains = std::iter::once(&self.input_gain_db).chain(self.device_gains.values());
+        for &gain in gains {
+            if !(-MAX_INPUT_GAIN_DB..=MAX_INPUT_GAIN_DB).contains(&gain) {
+                return Err(DomainError::Config(format!(
+                    "Input gain must be between -{0} and {0} dB, got {1}",
+                    MAX_INPUT_GAIN_DB, gain
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Events emitted by the audio capture system.
@@ -158,9 +513,40 @@ pub enum AudioEvent {
     LevelUpdate {
         /// RMS level normalized to 0.0-1.0.
         level: f32,
+        /// Peak level over the same window, normalized to 0.0-1.0.
+        peak: f32,
+        /// RMS level in dBFS, `LEVEL_FLOOR_DBFS` to 0.
+        level_db: f32,
+    },
+    /// The recording reached `max_recording_secs` and capture stopped; the
+    /// recording is still open until stopped.
+    MaxDurationReached,
+    /// Speech was followed by `silence_timeout_ms` of silence and capture
+    /// stopped; the recording is still open until stopped.
+    SilenceTimeout {
+        silence_ms: u32,
+    },
+    /// The recording buffer is full and new samples were dropped. Sent at
+    /// most once per second while it lasts.
+    BufferFull {
+        /// Samples dropped since the previous `BufferFull` event.
+        dropped_samples: usize,
     },
 }
 
+/// Name fragments of well-known virtual loopback/aggregate input devices.
+const VIRTUAL_DEVICE_PATTERNS: &[&str] = &[
+    "blackhole",
+    "soundflower",
+    "loopback",
+    "monitor of",
+    "stereo mix",
+    "what u hear",
+    "vb-audio",
+    "voicemeeter",
+    "aggregate device",
+];
+
 /// Input audio device information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioDevice {
@@ -170,12 +556,537 @@ pub struct AudioDevice {
     pub name: String,
     /// Whether this is the system default device.
     pub is_default: bool,
+    /// Whether this looks like a virtual loopback device that captures
+    /// system audio rather than a microphone.
+    #[serde(default)]
+    pub is_virtual: bool,
+    /// Common sample rates the device can capture at natively, in Hz. Empty
+    /// if the device couldn't be queried.
+    #[serde(default)]
+    pub supported_sample_rates: Vec<u32>,
+    /// Most input channels the device offers; 0 if unknown.
+    #[serde(default)]
+    pub max_channels: u16,
+}
+
+impl AudioDevice {
+    /// Heuristic check for virtual loopback/aggregate devices by name.
+    pub fn is_virtual_name(name: &str) -> bool {
+        let name = name.to_lowercase();
+        VIRTUAL_DEVICE_PATTERNS.iter().any(|p| name.contains(p)) || name.ends_with(".monitor")
+    }
+}
+
+/// Native format of the open input stream, before downmixing and resampling.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StreamInfo {
+    pub device_name: String,
+    /// Rate the device delivers, in Hz.
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Native sample format, e.g. "f32".
+    pub sample_format: String,
+}
+
+/// Result of timing a capture stream against the wall clock.
+///
+/// Interfaces whose real rate differs from the reported one corrupt resampling,
+/// so this compares the frames delivered with what the reported rate implies.
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureRateMeasurement {
+    pub device_name: String,
+    /// Sample rate reported by the device, in Hz.
+    pub reported_rate: u32,
+    /// Effective rate derived from the frames actually delivered, in Hz.
+    pub measured_rate: f64,
+    /// Frames expected at the reported rate over the measured window.
+    pub expected_samples: u64,
+    /// Frames actually delivered over the measured window.
+    pub actual_samples: u64,
+    /// Length of the measured window in seconds.
+    pub duration_secs: f64,
+    /// Deviation of the measured rate from the reported rate, in parts per million.
+    pub drift_ppm: f64,
+}
+
+impl CaptureRateMeasurement {
+    /// Build a measurement from `actual_samples` frames captured over `duration_secs`.
+    pub fn new(
+        device_name: String,
+        reported_rate: u32,
+        actual_samples: u64,
+        duration_secs: f64,
+    ) -> Self {
+        let measured_rate = if duration_secs > 0.0 {
+            actual_samples as f64 / duration_secs
+        } else {
+            0.0
+        };
+        let drift_ppm = if reported_rate > 0 {
+            (measured_rate / reported_rate as f64 - 1.0) * 1_000_000.0
+        } else {
+            0.0
+        };
+
+        Self {
+            device_name,
+            reported_rate,
+            measured_rate,
+            expected_samples: (reported_rate as f64 * duration_secs).round() as u64,
+            actual_samples,
+            duration_secs,
+            drift_ppm,
+        }
+    }
+}
+
+/// Root-mean-square level of PCM samples, normalized to 0.0-1.0.
+pub fn calculate_rms(samples: &[i16]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum_squares: f64 = samples.iter().map(|&s| (s as f64).powi(2)).sum();
+    let rms = (sum_squares / samples.len() as f64).sqrt();
+    (rms / 32767.0).min(1.0) as f32
+}
+
+/// RMS level of PCM samples in dBFS, clamped to `LEVEL_FLOOR_DBFS..=0.0`.
+pub fn calculate_rms_dbfs(samples: &[i16]) -> f32 {
+    rms_to_dbfs(calculate_rms(samples))
+}
+
+/// Convert a normalized RMS level (as from `calculate_rms`) to dBFS.
+///
+/// Follows AES17: a full-scale sine reads 0 dBFS, so the RMS is scaled by
+/// √2 before taking the log.
+pub fn rms_to_dbfs(level: f32) -> f32 {
+    if level <= 0.0 {
+        return LEVEL_FLOOR_DBFS;
+    }
+    (20.0 * (level * std::f32::consts::SQRT_2).log10()).clamp(LEVEL_FLOOR_DBFS, 0.0)
+}
+
+/// Like `calculate_rms_dbfs`, without the floor: thresholds near
+/// `LEVEL_FLOOR_DBFS` still see quieter audio as below them. Silence is
+/// negative infinity.
+fn calculate_rms_dbfs_unclamped(samples: &[i16]) -> f32 {
+    20.0 * (calculate_rms(samples) * std::f32::consts::SQRT_2).log10()
+}
+
+/// Largest absolute sample, normalized to 0.0-1.0. Values near 1.0 mean
+/// the input is clipping.
+pub fn calculate_peak(samples: &[i16]) -> f32 {
+    let peak = samples.iter().map(|&s| s.unsigned_abs()).max().unwrap_or(0);
+    (peak as f32 / 32767.0).min(1.0)
+}
+
+/// Apply automatic gain control in place, steering the level toward
+/// `target_dbfs`.
+///
+/// An envelope follower tracks the signal level, rising with the `attack_ms`
+/// time constant and falling with `release_ms`; each sample is scaled by
+/// target / envelope. The boost is capped at `MAX_AGC_GAIN_DB` so pauses
+/// aren't raised to loud noise.
+pub fn apply_agc(
+    samples: &mut [i16],
+    sample_rate: u32,
+    target_dbfs: f32,
+    attack_ms: u32,
+    release_ms: u32,
+) {
+    let coefficient = |ms: u32| (-1000.0 / (ms.max(1) as f32 * sample_rate.max(1) as f32)).exp();
+    let attack = coefficient(attack_ms);
+    let release = coefficient(release_ms);
+    let target = 10f32.powf(target_dbfs / 20.0);
+    let max_gain = 10f32.powf(MAX_AGC_GAIN_DB / 20.0);
+
+    // Start at the target so the first samples pass roughly unchanged
+    let mut envelope = target;
+    for sample in samples.iter_mut() {
+        let level = (*sample as f32 / 32768.0).abs();
+        let coef = if level > envelope { attack } else { release };
+        envelope = coef * envelope + (1.0 - coef) * level;
+
+        let gain = (target / envelope.max(f32::EPSILON)).min(max_gain);
+        *sample = (*sample as f32 * gain).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+    }
+}
+
+/// Attenuate near-silent stretches in place with a noise gate.
+///
+/// The level is measured in dBFS over short windows. The gate opens when it
+/// reaches `open_dbfs` and closes once it drops below `close_dbfs`; while
+/// closed, audio is turned down by `NOISE_GATE_ATTENUATION_DB`. The gain
+/// ramps across each window so the gate doesn't click.
+pub fn apply_noise_gate(samples: &mut [i16], sample_rate: u32, open_dbfs: f32, close_dbfs: f32) {
+    let window = (sample_rate as usize * NOISE_GATE_WINDOW_MS as usize / 1000).max(1);
+    let closed_gain = 10f32.powf(-NOISE_GATE_ATTENUATION_DB / 20.0);
+
+    let mut open = false;
+    let mut gain = closed_gain;
+    for chunk in samples.chunks_mut(window) {
+        // The close threshold can sit below the meter's floor
+        let level = calculate_rms_dbfs_unclamped(chunk);
+        if level >= open_dbfs {
+            open = true;
+        } else if level < close_dbfs {
+            open = false;
+        }
+
+        let target = if open { 1.0 } else { closed_gain };
+        let step = (target - gain) / chunk.len() as f32;
+        for sample in chunk.iter_mut() {
+            gain += step;
+            *sample = (*sample as f32 * gain).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        }
+        gain = target;
+    }
+}
+
+/// Stateful low-pass filter for audio that arrives in chunks: a 4th-order
+/// Butterworth, as two cascaded biquad sections.
+///
+/// Filter state carries over between `process` calls, so a stream filtered
+/// chunk by chunk matches the same stream filtered in one go.
+#[derive(Debug, Clone)]
+pub struct LowPassFilter {
+    sections: [Biquad; 2],
+}
+
+impl LowPassFilter {
+    /// Create a filter for audio at `sample_rate` passing frequencies below
+    /// `cutoff_hz`.
+    pub fn new(sample_rate: u32, cutoff_hz: f32) -> Self {
+        // Quality factors of the two sections of a 4th-order Butterworth
+        let (q1, q2) = (0.541_196_1, 1.306_563);
+        Self {
+            sections: [
+                Biquad::low_pass(sample_rate, cutoff_hz, q1),
+                Biquad::low_pass(sample_rate, cutoff_hz, q2),
+            ],
+        }
+    }
+
+    /// Filter removing what would alias when downsampling from `from_rate`
+    /// to `to_rate`, with the cutoff at 0.45 × `to_rate`.
+    pub fn anti_aliasing(from_rate: u32, to_rate: u32) -> Self {
+        Self::new(from_rate, to_rate as f32 * 0.45)
+    }
+
+    /// Forget the audio filtered so far, as for a new stream.
+    pub fn reset(&mut self) {
+        for section in &mut self.sections {
+            section.z1 = 0.0;
+            section.z2 = 0.0;
+        }
+    }
+
+    /// Filter samples in place.
+    pub fn process(&mut self, samples: &mut [i16]) {
+        for sample in samples.iter_mut() {
+            let filtered = self
+                .sections
+                .iter_mut()
+                .fold(*sample as f64, |x, section| section.process(x));
+            *sample = filtered.round().clamp(i16::MIN as f64, i16::MAX as f64) as i16;
+        }
+    }
+}
+
+/// Second-order IIR section (transposed direct form II).
+#[derive(Debug, Clone)]
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    z1: f64,
+    z2: f64,
+}
+
+impl Biquad {
+    /// Low-pass coefficients from the RBJ audio EQ cookbook.
+    fn low_pass(sample_rate: u32, cutoff_hz: f32, q: f64) -> Self {
+        let nyquist = sample_rate.max(1) as f64 / 2.0;
+        let cutoff = (cutoff_hz as f64).clamp(1.0, nyquist * 0.99);
+        let w0 = std::f64::consts::PI * cutoff / nyquist;
+        let alpha = w0.sin() / (2.0 * q);
+        let cos = w0.cos();
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: (1.0 - cos) / 2.0 / a0,
+            b1: (1.0 - cos) / a0,
+            b2: (1.0 - cos) / 2.0 / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha) / a0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0 * x + self.z1;
+        self.z1 = self.b1 * x - self.a1 * y + self.z2;
+        self.z2 = self.b2 * x - self.a2 * y;
+        y
+    }
+}
+
+/// Stateful windowed-sinc resampler for audio that arrives in chunks.
+///
+/// The last input samples and the read position carry over between
+/// `process` calls, so a stream resampled chunk by chunk matches the same
+/// stream resampled in one go. The output lags the input by half a kernel.
+/// There is no anti-aliasing filter: when downsampling, band-limit the input
+/// first (see `LowPassFilter::anti_aliasing`).
+#[derive(Debug, Clone)]
+pub struct StreamResampler {
+    ratio: f64,
+    half: i64,
+    /// Input samples still needed, starting at stream index `offset`.
+    history: Vec<f64>,
+    offset: i64,
+    /// Output samples produced so far.
+    produced: u64,
+}
+
+impl StreamResampler {
+    /// Create a resampler from `from_rate` to `to_rate`.
+    pub fn new(from_rate: u32, to_rate: u32) -> Self {
+        Self {
+            ratio: from_rate as f64 / to_rate.max(1) as f64,
+            half: (DEFAULT_SINC_TAPS / 2) as i64,
+            history: Vec::new(),
+            offset: 0,
+            produced: 0,
+        }
+    }
+
+    /// Forget the audio resampled so far, as for a new stream. Input still
+    /// waiting for its kernel to be covered is dropped.
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.offset = 0;
+        self.produced = 0;
+    }
+
+    /// Resample the next chunk, returning every output sample whose kernel
+    /// is covered by the input so far.
+    pub fn process(&mut self, samples: &[i16]) -> Vec<i16> {
+        self.history.extend(samples.iter().map(|&s| s as f64));
+        let end = self.offset + self.history.len() as i64;
+
+        let mut output = Vec::with_capacity((samples.len() as f64 / self.ratio).ceil() as usize);
+        loop {
+            let pos = self.produced as f64 * self.ratio;
+            if pos.floor() as i64 + self.half >= end {
+                break;
+            }
+            // Before the first sample, repeat it
+            let (history, offset) = (&self.history, self.offset);
+            output.push(sinc_interpolate(pos, self.half, |k| {
+                history[(k - offset).max(0) as usize]
+            }));
+            self.produced += 1;
+        }
+
+        // Keep what the next output's kernel reaches back to
+        let next_base = (self.produced as f64 * self.ratio).floor() as i64;
+        let drop = (next_base - self.half + 1 - self.offset).clamp(0, self.history.len() as i64);
+        self.history.drain(..drop as usize);
+        self.offset += drop;
+        output
+    }
+}
+
+/// Resample mono PCM from `from_rate` to `to_rate`.
+///
+/// Large rate changes (48kHz to 16kHz capture) go through `resample_sinc`;
+/// tiny ones are interpolated linearly.
+pub fn resample(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let ratio = from_rate.max(to_rate) as f64 / from_rate.min(to_rate).max(1) as f64;
+    if ratio >= SINC_MIN_RATIO {
+        resample_sinc(samples, from_rate, to_rate, DEFAULT_SINC_TAPS)
+    } else {
+        resample_linear(samples, from_rate, to_rate)
+    }
+}
+
+/// Resample mono PCM with a windowed-sinc (Blackman) interpolation kernel of
+/// `taps` samples.
+///
+/// When downsampling, content above the target Nyquist is removed first by
+/// a low-pass FIR, lengthened with the ratio to keep its transition band
+/// narrow. Samples past either end are treated as repeats of the edge sample,
+/// so the output doesn't dip to zero at its boundaries. For audio arriving
+/// in chunks, use `StreamResampler`.
+pub fn resample_sinc(samples: &[i16], from_rate: u32, to_rate: u32, taps: usize) -> Vec<i16> {
+    if from_rate == to_rate || samples.is_empty() || to_rate == 0 {
+        return samples.to_vec();
+    }
+
+    let ratio = from_rate as f64 / to_rate as f64;
+    let input: Vec<f64> = if ratio > 1.0 {
+        let cutoff = 0.5 / ratio * ANTI_ALIAS_ROLLOFF;
+        low_pass(samples, cutoff, (taps as f64 * ratio).ceil() as usize)
+    } else {
+        samples.iter().map(|&s| s as f64).collect()
+    };
+    let at = |i: i64| input[i.clamp(0, input.len() as i64 - 1) as usize];
+
+    let half = (taps / 2).max(1) as i64;
+    let output_len = (samples.len() as f64 / ratio).ceil() as usize;
+    (0..output_len)
+        .map(|i| sinc_interpolate(i as f64 * ratio, half, at))
+        .collect()
+}
+
+/// Interpolate the input at fractional position `pos` with a Blackman-windowed
+/// sinc kernel of `2 * half` taps, reading input samples through `at`.
+fn sinc_interpolate(pos: f64, half: i64, at: impl Fn(i64) -> f64) -> i16 {
+    let base = pos.floor() as i64;
+    let (mut sum, mut weights) = (0.0, 0.0);
+    for k in base - half + 1..=base + half {
+        let t = pos - k as f64;
+        let weight = sinc(t) * blackman(t / half as f64);
+        sum += at(k) * weight;
+        weights += weight;
+    }
+    (sum / weights).round().clamp(i16::MIN as f64, i16::MAX as f64) as i16
+}
+
+/// Windowed-sinc low-pass FIR with `cutoff` in cycles per sample (< 0.5).
+fn low_pass(samples: &[i16], cutoff: f64, taps: usize) -> Vec<f64> {
+    let half = (taps / 2).max(1) as i64;
+    let mut kernel: Vec<f64> = (-half..=half)
+        .map(|j| {
+            let window = blackman(j as f64 / (half + 1) as f64);
+            2.0 * cutoff * sinc(2.0 * cutoff * j as f64) * window
+        })
+        .collect();
+    let gain: f64 = kernel.iter().sum();
+    kernel.iter_mut().for_each(|h| *h /= gain);
+
+    let last = samples.len() as i64 - 1;
+    (0..=last)
+        .map(|i| {
+            kernel
+                .iter()
+                .zip(-half..=half)
+                .map(|(h, j)| h * samples[(i - j).clamp(0, last) as usize] as f64)
+                .sum()
+        })
+        .collect()
+}
+
+/// Normalized sinc, sin(πx) / πx.
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        let px = std::f64::consts::PI * x;
+        px.sin() / px
+    }
+}
+
+/// Blackman window over x in [-1, 1], zero outside.
+fn blackman(x: f64) -> f64 {
+    if x.abs() >= 1.0 {
+        return 0.0;
+    }
+    let px = std::f64::consts::PI * x;
+    0.42 + 0.5 * px.cos() + 0.08 * (2.0 * px).cos()
+}
+
+/// Linearly interpolate mono PCM from `from_rate` to `to_rate`.
+fn resample_linear(samples: &[i16], from_rate: u32, to_rate: u32) -> Vec<i16> {
+    let ratio = from_rate as f64 / to_rate as f64;
+    let output_len = (samples.len() as f64 / ratio).ceil() as usize;
+    let mut output = Vec::with_capacity(output_len);
+
+    for i in 0..output_len {
+        let src_pos = i as f64 * ratio;
+        let src_idx = src_pos.floor() as usize;
+        let frac = src_pos.fract();
+
+        let sample = if src_idx + 1 < samples.len() {
+            let s0 = samples[src_idx] as f64;
+            let s1 = samples[src_idx + 1] as f64;
+            (s0 + (s1 - s0) * frac) as i16
+        } else if src_idx < samples.len() {
+            samples[src_idx]
+        } else {
+            0
+        };
+        output.push(sample);
+    }
+    output
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_capture_rate_measurement() {
+        // 48 kHz interface that actually delivers 48.048 kHz
+        let m = CaptureRateMeasurement::new("Mic".to_string(), 48_000, 480_480, 10.0);
+        assert_eq!(m.expected_samples, 480_000);
+        assert!((m.measured_rate - 48_048.0).abs() < 1e-6);
+        assert!((m.drift_ppm - 1000.0).abs() < 1e-6);
+
+        let m = CaptureRateMeasurement::new("Mic".to_string(), 48_000, 0, 0.0);
+        assert_eq!(m.measured_rate, 0.0);
+    }
+
+    #[test]
+    fn test_audio_state_transition_matrix() {
+        use AudioState::*;
+        let states = [Idle, Recording, DeviceLost, Recovering, Error];
+        let legal = [
+            (Idle, Recording),
+            (Recording, Idle),
+            (Recording, DeviceLost),
+            (DeviceLost, Recovering),
+            (Recovering, Idle),
+            (Recovering, Error),
+            (Error, Recovering),
+        ];
+
+        for from in states {
+            for to in states {
+                assert_eq!(
+                    from.can_transition_to(to),
+                    legal.contains(&(from, to)),
+                    "{:?} -> {:?}",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_atomic_state_rejects_illegal_transition() {
+        let state = AtomicAudioState::default();
+        assert!(matches!(
+            state.transition(AudioState::DeviceLost),
+            Err(DomainError::AudioStateTransition {
+                from: AudioState::Idle,
+                to: AudioState::DeviceLost,
+            })
+        ));
+        assert_eq!(state.load(), AudioState::Idle);
+
+        assert_eq!(state.transition(AudioState::Recording).unwrap(), AudioState::Idle);
+        assert_eq!(state.load(), AudioState::Recording);
+    }
+
     #[test]
     fn test_audio_state_can_start_recording() {
         assert!(AudioState::Idle.can_start_recording());
@@ -241,6 +1152,85 @@ mod tests {
         assert_eq!(config.buffer_duration_secs, 60);
         assert_eq!(config.sample_rate, 16_000);
         assert_eq!(config.max_recovery_attempts, 3);
+        assert!(!config.keep_device_warm);
+        assert_eq!(config.preroll_ms, 300);
+
+        // Pre-roll needs a warm stream
+        assert_eq!(config.preroll_samples(), 0);
+        let warm = AudioConfig {
+            keep_device_warm: true,
+            ..Default::default()
+        };
+        assert_eq!(warm.preroll_samples(), 4_800);
+    }
+
+    #[test]
+    fn test_audio_config_device_gains() {
+        let mut config = AudioConfig {
+            input_gain_db: 3.0,
+            ..Default::default()
+        };
+        config.set_gain_db(Some("USB Headset"), -6.0);
+
+        assert_eq!(config.gain_db_for(Some("USB Headset")), -6.0);
+        // Devices without a preset use the global gain
+        assert_eq!(config.gain_db_for(Some("Built-in Microphone")), 3.0);
+        assert_eq!(config.gain_db_for(None), 3.0);
+
+        config.set_gain_db(None, 1.5);
+        assert_eq!(config.input_gain_db, 1.5);
+        assert!(config.validate().is_ok());
+
+        config.set_gain_db(Some("USB Headset"), 45.0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_audio_device_is_virtual_name() {
+        assert!(AudioDevice::is_virtual_name("BlackHole 2ch"));
+        assert!(AudioDevice::is_virtual_name("Soundflower (64ch)"));
+        assert!(AudioDevice::is_virtual_name("Monitor of Built-in Audio Analog Stereo"));
+        assert!(AudioDevice::is_virtual_name("alsa_output.pci-0000_00_1f.3.analog-stereo.monitor"));
+        assert!(!AudioDevice::is_virtual_name("MacBook Pro Microphone"));
+        assert!(!AudioDevice::is_virtual_name("USB Audio Device"));
+    }
+
+    #[test]
+    fn test_audio_config_recovery_backoff() {
+        let config = AudioConfig::default();
+        // Default keeps the original 500ms, 1s, 2s schedule
+        assert_eq!(config.recovery_initial_delay_ms, 0);
+        assert_eq!(config.recovery_backoff_ms(1), 500);
+        assert_eq!(config.recovery_backoff_ms(2), 1000);
+        assert_eq!(config.recovery_backoff_ms(3), 2000);
+
+        let config = AudioConfig {
+            recovery_backoff_base_ms: 250,
+            ..Default::default()
+        };
+        assert_eq!(config.recovery_backoff_ms(3), 1000);
+        assert_eq!(config.recovery_backoff_ms(100), u64::MAX);
+    }
+
+    #[test]
+    fn test_keep_locked_device() {
+        let locked = AudioConfig {
+            device_id: Some("usb-mic".to_string()),
+            lock_device: true,
+            ..Default::default()
+        };
+
+        let mut saved = AudioConfig {
+            lock_device: true,
+            ..Default::default()
+        };
+        saved.keep_locked_device(&locked);
+        assert_eq!(saved.device_id.as_deref(), Some("usb-mic"));
+
+        // Unlocking in the same save lets the device change
+        let mut unlocked = AudioConfig::default();
+        unlocked.keep_locked_device(&locked);
+        assert_eq!(unlocked.device_id, None);
     }
 
     #[test]
@@ -248,5 +1238,258 @@ mod tests {
         let config = AudioConfig::default();
         // 60 seconds * 16000 samples/sec = 960000 samples
         assert_eq!(config.buffer_capacity(), 960_000);
+        assert_eq!(config.max_recording_samples(), config.buffer_capacity());
+
+        // The limit never exceeds the buffer
+        let config = AudioConfig {
+            max_recording_secs: Some(600),
+            ..Default::default()
+        };
+        assert_eq!(config.max_recording_samples(), 960_000);
+        let config = AudioConfig {
+            max_recording_secs: Some(30),
+            ..Default::default()
+        };
+        assert_eq!(config.max_recording_samples(), 480_000);
+
+        // Configs saved before the limit existed keep their full buffer
+        let config: AudioConfig = toml::from_str("buffer_duration_secs = 300").unwrap();
+        assert_eq!(config.recording_limit_secs(), 300);
+    }
+
+    #[test]
+    fn test_audio_config_buffer_duration_bounds() {
+        let at = |secs| AudioConfig {
+            buffer_duration_secs: secs,
+            ..Default::default()
+        };
+
+        let min = at(MIN_BUFFER_DURATION_SECS);
+        assert!(min.validate().is_ok());
+        assert_eq!(min.buffer_capacity(), 80_000);
+        assert_eq!(min.buffer_bytes(), 160_000);
+
+        let max = at(MAX_BUFFER_DURATION_SECS);
+        assert!(max.validate().is_ok());
+        assert_eq!(max.buffer_capacity(), 9_600_000);
+        assert_eq!(max.buffer_bytes(), 19_200_000);
+
+        assert!(at(0).validate().is_err());
+        assert!(at(MIN_BUFFER_DURATION_SECS - 1).validate().is_err());
+        assert!(at(MAX_BUFFER_DURATION_SECS + 1).validate().is_err());
+    }
+
+    #[test]
+    fn test_audio_config_channel_capacities() {
+        assert!(AudioConfig {
+            event_capacity: 0,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+        assert!(AudioConfig {
+            command_capacity: 0,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_agc_levels_quiet_then_loud() {
+        let rate = 16_000;
+        let tone = |amplitude: f32, secs: usize| {
+            (0..rate * secs).map(move |i| {
+                let t = i as f32 / rate as f32;
+                ((t * 200.0 * std::f32::consts::TAU).sin() * amplitude * 32767.0) as i16
+            })
+        };
+        // -40 dBFS speech, then someone leans into the mic at -6 dBFS
+        let mut samples: Vec<i16> = tone(0.01, 2).chain(tone(0.5, 2)).collect();
+        let db = |samples: &[i16]| 20.0 * calculate_rms(samples).log10();
+        let quiet = 16_000..32_000;
+        let loud = 48_000..64_000;
+        assert!(db(&samples[loud.clone()]) - db(&samples[quiet.clone()]) > 30.0);
+
+        apply_agc(&mut samples, rate as u32, -20.0, 10, 400);
+
+        // Both settle within a few dB of each other and of the target
+        let (quiet_db, loud_db) = (db(&samples[quiet]), db(&samples[loud]));
+        assert!((loud_db - quiet_db).abs() < 3.0, "{} vs {}", quiet_db, loud_db);
+        assert!((-26.0..-14.0).contains(&loud_db), "{}", loud_db);
+
+        // Settings outside the allowed range are rejected
+        let config = |target, attack| AudioConfig {
+            agc_target_dbfs: target,
+            agc_attack_ms: attack,
+            ..Default::default()
+        };
+        assert!(config(-20.0, 10).validate().is_ok());
+        assert!(config(-60.0, 10).validate().is_err());
+        assert!(config(3.0, 10).validate().is_err());
+        assert!(config(-20.0, 0).validate().is_err());
+    }
+
+    #[test]
+    fn test_channel_mode_parsing() {
+        let parse = |s: &str| toml::from_str::<AudioConfig>(s).unwrap().channel_mode;
+        assert_eq!(parse(r#"channel_mode = "left""#), ChannelMode::Left);
+        assert_eq!(parse(r#"channel_mode = "average""#), ChannelMode::Mix);
+        assert_eq!(parse("channel_mode = { channel = 3 }"), ChannelMode::Channel(3));
+        assert_eq!(parse("channel_mode = { index = 3 }"), ChannelMode::Channel(3));
+        assert_eq!(parse(""), ChannelMode::Mix);
+
+        assert_eq!(ChannelMode::Channel(3).channel_index(2), Some(0));
+        assert_eq!(ChannelMode::Right.channel_index(4), Some(1));
+        assert_eq!(ChannelMode::Mix.channel_index(2), None);
+    }
+
+    #[test]
+    fn test_noise_gate_attenuates_quiet_sections() {
+        let rate = 16_000;
+        let tone = |amplitude: f32, ms: usize| {
+            (0..rate * ms / 1000).map(move |i| {
+                let t = i as f32 / rate as f32;
+                ((t * 200.0 * std::f32::consts::TAU).sin() * amplitude * 32767.0) as i16
+            })
+        };
+        // Speech at -12 dBFS alternating with room noise at -60 dBFS
+        let mut samples: Vec<i16> = Vec::new();
+        for _ in 0..3 {
+            samples.extend(tone(0.25, 500));
+            samples.extend(tone(0.001, 500));
+        }
+        let original = samples.clone();
+
+        apply_noise_gate(&mut samples, rate as u32, -50.0, -56.0);
+
+        for section in 0..6 {
+            // Skip the ramps at the section edges
+            let range = section * 8_000 + 400..(section + 1) * 8_000 - 400;
+            let before = calculate_rms(&original[range.clone()]);
+            let after = calculate_rms(&samples[range]);
+            if section % 2 == 0 {
+                assert!((after / before - 1.0).abs() < 0.01, "speech {}", section);
+            } else {
+                assert!(after < before * 0.1, "noise {}", section);
+            }
+        }
+
+        // Hysteresis keeps the gate open through a dip between the thresholds
+        let mut dip: Vec<i16> = tone(0.25, 100).chain(tone(0.002, 100)).collect();
+        let dip_original = dip.clone();
+        apply_noise_gate(&mut dip, rate as u32, -50.0, -56.0);
+        assert_eq!(&dip[1_600..], &dip_original[1_600..]);
+
+        // A close threshold below LEVEL_FLOOR_DBFS still closes the gate
+        let mut faint: Vec<i16> = tone(0.25, 100).chain(tone(0.0002, 500)).collect();
+        let faint_original = faint.clone();
+        apply_noise_gate(&mut faint, rate as u32, -58.0, -64.0);
+        let tail = 3_200..faint.len();
+        assert!(calculate_rms(&faint[tail.clone()]) < calculate_rms(&faint_original[tail]) * 0.1);
+
+        let config = |open, hysteresis| AudioConfig {
+            noise_gate_dbfs: open,
+            noise_gate_hysteresis_db: hysteresis,
+            ..Default::default()
+        };
+        assert!(config(-50.0, 6.0).validate().is_ok());
+        assert_eq!(config(-50.0, 6.0).noise_gate_close_dbfs(), -56.0);
+        assert!(config(-70.0, 6.0).validate().is_err());
+        assert!(config(-50.0, -1.0).validate().is_err());
+    }
+
+    #[test]
+    fn test_calculate_rms_dbfs() {
+        let sine = |amplitude: f64| -> Vec<i16> {
+            (0..16_000)
+                .map(|i| {
+                    let phase = 2.0 * std::f64::consts::PI * 440.0 * i as f64 / 16_000.0;
+                    (phase.sin() * amplitude * 32_767.0) as i16
+                })
+                .collect()
+        };
+        assert!((calculate_rms_dbfs(&sine(0.5)) + 6.02).abs() < 0.1);
+        assert!(calculate_rms_dbfs(&sine(1.0)).abs() < 0.1);
+        assert!((calculate_rms_dbfs(&sine(0.01)) + 40.0).abs() < 0.1);
+
+        // Silence and near-silence sit at the floor
+        assert_eq!(calculate_rms_dbfs(&[0; 160]), LEVEL_FLOOR_DBFS);
+        assert_eq!(calculate_rms_dbfs(&[1; 160]), LEVEL_FLOOR_DBFS);
+        assert_eq!(calculate_rms_dbfs(&[]), LEVEL_FLOOR_DBFS);
+    }
+
+    #[test]
+    fn test_calculate_peak_finds_spike() {
+        let mut samples = vec![120i16; 1600];
+        samples[800] = -32_000;
+        assert!((calculate_peak(&samples) - 32_000.0 / 32_767.0).abs() < 1e-6);
+        // The spike barely moves the RMS level
+        assert!(calculate_rms(&samples) < 0.03);
+
+        assert_eq!(calculate_peak(&[i16::MIN]), 1.0);
+        assert_eq!(calculate_peak(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_low_pass_attenuates_high_tone() {
+        let tone = |hz: f64| -> Vec<i16> {
+            let step = 2.0 * std::f64::consts::PI * hz / 48_000.0;
+            (0..4_800).map(|i| ((step * i as f64).sin() * 10_000.0) as i16).collect()
+        };
+        // Skip the filter's settling time
+        let filtered_rms = |samples: &[i16]| {
+            let mut filtered = samples.to_vec();
+            LowPassFilter::anti_aliasing(48_000, 16_000).process(&mut filtered);
+            calculate_rms(&filtered[480..])
+        };
+
+        let high = tone(12_000.0);
+        assert!(filtered_rms(&high) < calculate_rms(&high) / 10.0);
+        let low = tone(1_000.0);
+        assert!((filtered_rms(&low) / calculate_rms(&low) - 1.0).abs() < 0.05);
+
+        // Filtering in chunks gives the same result as in one go
+        let mut whole = high.clone();
+        LowPassFilter::anti_aliasing(48_000, 16_000).process(&mut whole);
+        let mut chunked = high;
+        let mut filter = LowPassFilter::anti_aliasing(48_000, 16_000);
+        chunked.chunks_mut(441).for_each(|chunk| filter.process(chunk));
+        assert_eq!(chunked, whole);
+    }
+
+    #[test]
+    fn test_resample_sinc_keeps_level_at_edges() {
+        // A constant signal stays constant, including the first and last
+        // samples of the chunk
+        let dc = vec![1000i16; 480];
+        for (from, to, taps) in [(48_000, 16_000, 32), (44_100, 16_000, 8), (8_000, 16_000, 16)] {
+            let resampled = resample_sinc(&dc, from, to, taps);
+            assert_eq!(resampled.len(), (480.0 * to as f64 / from as f64).ceil() as usize);
+            assert!(resampled.iter().all(|&s| (s - 1000).abs() <= 1), "{} -> {}", from, to);
+        }
+        assert!(resample_sinc(&[], 48_000, 16_000, 32).is_empty());
+    }
+
+    #[test]
+    fn test_stream_resampler_matches_one_pass() {
+        let input: Vec<i16> = (0..4_800)
+            .map(|i| ((i as f64 * 0.05).sin() * 10_000.0) as i16)
+            .collect();
+        for (from, to) in [(48_000, 16_000), (44_100, 16_000), (8_000, 16_000)] {
+            let one_pass = StreamResampler::new(from, to).process(&input);
+            let mut resampler = StreamResampler::new(from, to);
+            let chunked: Vec<i16> = input.chunks(441).flat_map(|c| resampler.process(c)).collect();
+            assert_eq!(chunked, one_pass, "{} -> {}", from, to);
+
+            // Only the last half kernel is held back
+            let expected = (input.len() as f64 * to as f64 / from as f64).ceil() as usize;
+            assert!(expected - one_pass.len() <= DEFAULT_SINC_TAPS, "{} -> {}", from, to);
+        }
+
+        // Without a low-pass to apply, one-shot resampling agrees
+        let one_pass = StreamResampler::new(8_000, 16_000).process(&input);
+        let one_shot = resample_sinc(&input, 8_000, 16_000, DEFAULT_SINC_TAPS);
+        assert_eq!(one_pass, one_shot[..one_pass.len()]);
     }
 }
@@ -1,5 +1,19 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use super::audio::AudioConfig;
+use super::error::DomainError;
+use super::hardware::HardwareProfile;
+use super::language::normalize_language;
+use super::transcription::SamplingMode;
+
+/// Bounds for `TranscriptionConfig::download_buffer_kb`.
+pub const MIN_DOWNLOAD_BUFFER_KB: u32 = 64;
+pub const MAX_DOWNLOAD_BUFFER_KB: u32 = 16 * 1024;
+
 /// Privacy-related configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -8,6 +22,35 @@ pub struct PrivacyConfig {
     pub local_only: bool,
     /// Allowed domains when local_only is false.
     pub allowed_domains: Vec<String>,
+    /// Keep local usage counters (sessions, words, audio time) in the data
+    /// directory. Opt-in; transcribed content is never stored.
+    pub usage_stats: bool,
+    /// Keep the last recording in memory so it can be re-transcribed with
+    /// different settings. Off by default; zeroized on clear, exit, or when disabled.
+    pub retain_last_recording: bool,
+    /// Write every recording to the data directory, encrypted with a key
+    /// held in the OS keychain, so it can be re-transcribed later (e.g. with
+    /// a better model). Off by default.
+    ///
+    /// Tradeoff: audio then outlives the session. It is unreadable without
+    /// the keychain entry, but anyone who can unlock the user's keychain can
+    /// decrypt it, and deleting the files is the only way to remove it.
+    pub store_encrypted_audio: bool,
+    /// Master switch guaranteeing nothing derived from dictation is kept.
+    /// While on, `enforce_ephemeral` forces and locks:
+    /// - `local_only = true` (no network requests)
+    /// - `usage_stats = false` (no counters written to disk)
+    /// - `retain_last_recording = false` (no audio kept after transcription)
+    /// - `store_encrypted_audio = false` (no audio written to disk)
+    ///
+    /// The `output.jsonl_sink` export is also skipped while it is on.
+    pub ephemeral: bool,
+    /// Apps in which dictation never starts (e.g. password managers), by
+    /// identifier: bundle ID on macOS, window class on X11. Case-insensitive.
+    pub blocked_apps: Vec<String>,
+    /// When non-empty, dictation only starts in these apps. Where the
+    /// frontmost app can't be detected (Wayland, Windows) nothing matches it.
+    pub allowed_apps: Vec<String>,
 }
 
 impl Default for PrivacyConfig {
@@ -15,6 +58,12 @@ impl Default for PrivacyConfig {
         Self {
             local_only: true,
             allowed_domains: Self::default_allowed_domains(),
+            usage_stats: false,
+            retain_last_recording: false,
+            store_encrypted_audio: false,
+            ephemeral: false,
+            blocked_apps: Vec::new(),
+            allowed_apps: Vec::new(),
         }
     }
 }
@@ -30,6 +79,33 @@ impl PrivacyConfig {
             "cdn-lfs-us-1.huggingface.co".to_string(),
         ]
     }
+
+    /// Whether an app allow or block list is set, so the frontmost app
+    /// needs to be detected before dictation starts.
+    pub fn filters_apps(&self) -> bool {
+        !self.blocked_apps.is_empty() || !self.allowed_apps.is_empty()
+    }
+
+    /// Whether dictation may start with `app` frontmost (`None` when unknown).
+    pub fn is_app_allowed(&self, app: Option<&str>) -> bool {
+        let listed = |apps: &[String]| {
+            app.is_some_and(|app| apps.iter().any(|a| a.eq_ignore_ascii_case(app)))
+        };
+        if listed(&self.blocked_apps) {
+            return false;
+        }
+        self.allowed_apps.is_empty() || listed(&self.allowed_apps)
+    }
+
+    /// Override the settings implied by `ephemeral` (no-op when it is off).
+    pub fn enforce_ephemeral(&mut self) {
+        if self.ephemeral {
+            self.local_only = true;
+            self.usage_stats = false;
+            self.retain_last_recording = false;
+            self.store_encrypted_audio = false;
+        }
+    }
 }
 
 /// Logging configuration.
@@ -64,6 +140,15 @@ pub struct UiConfig {
     pub start_minimized: bool,
     /// Theme: "system", "light", "dark".
     pub theme: String,
+    /// Show an OS notification when the microphone is lost or can't be
+    /// recovered (rate-limited).
+    pub notify_on_device_loss: bool,
+    /// The user went through onboarding and agreed to the first-run model
+    /// setup (see `AppController::ensure_default_model`).
+    pub onboarding_acknowledged: bool,
+    /// The first-run model setup found or installed a model. It isn't
+    /// retried on launch after that, even if every model is deleted.
+    pub default_model_setup_done: bool,
 }
 
 impl Default for UiConfig {
@@ -72,6 +157,9 @@ impl Default for UiConfig {
             show_tray: true,
             start_minimized: false,
             theme: "system".to_string(),
+            notify_on_device_loss: false,
+            onboarding_acknowledged: false,
+            default_model_setup_done: false,
         }
     }
 }
@@ -93,18 +181,151 @@ pub struct TranscriptionConfig {
     /// VAD: Entropy threshold for detecting non-speech.
     /// Default 2.4 from whisper.cpp recommendations.
     pub vad_entropy_threshold: f32,
+    /// Minimum segment confidence (mean token probability, 0.0-1.0).
+    /// Low-confidence segments are often hallucinations. 0.0 keeps all.
+    pub min_segment_confidence: f32,
+    /// Load the selected model in the background at startup so the first
+    /// dictation doesn't wait for it (costs startup time and memory).
+    pub preload_model_on_startup: bool,
+    /// Model to use per forced language code (e.g. "en" -> "whisper-small.en").
+    /// Mapped models are switched to before transcribing when installed;
+    /// otherwise the selected `model` is used.
+    pub language_model_map: HashMap<String, String>,
+    /// With VAD disabled, skip decoding recordings that are silent throughout.
+    pub skip_silent_buffers: bool,
+    /// Emit each segment as whisper finalizes it (`transcript-segment`
+    /// events), for a live typing preview. The final text is unaffected.
+    pub live_segments: bool,
+    /// Report the transcript as it grows (`transcribe-progress` events),
+    /// decoding long recordings in windows. Approximate: words at window
+    /// seams may come out slightly differently than in a single pass.
+    pub partial_results: bool,
+    /// Output English whatever language is spoken, using whisper's
+    /// translate task. Needs a multilingual model; `language` still names
+    /// the spoken language.
+    pub translate: bool,
+    /// Decoding strategy: greedy (fast, the default) or beam search.
+    pub sampling: SamplingMode,
+    /// Vocabulary to bias transcription toward, such as product names and
+    /// acronyms, passed to whisper as an initial prompt. Long prompts are
+    /// cut from the front to fit whisper's limit.
+    pub initial_prompt: Option<String>,
+    /// KiB of a model download collected in memory before each disk write.
+    /// Larger buffers mean fewer writes on slow disks.
+    pub download_buffer_kb: u32,
 }
 
 impl Default for TranscriptionConfig {
     fn default() -> Self {
+        let vad = VadDefaults::default();
         Self {
             model: "whisper-small".to_string(),
             language: "auto".to_string(),
             vad_enabled: true,
-            // Defaults from whisper.cpp:
-            // https://github.com/ggerganov/whisper.cpp/blob/master/whisper.h
-            vad_no_speech_threshold: 0.6,
-            vad_entropy_threshold: 2.4,
+            vad_no_speech_threshold: vad.no_speech_threshold,
+            vad_entropy_threshold: vad.entropy_threshold,
+            min_segment_confidence: 0.0,
+            preload_model_on_startup: false,
+            language_model_map: HashMap::new(),
+            skip_silent_buffers: false,
+            live_segments: false,
+            partial_results: false,
+            translate: false,
+            sampling: SamplingMode::default(),
+            initial_prompt: None,
+            download_buffer_kb: 2048,
+        }
+    }
+}
+
+impl TranscriptionConfig {
+    /// Validate the language and VAD thresholds (see `VadDefaults` for ranges).
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if normalize_language(&self.language).is_none() {
+            return Err(DomainError::Config(format!(
+                "Unknown language: {}",
+                self.language
+            )));
+        }
+
+        for language in self.language_model_map.keys() {
+            if normalize_language(language).map_or(true, |l| l == "auto") {
+                return Err(DomainError::Config(format!(
+                    "Unknown language in language_model_map: {}",
+                    language
+                )));
+            }
+        }
+
+        let vad = VadDefaults::default();
+
+        if !(vad.no_speech_min..=vad.no_speech_max).contains(&self.vad_no_speech_threshold) {
+            return Err(DomainError::Config(format!(
+                "vad_no_speech_threshold must be between {} and {}, got {}",
+                vad.no_speech_min, vad.no_speech_max, self.vad_no_speech_threshold
+            )));
+        }
+
+        if !(vad.entropy_min..=vad.entropy_max).contains(&self.vad_entropy_threshold) {
+            return Err(DomainError::Config(format!(
+                "vad_entropy_threshold must be between {} and {}, got {}",
+                vad.entropy_min, vad.entropy_max, self.vad_entropy_threshold
+            )));
+        }
+
+        if !(0.0..=1.0).contains(&self.min_segment_confidence) {
+            return Err(DomainError::Config(format!(
+                "min_segment_confidence must be between 0 and 1, got {}",
+                self.min_segment_confidence
+            )));
+        }
+
+        if self.sampling.candidates() == 0 {
+            return Err(DomainError::Config(
+                "sampling best_of and beam_size must be at least 1".to_string(),
+            ));
+        }
+
+        if !(MIN_DOWNLOAD_BUFFER_KB..=MAX_DOWNLOAD_BUFFER_KB).contains(&self.download_buffer_kb) {
+            return Err(DomainError::Config(format!(
+                "download_buffer_kb must be between {} and {}, got {}",
+                MIN_DOWNLOAD_BUFFER_KB, MAX_DOWNLOAD_BUFFER_KB, self.download_buffer_kb
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Recommended VAD thresholds and their valid ranges (for settings sliders).
+#[derive(Debug, Clone, Serialize)]
+pub struct VadDefaults {
+    /// Recommended no-speech probability threshold.
+    pub no_speech_threshold: f32,
+    /// Minimum valid no-speech threshold.
+    pub no_speech_min: f32,
+    /// Maximum valid no-speech threshold.
+    pub no_speech_max: f32,
+    /// Recommended entropy threshold.
+    pub entropy_threshold: f32,
+    /// Minimum valid entropy threshold.
+    pub entropy_min: f32,
+    /// Maximum valid entropy threshold.
+    pub entropy_max: f32,
+}
+
+impl Default for VadDefaults {
+    fn default() -> Self {
+        // Defaults from whisper.cpp:
+        // https://github.com/ggerganov/whisper.cpp/blob/master/whisper.h
+        Self {
+            no_speech_threshold: 0.6,
+            no_speech_min: 0.0,
+            no_speech_max: 1.0,
+            entropy_threshold: 2.4,
+            // Token entropy is non-negative; above ~5 nearly all speech is rejected
+            entropy_min: 0.0,
+            entropy_max: 5.0,
         }
     }
 }
@@ -129,19 +350,108 @@ impl Default for ShortcutConfig {
     }
 }
 
+/// How transcribed text is delivered to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMode {
+    /// Copy to clipboard and simulate a paste into the active application.
+    #[default]
+    Paste,
+    /// Copy to clipboard only; the user pastes manually.
+    /// Safe fallback where synthetic input is unreliable (Wayland, locked-down apps).
+    ClipboardOnly,
+}
+
+/// Casing forced onto transcribed text, e.g. for code or constant names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseTransform {
+    /// Keep the casing Whisper produced.
+    #[default]
+    None,
+    /// all lowercase.
+    Lower,
+    /// ALL UPPERCASE.
+    Upper,
+    /// First Letter Of Every Word uppercased, the rest left as is.
+    Title,
+}
+
+/// Where a single transcription is delivered, overriding `OutputConfig::mode`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputSink {
+    /// Inject into the active application as configured by `OutputConfig::mode`.
+    ActiveApp,
+    /// Copy to the clipboard only.
+    Clipboard,
+    /// Append to a text file, one transcription per line.
+    File { path: PathBuf },
+}
+
 /// Output/text injection configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct OutputConfig {
     /// Delay in ms before simulating paste (for clipboard sync).
     pub paste_delay_ms: u64,
+    /// Output mode: "paste" or "clipboard_only".
+    pub mode: OutputMode,
+    /// Append consecutive dictations to a pending transcript, with spacing
+    /// handled between chunks, until it is finalized.
+    pub append_mode: bool,
+    /// Prefix right-to-left output (Arabic, Hebrew, ...) with a direction mark
+    /// so it displays correctly in left-to-right fields.
+    pub rtl_marks: bool,
+    /// Force the casing of transcribed text.
+    pub case_transform: CaseTransform,
+    /// Linux only: also put the text in the PRIMARY selection, for
+    /// middle-click paste. Ignored on other platforms.
+    pub use_primary_selection: bool,
+    /// Hold each dictation for review: a "confirm-inject" event is emitted
+    /// and nothing is injected until the frontend calls `confirm_inject`.
+    pub confirm_before_inject: bool,
+    /// Seconds a dictation waits for confirmation before it is discarded.
+    pub confirm_timeout_secs: u32,
+    /// Append each transcription as a JSON line to this file, for external
+    /// tools to tail. Unlike history, this is an integration point.
+    pub jsonl_sink: Option<PathBuf>,
 }
 
 impl Default for OutputConfig {
     fn default() -> Self {
         Self {
             paste_delay_ms: 100,
+            mode: OutputMode::Paste,
+            append_mode: false,
+            rtl_marks: false,
+            case_transform: CaseTransform::None,
+            use_primary_selection: false,
+            confirm_before_inject: false,
+            confirm_timeout_secs: 60,
+            jsonl_sink: None,
+        }
+    }
+}
+
+impl OutputConfig {
+    /// Validate configuration values before applying them.
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if let Some(path) = &self.jsonl_sink {
+            if !path.is_absolute() {
+                return Err(DomainError::Config(format!(
+                    "jsonl_sink must be an absolute path, got {}",
+                    path.display()
+                )));
+            }
+            if path.is_dir() {
+                return Err(DomainError::Config(format!(
+                    "jsonl_sink must be a file, got directory {}",
+                    path.display()
+                )));
+            }
         }
+        Ok(())
     }
 }
 
@@ -155,6 +465,10 @@ pub struct AppConfig {
     pub transcription: TranscriptionConfig,
     pub shortcut: ShortcutConfig,
     pub output: OutputConfig,
+    pub audio: AudioConfig,
+    /// Hardware profile to use instead of the detected one, for model
+    /// recommendations and thread counts.
+    pub hardware_override: Option<HardwareProfile>,
 }
 
 impl AppConfig {
@@ -162,4 +476,211 @@ impl AppConfig {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Validate configuration values before applying them.
+    pub fn validate(&self) -> Result<(), DomainError> {
+        self.transcription.validate()?;
+        self.output.validate()?;
+        if let Some(profile) = &self.hardware_override {
+            profile.validate()?;
+        }
+        self.audio.validate()
+    }
+
+    /// Reset settings that fail validation to their defaults, keeping the
+    /// valid ones. For a config read from disk, where a bad value shouldn't
+    /// keep the app from starting. Returns a description of each reset.
+    pub fn repair(&mut self) -> Vec<String> {
+        let mut reset = Vec::new();
+        repair_section(
+            "transcription",
+            &mut self.transcription,
+            TranscriptionConfig::validate,
+            &mut reset,
+        );
+        repair_section("output", &mut self.output, OutputConfig::validate, &mut reset);
+        repair_section("audio", &mut self.audio, AudioConfig::validate, &mut reset);
+        if let Some(Err(e)) = self.hardware_override.as_ref().map(HardwareProfile::validate) {
+            reset.push(format!("hardware_override: {}", e));
+            self.hardware_override = None;
+        }
+        reset
+    }
+}
+
+/// Rebuild an invalid config section setting by setting: starting from the
+/// defaults, each of its settings is kept unless it makes validation fail.
+fn repair_section<T>(
+    name: &str,
+    section: &mut T,
+    validate: impl Fn(&T) -> Result<(), DomainError>,
+    reset: &mut Vec<String>,
+) where
+    T: Serialize + DeserializeOwned + Default,
+{
+    if validate(section).is_ok() {
+        return;
+    }
+    let table = |value: &T| match toml::Value::try_from(value) {
+        Ok(toml::Value::Table(table)) => Some(table),
+        _ => None,
+    };
+    let (Some(fields), Some(mut repaired)) = (table(section), table(&T::default())) else {
+        reset.push(format!("{}: all settings", name));
+        *section = T::default();
+        return;
+    };
+
+    for (key, value) in fields {
+        let mut candidate = repaired.clone();
+        candidate.insert(key.clone(), value);
+        let checked = toml::Value::Table(candidate.clone())
+            .try_into::<T>()
+            .map_err(DomainError::from)
+            .and_then(|parsed| validate(&parsed));
+        match checked {
+            Ok(()) => repaired = candidate,
+            Err(e) => reset.push(format!("{}.{}: {}", name, key, e)),
+        }
+    }
+    *section = toml::Value::Table(repaired).try_into().unwrap_or_default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(AppConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_ephemeral_forces_dependent_flags() {
+        let mut privacy = PrivacyConfig {
+            local_only: false,
+            usage_stats: true,
+            retain_last_recording: true,
+            store_encrypted_audio: true,
+            ephemeral: true,
+            ..Default::default()
+        };
+        privacy.enforce_ephemeral();
+        assert!(privacy.local_only);
+        assert!(!privacy.usage_stats);
+        assert!(!privacy.retain_last_recording);
+        assert!(!privacy.store_encrypted_audio);
+
+        // Without ephemeral, user choices are kept
+        let mut privacy = PrivacyConfig {
+            local_only: false,
+            usage_stats: true,
+            ..Default::default()
+        };
+        privacy.enforce_ephemeral();
+        assert!(!privacy.local_only);
+        assert!(privacy.usage_stats);
+    }
+
+    #[test]
+    fn test_app_allow_and_block_lists() {
+        assert!(!PrivacyConfig::default().filters_apps());
+
+        let mut privacy = PrivacyConfig {
+            blocked_apps: vec!["com.1password.1password".to_string()],
+            ..Default::default()
+        };
+        assert!(privacy.filters_apps());
+        assert!(privacy.is_app_allowed(Some("com.apple.TextEdit")));
+        assert!(privacy.is_app_allowed(None));
+        assert!(!privacy.is_app_allowed(Some("com.1Password.1password")));
+
+        privacy.allowed_apps = vec!["code".to_string(), "com.1password.1password".to_string()];
+        assert!(privacy.is_app_allowed(Some("Code")));
+        assert!(!privacy.is_app_allowed(Some("firefox")));
+        assert!(!privacy.is_app_allowed(None));
+        // The blocklist wins
+        assert!(!privacy.is_app_allowed(Some("com.1password.1password")));
+    }
+
+    #[test]
+    fn test_unknown_language_rejected() {
+        let mut config = AppConfig::default();
+        config.transcription.language = "en-GB".to_string();
+        assert!(config.validate().is_ok());
+
+        config.transcription.language = "klingon".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_output_sink_payload() {
+        let sink: OutputSink =
+            serde_json::from_str(r#"{"type":"file","path":"/tmp/notes.txt"}"#).unwrap();
+        assert_eq!(
+            sink,
+            OutputSink::File {
+                path: PathBuf::from("/tmp/notes.txt")
+            }
+        );
+        let sink: OutputSink = serde_json::from_str(r#"{"type":"clipboard"}"#).unwrap();
+        assert_eq!(sink, OutputSink::Clipboard);
+    }
+
+    #[test]
+    fn test_language_model_map_keys_validated() {
+        let mut config = AppConfig::default();
+        let map = &mut config.transcription.language_model_map;
+        map.insert("EN".to_string(), "whisper-small.en".to_string());
+        assert!(config.validate().is_ok());
+
+        for language in ["auto", "klingon"] {
+            let mut config = AppConfig::default();
+            let map = &mut config.transcription.language_model_map;
+            map.insert(language.to_string(), "whisper-small".to_string());
+            assert!(config.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_vad_thresholds_out_of_range() {
+        let mut config = AppConfig::default();
+        config.transcription.vad_no_speech_threshold = 1.5;
+        assert!(config.validate().is_err());
+
+        let mut config = AppConfig::default();
+        config.transcription.vad_entropy_threshold = -0.1;
+        assert!(config.validate().is_err());
+
+        let mut config = AppConfig::default();
+        config.transcription.vad_no_speech_threshold = f32::NAN;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_download_buffer_bounds() {
+        let mut config = AppConfig::default();
+        config.transcription.download_buffer_kb = MAX_DOWNLOAD_BUFFER_KB;
+        assert!(config.validate().is_ok());
+
+        config.transcription.download_buffer_kb = MIN_DOWNLOAD_BUFFER_KB - 1;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_sampling_mode_config() {
+        let config: TranscriptionConfig =
+            toml::from_str("sampling = { beam = { beam_size = 5 } }").unwrap();
+        assert_eq!(config.sampling, SamplingMode::Beam { beam_size: 5 });
+        assert!(config.validate().is_ok());
+
+        let config: TranscriptionConfig = toml::from_str("").unwrap();
+        assert_eq!(config.sampling, SamplingMode::Greedy { best_of: 1 });
+
+        let config = TranscriptionConfig {
+            sampling: SamplingMode::Greedy { best_of: 0 },
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
 }
@@ -55,14 +55,32 @@ pub enum DomainError {
     #[error("Whisper error: {0}")]
     Whisper(String),
 
+    #[error(
+        "Model {model} is English-only and can't transcribe '{language}'; \
+         install a multilingual model"
+    )]
+    LanguageUnsupported { language: String, model: String },
+
     #[error("Clipboard error: {0}")]
     Clipboard(String),
 
     #[error("Input simulation error: {0}")]
     InputSimulation(String),
 
+    #[error("Encryption error: {0}")]
+    Encryption(String),
+
+    #[error("Shortcut {shortcut} is unavailable, likely used by another app: {reason}")]
+    ShortcutConflict { shortcut: String, reason: String },
+
     #[error("Invalid state for this operation")]
     InvalidState,
+
+    #[error("Application is shutting down")]
+    ShuttingDown,
+
+    #[error("No text is awaiting confirmation")]
+    NothingToConfirm,
 }
 
 impl From<std::io::Error> for DomainError {
@@ -88,3 +106,18 @@ impl From<serde_json::Error> for DomainError {
         DomainError::Serialization(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde_errors_map_to_serialization() {
+        let err: DomainError = serde_json::from_str::<u32>("not json").unwrap_err().into();
+        assert!(matches!(err, DomainError::Serialization(_)));
+
+        // Parse errors in the config file are configuration errors
+        let err: DomainError = toml::from_str::<toml::Table>("= broken").unwrap_err().into();
+        assert!(matches!(err, DomainError::Config(_)));
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use super::error::DomainError;
 use super::model::Quantization;
 
 /// CPU architecture.
@@ -35,7 +36,7 @@ impl std::fmt::Display for CpuArch {
 }
 
 /// SIMD capabilities of the CPU.
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SimdCapabilities {
     /// x86: AVX support.
     pub avx: bool,
@@ -115,8 +116,62 @@ impl std::fmt::Display for OsType {
     }
 }
 
+/// Graphical session type.
+///
+/// Only meaningful on Linux, where synthetic input depends on the display
+/// server. Other platforms always report `Native`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionType {
+    /// X11 session (enigo synthetic input works).
+    X11,
+    /// Wayland session (X11 synthetic input is usually ignored).
+    Wayland,
+    /// Non-Linux platform with a single native input API.
+    Native,
+    /// Session type could not be determined.
+    Unknown,
+}
+
+impl SessionType {
+    /// Detect the current session type from the environment.
+    #[cfg(target_os = "linux")]
+    pub fn detect() -> Self {
+        let session_type = std::env::var("XDG_SESSION_TYPE").ok();
+        let has_wayland_display = std::env::var_os("WAYLAND_DISPLAY").is_some();
+        Self::from_env(session_type.as_deref(), has_wayland_display)
+    }
+
+    /// Detect the current session type from the environment.
+    #[cfg(not(target_os = "linux"))]
+    pub fn detect() -> Self {
+        SessionType::Native
+    }
+
+    /// Resolve the session type from `XDG_SESSION_TYPE` and the presence of
+    /// `WAYLAND_DISPLAY` (used when the former is unset, e.g. under sudo).
+    pub fn from_env(xdg_session_type: Option<&str>, has_wayland_display: bool) -> Self {
+        match xdg_session_type.map(|s| s.trim().to_lowercase()).as_deref() {
+            Some("wayland") => SessionType::Wayland,
+            Some("x11") => SessionType::X11,
+            _ if has_wayland_display => SessionType::Wayland,
+            _ => SessionType::Unknown,
+        }
+    }
+}
+
+impl std::fmt::Display for SessionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionType::X11 => write!(f, "x11"),
+            SessionType::Wayland => write!(f, "wayland"),
+            SessionType::Native => write!(f, "native"),
+            SessionType::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
 /// Hardware profile of the system.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct HardwareProfile {
     /// CPU architecture.
     pub arch: CpuArch,
@@ -143,6 +198,66 @@ impl HardwareProfile {
     pub fn recommended_threads(&self) -> u32 {
         std::cmp::max(1, self.cores.saturating_sub(1))
     }
+
+    /// Compare the thread count transcription runs with against the cores.
+    ///
+    /// Fewer than half the physical cores is flagged as underused: usually a
+    /// too-small override or a container misreporting its CPUs.
+    pub fn thread_usage(&self, threads: u32) -> ThreadUsage {
+        ThreadUsage {
+            threads,
+            cores: self.cores,
+            recommended: self.recommended_threads(),
+            underused: threads.saturating_mul(2) < self.cores,
+        }
+    }
+
+    /// Stable identifier of this profile, for caching measurements that
+    /// depend on the hardware. Any override change yields a new fingerprint.
+    pub fn fingerprint(&self) -> String {
+        let simd: Vec<&str> = [
+            (self.simd.avx, "avx"),
+            (self.simd.avx2, "avx2"),
+            (self.simd.avx512, "avx512"),
+            (self.simd.neon, "neon"),
+        ]
+        .into_iter()
+        .filter_map(|(supported, name)| supported.then_some(name))
+        .collect();
+
+        format!(
+            "{}-{}c{}t-{}gb-{}-{}",
+            self.arch,
+            self.cores,
+            self.threads,
+            self.ram_gb(),
+            self.os,
+            if simd.is_empty() { "nosimd".to_string() } else { simd.join("+") },
+        )
+    }
+
+    /// Validate a user-supplied profile (see `AppConfig::hardware_override`).
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.cores == 0 || self.threads == 0 || self.ram_bytes == 0 {
+            return Err(DomainError::Config(
+                "Hardware profile needs at least one core, one thread and some RAM".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Transcription thread count compared to the hardware profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ThreadUsage {
+    /// Threads used for transcription.
+    pub threads: u32,
+    /// Number of physical CPU cores.
+    pub cores: u32,
+    /// Thread count recommended for this profile.
+    pub recommended: u32,
+    /// Whether `threads` is well below the core count.
+    pub underused: bool,
 }
 
 /// Model recommendation based on hardware profile.
@@ -192,5 +307,32 @@ mod tests {
         // recommended_threads = cores - 1 = 7
         assert_eq!(profile.recommended_threads(), 7);
         assert_eq!(profile.ram_gb(), 16);
+
+        let usage = profile.thread_usage(7);
+        assert_eq!(usage.recommended, 7);
+        assert!(!usage.underused);
+        assert!(!profile.thread_usage(4).underused);
+        assert!(profile.thread_usage(3).underused);
+
+        let simd = SimdCapabilities {
+            avx: true,
+            avx2: true,
+            ..Default::default()
+        };
+        assert_eq!(profile.fingerprint(), "x86_64-8c8t-16gb-macOS-nosimd");
+        assert_eq!(
+            HardwareProfile { simd, ..profile.clone() }.fingerprint(),
+            "x86_64-8c8t-16gb-macOS-avx+avx2"
+        );
+    }
+
+    #[test]
+    fn test_session_type_from_env() {
+        assert_eq!(SessionType::from_env(Some("wayland"), false), SessionType::Wayland);
+        assert_eq!(SessionType::from_env(Some("X11"), true), SessionType::X11);
+        // Unset or unrecognized session type falls back to WAYLAND_DISPLAY
+        assert_eq!(SessionType::from_env(None, true), SessionType::Wayland);
+        assert_eq!(SessionType::from_env(Some("tty"), false), SessionType::Unknown);
+        assert_eq!(SessionType::from_env(None, false), SessionType::Unknown);
     }
 }
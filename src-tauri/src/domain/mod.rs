@@ -2,12 +2,33 @@ pub mod audio;
 pub mod config;
 pub mod error;
 pub mod hardware;
+pub mod language;
 pub mod model;
+pub mod redaction;
+pub mod stats;
+pub mod text;
 pub mod transcription;
 
-pub use audio::{AtomicAudioState, AudioConfig, AudioDevice, AudioEvent, AudioState};
-pub use config::AppConfig;
+pub use audio::{
+    apply_agc, apply_noise_gate, calculate_peak, calculate_rms, calculate_rms_dbfs, resample,
+    rms_to_dbfs, AtomicAudioState, AudioConfig, AudioDevice, AudioEvent, AudioState,
+    CaptureRateMeasurement, ChannelMode, LowPassFilter, StreamInfo, StreamResampler,
+};
+pub use config::{AppConfig, OutputMode, OutputSink, VadDefaults};
 pub use error::DomainError;
-pub use hardware::{CpuArch, HardwareProfile, ModelRecommendation, OsType, SimdCapabilities};
-pub use model::{DownloadProgress, InstalledModel, ModelCatalog, Quantization};
-pub use transcription::AudioBuffer;
+pub use hardware::{
+    CpuArch, HardwareProfile, ModelRecommendation, OsType, SessionType, SimdCapabilities,
+    ThreadUsage,
+};
+pub use language::normalize_language;
+pub use redaction::redact_secrets;
+pub use model::{
+    DownloadProgress, DownloadQueueEntry, InstalledModel, LanguageModelSuggestion, ModelCatalog,
+    ModelFileKind, ModelsDirEntry, Quantization,
+};
+pub use stats::{BenchmarkCache, BenchmarkResult, UsageStats};
+pub use text::process_output;
+pub use transcription::{
+    AudioBuffer, DictationSummary, SamplingMode, StoredRecording, TranscriptRecord,
+    TranscriptSegment,
+};
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Quantization level for GGUF models.
@@ -39,6 +40,28 @@ impl Quantization {
             _ => None,
         }
     }
+
+    /// Quality rank, from smallest/lowest quality (0) to largest/highest (4).
+    pub fn rank(&self) -> u8 {
+        match self {
+            Quantization::Q4_0 => 0,
+            Quantization::Q5_0 => 1,
+            Quantization::Q5_1 => 2,
+            Quantization::Q8_0 => 3,
+            Quantization::F16 => 4,
+        }
+    }
+
+    /// Pick the candidate closest to `self` by quality rank.
+    ///
+    /// An exact match wins; otherwise the smallest rank distance, and on a tie
+    /// the higher-quality candidate (Q5_1 prefers Q8_0 over Q5_0).
+    pub fn nearest(&self, candidates: &[Quantization]) -> Option<Quantization> {
+        candidates.iter().copied().min_by_key(|c| {
+            let distance = (c.rank() as i16 - self.rank() as i16).unsigned_abs();
+            (distance, std::cmp::Reverse(c.rank()))
+        })
+    }
 }
 
 impl std::fmt::Display for Quantization {
@@ -73,9 +96,21 @@ pub struct ModelInfo {
     pub min_ram_gb: u32,
     /// Available variants (quantization levels).
     pub variants: Vec<ModelVariant>,
+    /// Trained on English only (the ".en" whisper models); other forced
+    /// languages aren't transcribed.
+    #[serde(default)]
+    pub english_only: bool,
 }
 
 impl ModelInfo {
+    /// Estimated memory needed to load a variant: the weights plus ~50% for
+    /// the whisper context and buffers. None if the variant size is unknown.
+    pub fn estimated_ram_bytes(&self, quant: Quantization) -> Option<u64> {
+        self.variant(quant)
+            .map(|v| v.size_bytes + v.size_bytes / 2)
+            .filter(|&bytes| bytes > 0)
+    }
+
     /// Find a specific variant by quantization.
     pub fn variant(&self, quant: Quantization) -> Option<&ModelVariant> {
         self.variants.iter().find(|v| v.quantization == quant)
@@ -87,6 +122,24 @@ impl ModelInfo {
         self.variant(Quantization::Q5_1)
             .or_else(|| self.variants.first())
     }
+
+    /// Quantization levels of this model that are installed locally.
+    pub fn installed_variants(&self, installed: &[InstalledModel]) -> Vec<Quantization> {
+        installed
+            .iter()
+            .filter(|m| m.id == self.id)
+            .map(|m| m.quantization)
+            .collect()
+    }
+
+    /// Closest installed quantization to `quant` (see `Quantization::nearest`).
+    pub fn nearest_variant(
+        &self,
+        quant: Quantization,
+        installed: &[InstalledModel],
+    ) -> Option<Quantization> {
+        quant.nearest(&self.installed_variants(installed))
+    }
 }
 
 /// Catalog of available models.
@@ -94,8 +147,24 @@ impl ModelInfo {
 pub struct ModelCatalog {
     /// Catalog version for compatibility checking.
     pub version: u32,
-    /// Available models.
+    /// Available models, from smallest to largest.
     pub models: Vec<ModelInfo>,
+    /// Smallest model giving usable accuracy per language code. Languages
+    /// not listed work with any model.
+    #[serde(default)]
+    pub language_minimums: HashMap<String, String>,
+}
+
+/// Advice that the selected model is too small for a language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LanguageModelSuggestion {
+    pub language: String,
+    /// Model that would be used for the language.
+    pub model: String,
+    /// Smallest model recommended for the language.
+    pub recommended_model: String,
+    /// Human-readable warning for settings.
+    pub message: String,
 }
 
 impl ModelCatalog {
@@ -108,6 +177,76 @@ impl ModelCatalog {
     pub fn model_ids(&self) -> Vec<&str> {
         self.models.iter().map(|m| m.id.as_str()).collect()
     }
+
+    /// Suggest a larger model if `model_id` is below the recommended minimum
+    /// for `language`, or English-only when the language isn't English.
+    /// Returns `None` when the model is suitable, or when either the language
+    /// or the model isn't in the catalog.
+    pub fn suggest_model_for_language(
+        &self,
+        model_id: &str,
+        language: &str,
+    ) -> Option<LanguageModelSuggestion> {
+        let minimum = self.language_minimums.get(language)?;
+        let rank = |id: &str| self.models.iter().position(|m| m.id == id);
+        let english_only = |id: &str| self.get(id).is_some_and(|m| m.english_only);
+        let needs_multilingual = language != "en";
+
+        // English-only models can't transcribe other languages at any size
+        let recommended = if needs_multilingual && english_only(minimum) {
+            self.models[rank(minimum)?..]
+                .iter()
+                .find(|m| !m.english_only)?
+                .id
+                .as_str()
+        } else {
+            minimum.as_str()
+        };
+        let unusable = needs_multilingual && english_only(model_id);
+        if rank(model_id)? >= rank(recommended)? && !unusable {
+            return None;
+        }
+
+        let name = |id: &str| self.get(id).map_or(id, |m| m.name.as_str()).to_string();
+        let message = if unusable {
+            format!(
+                "{} is English-only and can't transcribe '{}'; use {} or larger.",
+                name(model_id),
+                language,
+                name(recommended)
+            )
+        } else {
+            format!(
+                "{} is too small for accurate '{}' transcription; use {} or larger.",
+                name(model_id),
+                language,
+                name(recommended)
+            )
+        };
+        Some(LanguageModelSuggestion {
+            language: language.to_string(),
+            model: model_id.to_string(),
+            recommended_model: recommended.to_string(),
+            message,
+        })
+    }
+
+    /// First installed model (smallest, in catalog order) that can transcribe
+    /// languages other than English, skipping files that failed verification.
+    pub fn multilingual_fallback(
+        &self,
+        installed: &[InstalledModel],
+    ) -> Option<(&ModelInfo, Quantization)> {
+        self.models
+            .iter()
+            .filter(|m| !m.english_only)
+            .find_map(|model| {
+                installed
+                    .iter()
+                    .find(|m| m.id == model.id && !m.verification_failed)
+                    .map(|m| (model, m.quantization))
+            })
+    }
 }
 
 /// An installed model on the local filesystem.
@@ -123,6 +262,35 @@ pub struct InstalledModel {
     pub sha256: String,
     /// File size in bytes.
     pub size_bytes: u64,
+    /// The file failed a checksum check after it wouldn't load; it should
+    /// be downloaded again.
+    #[serde(default)]
+    pub verification_failed: bool,
+}
+
+/// What a file in the models directory is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ModelFileKind {
+    /// A model listed by `list_installed`.
+    InstalledModel,
+    /// An interrupted or in-progress download (`.download`) or file import
+    /// (`.import`).
+    PartialDownload,
+    /// Persists that an installed model failed verification (`.corrupt`).
+    CorruptMarker,
+    /// Anything else: renamed, foreign or unknown-variant files.
+    Unknown,
+}
+
+/// A file in the models directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelsDirEntry {
+    /// File name, relative to the models directory.
+    pub name: String,
+    pub kind: ModelFileKind,
+    /// File size in bytes.
+    pub size_bytes: u64,
 }
 
 /// Progress information for model download.
@@ -138,6 +306,8 @@ pub struct DownloadProgress {
     pub total_bytes: u64,
     /// Download progress as percentage (0.0 - 100.0).
     pub percent: f32,
+    /// The total size is unknown, so `percent` is meaningless.
+    pub indeterminate: bool,
 }
 
 impl DownloadProgress {
@@ -149,21 +319,35 @@ impl DownloadProgress {
             bytes_downloaded: 0,
             total_bytes: 0,
             percent: 0.0,
+            indeterminate: false,
         }
     }
 
     /// Update progress with downloaded bytes.
+    ///
+    /// `percent` is clamped to 100 in case the server under-reports the size;
+    /// a `total` of 0 (unknown length) marks the progress indeterminate.
     pub fn update(&mut self, downloaded: u64, total: u64) {
         self.bytes_downloaded = downloaded;
         self.total_bytes = total;
+        self.indeterminate = total == 0;
         self.percent = if total > 0 {
-            (downloaded as f32 / total as f32) * 100.0
+            ((downloaded as f64 / total as f64) * 100.0).clamp(0.0, 100.0) as f32
         } else {
             0.0
         };
     }
 }
 
+/// A download waiting in, or at the front of, the download queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadQueueEntry {
+    #[serde(flatten)]
+    pub progress: DownloadProgress,
+    /// Whether this download is transferring (only the front entry is).
+    pub active: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -174,10 +358,198 @@ mod tests {
         assert_eq!(Quantization::from_suffix("Q5_1"), Some(Quantization::Q5_1));
     }
 
+    fn installed(id: &str, quantization: Quantization) -> InstalledModel {
+        InstalledModel {
+            id: id.to_string(),
+            quantization,
+            path: PathBuf::from(format!("{}-{}.bin", id, quantization)),
+            sha256: String::new(),
+            size_bytes: 0,
+            verification_failed: false,
+        }
+    }
+
+    #[test]
+    fn test_estimated_ram_bytes() {
+        let info = ModelInfo {
+            id: "whisper-small".to_string(),
+            name: "Whisper Small".to_string(),
+            description: String::new(),
+            min_ram_gb: 2,
+            english_only: false,
+            variants: vec![ModelVariant {
+                quantization: Quantization::Q5_1,
+                size_bytes: 200_000_000,
+                sha256: String::new(),
+                url: String::new(),
+            }],
+        };
+        assert_eq!(info.estimated_ram_bytes(Quantization::Q5_1), Some(300_000_000));
+        assert_eq!(info.estimated_ram_bytes(Quantization::F16), None);
+    }
+
+    #[test]
+    fn test_nearest_variant_fallback() {
+        let info = ModelInfo {
+            id: "whisper-small".to_string(),
+            name: "Whisper Small".to_string(),
+            description: String::new(),
+            min_ram_gb: 2,
+            english_only: false,
+            variants: Vec::new(),
+        };
+        let models = vec![
+            installed("whisper-small", Quantization::Q5_0),
+            installed("whisper-small", Quantization::F16),
+            installed("whisper-base", Quantization::Q5_1),
+        ];
+
+        assert_eq!(
+            info.installed_variants(&models),
+            vec![Quantization::Q5_0, Quantization::F16]
+        );
+        // Exact match
+        assert_eq!(info.nearest_variant(Quantization::Q5_0, &models), Some(Quantization::Q5_0));
+        // Q5_1 is one step from Q5_0 and two from F16
+        assert_eq!(info.nearest_variant(Quantization::Q5_1, &models), Some(Quantization::Q5_0));
+        // Q8_0 is one step from F16
+        assert_eq!(info.nearest_variant(Quantization::Q8_0, &models), Some(Quantization::F16));
+        // No installed variants
+        assert_eq!(info.nearest_variant(Quantization::Q5_1, &models[2..]), None);
+    }
+
+    #[test]
+    fn test_nearest_prefers_higher_quality_on_tie() {
+        let candidates = [Quantization::Q5_0, Quantization::Q8_0];
+        assert_eq!(Quantization::Q5_1.nearest(&candidates), Some(Quantization::Q8_0));
+    }
+
+    #[test]
+    fn test_suggest_model_for_language() {
+        let model = |id: &str| ModelInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            min_ram_gb: 1,
+            english_only: false,
+            variants: Vec::new(),
+        };
+        let catalog = ModelCatalog {
+            version: 1,
+            models: vec![model("tiny"), model("small"), model("large")],
+            language_minimums: HashMap::from([
+                ("fr".to_string(), "small".to_string()),
+                ("sw".to_string(), "large".to_string()),
+            ]),
+        };
+
+        let suggestion = catalog.suggest_model_for_language("tiny", "sw").unwrap();
+        assert_eq!(suggestion.recommended_model, "large");
+        assert!(suggestion.message.contains("'sw'"));
+        assert!(catalog.suggest_model_for_language("small", "sw").is_some());
+
+        // Big enough, unlisted language, unknown model
+        assert_eq!(catalog.suggest_model_for_language("small", "fr"), None);
+        assert_eq!(catalog.suggest_model_for_language("large", "sw"), None);
+        assert_eq!(catalog.suggest_model_for_language("tiny", "en"), None);
+        assert_eq!(catalog.suggest_model_for_language("custom", "fr"), None);
+
+        // English-only models are never enough for other languages
+        let catalog = ModelCatalog {
+            models: vec![
+                ModelInfo {
+                    english_only: true,
+                    ..model("small.en")
+                },
+                model("medium"),
+            ],
+            language_minimums: HashMap::from([
+                ("en".to_string(), "small.en".to_string()),
+                ("fr".to_string(), "small.en".to_string()),
+            ]),
+            ..catalog
+        };
+        let suggestion = catalog.suggest_model_for_language("small.en", "fr").unwrap();
+        assert_eq!(suggestion.recommended_model, "medium");
+        assert!(suggestion.message.contains("English-only"));
+        assert_eq!(catalog.suggest_model_for_language("medium", "fr"), None);
+        assert_eq!(catalog.suggest_model_for_language("small.en", "en"), None);
+    }
+
+    #[test]
+    fn test_bundled_catalog_version_bumped() {
+        // Version 1 had neither `english_only` nor `language_minimums`
+        let catalog: ModelCatalog =
+            serde_json::from_str(include_str!("../../resources/model_catalog.json")).unwrap();
+        assert!(catalog.version > 1);
+    }
+
+    #[test]
+    fn test_bundled_language_minimums_are_multilingual() {
+        let catalog: ModelCatalog =
+            serde_json::from_str(include_str!("../../resources/model_catalog.json")).unwrap();
+        for (language, model_id) in &catalog.language_minimums {
+            let model = catalog.get(model_id).unwrap();
+            assert!(language == "en" || !model.english_only, "{} -> {}", language, model_id);
+        }
+    }
+
+    #[test]
+    fn test_multilingual_fallback() {
+        let model = |id: &str, english_only| ModelInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            min_ram_gb: 1,
+            english_only,
+            variants: Vec::new(),
+        };
+        let installed = |id: &str, verification_failed| InstalledModel {
+            id: id.to_string(),
+            quantization: Quantization::Q5_0,
+            path: PathBuf::new(),
+            sha256: String::new(),
+            size_bytes: 0,
+            verification_failed,
+        };
+        let catalog = ModelCatalog {
+            version: 1,
+            models: vec![model("small.en", true), model("medium", false), model("large", false)],
+            language_minimums: HashMap::new(),
+        };
+
+        assert!(catalog.multilingual_fallback(&[installed("small.en", false)]).is_none());
+        assert!(catalog.multilingual_fallback(&[installed("medium", true)]).is_none());
+        let all = [installed("large", false), installed("medium", false)];
+        let (model, quant) = catalog.multilingual_fallback(&all).unwrap();
+        assert_eq!((model.id.as_str(), quant), ("medium", Quantization::Q5_0));
+    }
+
+    #[test]
+    fn test_bundled_catalog_english_only_matches_urls() {
+        let catalog: ModelCatalog =
+            serde_json::from_str(include_str!("../../resources/model_catalog.json")).unwrap();
+        for model in &catalog.models {
+            for variant in &model.variants {
+                assert_eq!(model.english_only, variant.url.contains(".en-"), "{}", model.id);
+            }
+        }
+    }
+
     #[test]
     fn test_download_progress() {
         let mut progress = DownloadProgress::new("whisper-small".to_string(), Quantization::Q5_1);
         progress.update(50, 100);
         assert_eq!(progress.percent, 50.0);
+        assert!(!progress.indeterminate);
+
+        // Server reported a smaller size than it sent
+        progress.update(150, 100);
+        assert_eq!(progress.percent, 100.0);
+
+        // Unknown length
+        progress.update(4096, 0);
+        assert!(progress.indeterminate);
+        assert_eq!(progress.percent, 0.0);
     }
 }
@@ -0,0 +1,175 @@
+use std::borrow::Cow;
+
+/// Replacement for scrubbed secrets.
+const REDACTED: &str = "[REDACTED]";
+
+/// Query parameter and field names whose values are secrets.
+const SECRET_PARAMS: &[&str] = &[
+    "api_key",
+    "api-key",
+    "apikey",
+    "access_token",
+    "refresh_token",
+    "token",
+    "secret",
+    "client_secret",
+    "password",
+    "key",
+];
+
+/// Minimum length after "sk-" for an OpenAI-style key.
+const MIN_SK_KEY_LEN: usize = 20;
+
+/// Scrub known secret patterns from a log line or error message: bearer
+/// tokens, `api_key=`-style parameters (also as JSON fields) and `sk-` keys.
+/// Bare hex strings such as checksums and recording IDs are kept.
+///
+/// Borrows the input unchanged when there is nothing to redact.
+pub fn redact_secrets(input: &str) -> Cow<'_, str> {
+    let bytes = input.as_bytes();
+    let mut out = String::new();
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let at_boundary = i == 0 || !is_word_byte(bytes[i - 1]);
+        match at_boundary.then(|| secret_at(bytes, i)).flatten() {
+            Some((start, end)) => {
+                out.push_str(&input[copied..start]);
+                out.push_str(REDACTED);
+                copied = end;
+                i = end;
+            }
+            None => i += 1,
+        }
+    }
+
+    if copied == 0 {
+        return Cow::Borrowed(input);
+    }
+    out.push_str(&input[copied..]);
+    Cow::Owned(out)
+}
+
+/// Find a secret starting at `i`, returning the byte range of the part to
+/// replace (the value only, so "Bearer " or "api_key=" stay readable).
+fn secret_at(bytes: &[u8], i: usize) -> Option<(usize, usize)> {
+    let rest = &bytes[i..];
+
+    if starts_with_ignore_case(rest, b"bearer ") {
+        let start = skip_spaces(bytes, i + 7);
+        return non_empty(start, scan(bytes, start, is_value_byte));
+    }
+
+    for name in SECRET_PARAMS {
+        if !starts_with_ignore_case(rest, name.as_bytes()) {
+            continue;
+        }
+        // name=value, name: value, "name":"value"
+        let mut pos = i + name.len();
+        if bytes.get(pos) == Some(&b'"') {
+            pos += 1;
+        }
+        if !matches!(bytes.get(pos), Some(b'=') | Some(b':')) {
+            continue;
+        }
+        pos = skip_spaces(bytes, pos + 1);
+        if bytes.get(pos) == Some(&b'"') {
+            pos += 1;
+        }
+        return non_empty(pos, scan(bytes, pos, is_value_byte));
+    }
+
+    if rest.starts_with(b"sk-") {
+        let end = scan(bytes, i + 3, is_word_byte);
+        return (end - (i + 3) >= MIN_SK_KEY_LEN).then_some((i, end));
+    }
+
+    None
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'-'
+}
+
+/// Bytes that can be part of a secret value (stops at separators and quotes).
+fn is_value_byte(b: u8) -> bool {
+    !b.is_ascii_whitespace() && !matches!(b, b'&' | b'"' | b'\'' | b',' | b';' | b'\\' | b'}')
+}
+
+fn starts_with_ignore_case(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.len() >= needle.len() && haystack[..needle.len()].eq_ignore_ascii_case(needle)
+}
+
+fn skip_spaces(bytes: &[u8], pos: usize) -> usize {
+    scan(bytes, pos, |b| b == b' ')
+}
+
+/// Index of the first byte at or after `pos` not matching `pred`.
+fn scan(bytes: &[u8], pos: usize, pred: impl Fn(u8) -> bool) -> usize {
+    pos + bytes[pos.min(bytes.len())..]
+        .iter()
+        .take_while(|&&b| pred(b))
+        .count()
+}
+
+fn non_empty(start: usize, end: usize) -> Option<(usize, usize)> {
+    (end > start).then_some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_sample_payloads() {
+        assert_eq!(
+            redact_secrets("Authorization: Bearer eyJhbGciOi.x-y_z"),
+            "Authorization: Bearer [REDACTED]"
+        );
+        assert_eq!(
+            redact_secrets("HTTP 401 for https://api.example.com/v1?api_key=abc123&lang=en"),
+            "HTTP 401 for https://api.example.com/v1?api_key=[REDACTED]&lang=en"
+        );
+        // JSON file log line
+        assert_eq!(
+            redact_secrets(r#"{"fields":{"token":"abc","url":"x?ACCESS_TOKEN=def"}}"#),
+            r#"{"fields":{"token":"[REDACTED]","url":"x?ACCESS_TOKEN=[REDACTED]"}}"#
+        );
+        assert_eq!(
+            redact_secrets("key sk-proj-AbCdEfGhIjKlMnOpQrStUv rejected"),
+            "key [REDACTED] rejected"
+        );
+        assert_eq!(
+            redact_secrets("key=0123456789abcdef0123456789ABCDEF leaked"),
+            "key=[REDACTED] leaked"
+        );
+    }
+
+    #[test]
+    fn test_leaves_ordinary_text_alone() {
+        let lines = [
+            "Network request allowed url=https://huggingface.co/model.bin",
+            "monkey=banana tokenizer=bpe",
+            "Loaded model whisper-small (466 MB) in 1.2s",
+            "deadbeef is short hex",
+            "task-0123456789abcdef0123456789abcdef-x is an identifier",
+            "Recording stored id=0123456789abcdef0123456789abcdef",
+            "Bearer  ",
+        ];
+        for line in lines {
+            assert!(matches!(redact_secrets(line), Cow::Borrowed(_)), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_keeps_checksums() {
+        // The only diagnostic for a corrupt model
+        let line = concat!(
+            r#"{"fields":{"message":"Model verification failed","#,
+            r#""expected":"4baf70dd0d7c4247ba2b81fafd9c01005ac77c2f9ef064e00dcf195d0e2fdd2f","#,
+            r#""actual":"e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"}}"#
+        );
+        assert!(matches!(redact_secrets(line), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::model::Quantization;
+use super::transcription::DictationSummary;
+
+/// Aggregate usage statistics.
+///
+/// Only counters are kept; transcribed content is never stored.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageStats {
+    /// Number of completed dictations.
+    pub sessions: u64,
+    /// Total transcribed words.
+    pub total_words: u64,
+    /// Total recorded audio in seconds.
+    pub total_audio_secs: f64,
+    /// Dictation count per language ("unknown" when not detected).
+    pub languages: BTreeMap<String, u64>,
+    /// Dictation count per model.
+    pub models: BTreeMap<String, u64>,
+}
+
+impl UsageStats {
+    /// Add a completed dictation to the totals.
+    pub fn record(&mut self, summary: &DictationSummary) {
+        self.sessions += 1;
+        self.total_words += summary.word_count as u64;
+        self.total_audio_secs += f64::from(summary.recording_secs);
+
+        let language = summary.language.as_deref().unwrap_or("unknown");
+        *self.languages.entry(language.to_string()).or_default() += 1;
+        *self.models.entry(summary.model.clone()).or_default() += 1;
+    }
+}
+
+/// Measured transcription speed of one model variant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    /// Model ID.
+    pub model: String,
+    /// Quantization of the measured variant.
+    pub quant: Quantization,
+    /// Language the benchmark ran with (None for auto-detect).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Transcription time divided by audio duration (below 1 is faster than real time).
+    pub realtime_factor: f32,
+    /// When the measurement was taken, in ms since the Unix epoch.
+    pub measured_at: u64,
+}
+
+/// Benchmark results measured on one hardware profile.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BenchmarkCache {
+    /// `HardwareProfile::fingerprint` the results were measured on.
+    pub fingerprint: String,
+    /// One result per model, quantization and language.
+    pub results: Vec<BenchmarkResult>,
+}
+
+impl BenchmarkCache {
+    /// Drop all results if they were measured on different hardware.
+    pub fn invalidate_unless(&mut self, fingerprint: &str) {
+        if self.fingerprint != fingerprint {
+            self.fingerprint = fingerprint.to_string();
+            self.results.clear();
+        }
+    }
+
+    /// Get the cached result for a model variant and language.
+    pub fn get(
+        &self,
+        model: &str,
+        quant: Quantization,
+        language: Option<&str>,
+    ) -> Option<&BenchmarkResult> {
+        self.results
+            .iter()
+            .find(|r| r.model == model && r.quant == quant && r.language.as_deref() == language)
+    }
+
+    /// Add a result, replacing any earlier one for the same key.
+    pub fn insert(&mut self, result: BenchmarkResult) {
+        self.results.retain(|r| {
+            r.model != result.model || r.quant != result.quant || r.language != result.language
+        });
+        self.results.push(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_stats_record() {
+        let mut stats = UsageStats::default();
+        stats.record(&DictationSummary::new(
+            "hello world",
+            1.5,
+            200,
+            Some("en".to_string()),
+            "whisper-small".to_string(),
+        ));
+        stats.record(&DictationSummary::new(
+            "bonjour",
+            2.0,
+            150,
+            None,
+            "whisper-small".to_string(),
+        ));
+
+        assert_eq!(stats.sessions, 2);
+        assert_eq!(stats.total_words, 3);
+        assert!((stats.total_audio_secs - 3.5).abs() < 0.001);
+        assert_eq!(stats.languages.get("en"), Some(&1));
+        assert_eq!(stats.languages.get("unknown"), Some(&1));
+        assert_eq!(stats.models.get("whisper-small"), Some(&2));
+    }
+
+    #[test]
+    fn test_benchmark_cache() {
+        let result = |quant, language: Option<&str>, rtf| BenchmarkResult {
+            model: "whisper-base".to_string(),
+            quant,
+            language: language.map(String::from),
+            realtime_factor: rtf,
+            measured_at: 0,
+        };
+
+        let mut cache = BenchmarkCache::default();
+        cache.invalidate_unless("x86_64-4c8t");
+        cache.insert(result(Quantization::Q5_1, None, 0.5));
+        cache.insert(result(Quantization::Q5_1, Some("de"), 0.6));
+        cache.insert(result(Quantization::Q5_1, None, 0.4));
+        assert_eq!(cache.results.len(), 2);
+        assert_eq!(
+            cache.get("whisper-base", Quantization::Q5_1, None).map(|r| r.realtime_factor),
+            Some(0.4)
+        );
+        assert!(cache.get("whisper-base", Quantization::Q8_0, None).is_none());
+
+        // Same hardware keeps results, different hardware drops them
+        cache.invalidate_unless("x86_64-4c8t");
+        assert_eq!(cache.results.len(), 2);
+        cache.invalidate_unless("x86_64-8c16t");
+        assert!(cache.results.is_empty());
+        assert_eq!(cache.fingerprint, "x86_64-8c16t");
+    }
+}
@@ -0,0 +1,209 @@
+use super::config::{CaseTransform, OutputConfig};
+
+/// Characters that attach to the preceding word without a space.
+const CLOSING_PUNCTUATION: &[char] = &[
+    '.', ',', ';', ':', '!', '?', ')', ']', '}', '%', '。', '，', '、', '！', '？', '：', '；', '）',
+    '」', '』',
+];
+
+/// Languages written without spaces between words.
+const SPACELESS_LANGUAGES: &[&str] = &["zh", "ja", "yue"];
+
+/// Languages written right-to-left.
+const RTL_LANGUAGES: &[&str] = &["ar", "he", "fa", "ur", "yi", "ps", "sd"];
+
+/// Languages with dotted and dotless i, which case differently.
+const DOTTED_I_LANGUAGES: &[&str] = &["tr", "az"];
+
+/// Unicode RIGHT-TO-LEFT MARK.
+const RLM: char = '\u{200F}';
+
+/// Check whether a language code is written right-to-left.
+pub fn is_rtl_language(language: &str) -> bool {
+    RTL_LANGUAGES.contains(&language)
+}
+
+/// Check for Han, kana and CJK punctuation, which join without spaces.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3000}'..='\u{30FF}' // CJK punctuation, hiragana, katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK unified ideographs
+        | '\u{F900}'..='\u{FAFF}' // CJK compatibility ideographs
+        | '\u{FF00}'..='\u{FFEF}' // Full-width forms
+    )
+}
+
+/// Build the chunk to append after `pending` so consecutive dictations read
+/// as one text: the new text is trimmed and prefixed with a space unless
+/// `pending` is empty, already ends with whitespace, or the new text starts
+/// with closing punctuation.
+///
+/// No space is added for languages written without spaces (Chinese,
+/// Japanese), or between two CJK characters when the language is unknown.
+pub fn append_chunk(pending: &str, text: &str, language: Option<&str>) -> String {
+    let text = text.trim();
+    if text.is_empty() {
+        return String::new();
+    }
+
+    let spaceless = match language {
+        Some(language) => SPACELESS_LANGUAGES.contains(&language),
+        None => {
+            pending.chars().next_back().is_some_and(is_cjk)
+                && text.chars().next().is_some_and(is_cjk)
+        }
+    };
+
+    let needs_space = !spaceless
+        && !pending.is_empty()
+        && !pending.ends_with(char::is_whitespace)
+        && !text.starts_with(CLOSING_PUNCTUATION);
+
+    if needs_space {
+        format!(" {}", text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Prefix a right-to-left chunk with an RLM (after any leading space) so it
+/// keeps its direction when pasted into left-to-right context.
+pub fn with_rtl_mark(chunk: &str) -> String {
+    let text = chunk.trim_start();
+    let space = &chunk[..chunk.len() - text.len()];
+    format!("{}{}{}", space, RLM, text)
+}
+
+/// Change the casing of `text`.
+///
+/// Title case uppercases the first letter of each whitespace-separated word
+/// and leaves the rest alone, so acronyms survive. Scripts without case
+/// (CJK, Arabic, ...) pass through unchanged. Turkish and Azerbaijani map
+/// i/İ and ı/I.
+pub fn transform_case(text: &str, transform: CaseTransform, language: Option<&str>) -> String {
+    let dotted_i = language.is_some_and(|l| DOTTED_I_LANGUAGES.contains(&l));
+    let upper = |c: char, out: &mut String| match c {
+        'i' if dotted_i => out.push('İ'),
+        _ => out.extend(c.to_uppercase()),
+    };
+    let lower = |c: char, out: &mut String| match c {
+        'I' if dotted_i => out.push('ı'),
+        _ => out.extend(c.to_lowercase()),
+    };
+
+    let mut out = String::with_capacity(text.len());
+    match transform {
+        CaseTransform::None => out.push_str(text),
+        CaseTransform::Lower => text.chars().for_each(|c| lower(c, &mut out)),
+        CaseTransform::Upper => text.chars().for_each(|c| upper(c, &mut out)),
+        CaseTransform::Title => {
+            let mut word_start = true;
+            for c in text.chars() {
+                if c.is_whitespace() {
+                    word_start = true;
+                    out.push(c);
+                } else if word_start && c.is_alphanumeric() {
+                    word_start = false;
+                    upper(c, &mut out);
+                } else {
+                    out.push(c);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Apply the configured output transforms to a transcription: casing,
+/// append-mode spacing after `pending`, then the RTL mark.
+///
+/// Pure, so it can preview the result without touching any state.
+pub fn process_output(
+    output: &OutputConfig,
+    pending: &str,
+    text: &str,
+    language: Option<&str>,
+) -> String {
+    let text = transform_case(text, output.case_transform, language);
+    let chunk = if output.append_mode {
+        append_chunk(pending, &text, language)
+    } else {
+        text
+    };
+
+    if output.rtl_marks && language.is_some_and(is_rtl_language) {
+        with_rtl_mark(&chunk)
+    } else {
+        chunk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_chunk_spacing() {
+        assert_eq!(append_chunk("", " Hello world. ", None), "Hello world.");
+        assert_eq!(append_chunk("Hello world.", "How are you?", None), " How are you?");
+        // Pending text already ends with whitespace
+        assert_eq!(append_chunk("Dear team,\n", "thanks", None), "thanks");
+        // Punctuation attaches to the previous word
+        assert_eq!(append_chunk("It works", ", mostly.", None), ", mostly.");
+        assert_eq!(append_chunk("Hello", "   ", None), "");
+        assert_eq!(append_chunk("Bonjour.", "Ça va ?", Some("fr")), " Ça va ?");
+    }
+
+    #[test]
+    fn test_append_chunk_cjk() {
+        assert_eq!(append_chunk("今日は晴れです。", "散歩に行きます。", Some("ja")), "散歩に行きます。");
+        assert_eq!(append_chunk("我们走吧", "，好吗？", Some("zh")), "，好吗？");
+        // Detected from the script when the language is unknown
+        assert_eq!(append_chunk("你好", "世界", None), "世界");
+        // Korean uses spaces between words
+        assert_eq!(append_chunk("안녕하세요.", "반갑습니다.", Some("ko")), " 반갑습니다.");
+    }
+
+    #[test]
+    fn test_with_rtl_mark() {
+        assert!(is_rtl_language("he"));
+        assert!(!is_rtl_language("en"));
+        assert_eq!(with_rtl_mark(" שלום"), " \u{200F}שלום");
+        assert_eq!(with_rtl_mark("مرحبا"), "\u{200F}مرحبا");
+    }
+
+    #[test]
+    fn test_process_output() {
+        let mut output = OutputConfig::default();
+        assert_eq!(process_output(&output, "Hello", " world ", None), " world ");
+
+        output.append_mode = true;
+        output.rtl_marks = true;
+        assert_eq!(process_output(&output, "Hello", " world ", None), " world");
+        assert_eq!(process_output(&output, "שלום", "עולם", Some("he")), " \u{200F}עולם");
+
+        output.case_transform = CaseTransform::Upper;
+        assert_eq!(process_output(&output, "Hello", "world.", None), " WORLD.");
+    }
+
+    #[test]
+    fn test_transform_case() {
+        let text = "hello NASA, (quick) l'été";
+        assert_eq!(transform_case(text, CaseTransform::None, None), text);
+        assert_eq!(transform_case(text, CaseTransform::Lower, None), "hello nasa, (quick) l'été");
+        assert_eq!(transform_case(text, CaseTransform::Upper, None), "HELLO NASA, (QUICK) L'ÉTÉ");
+        assert_eq!(transform_case(text, CaseTransform::Title, None), "Hello NASA, (Quick) L'été");
+
+        // Non-Latin scripts
+        assert_eq!(transform_case("привет мир", CaseTransform::Title, Some("ru")), "Привет Мир");
+        assert_eq!(transform_case("γειά σου", CaseTransform::Upper, Some("el")), "ΓΕΙΆ ΣΟΥ");
+        assert_eq!(transform_case("東京 タワー", CaseTransform::Title, Some("ja")), "東京 タワー");
+        assert_eq!(transform_case("3rd time", CaseTransform::Title, None), "3rd Time");
+
+        // Turkish dotted and dotless i
+        assert_eq!(transform_case("istanbul", CaseTransform::Title, Some("tr")), "İstanbul");
+        assert_eq!(transform_case("IRMAK", CaseTransform::Lower, Some("tr")), "ırmak");
+        assert_eq!(transform_case("istanbul", CaseTransform::Title, Some("en")), "Istanbul");
+    }
+}
@@ -1,7 +1,29 @@
-use zeroize::Zeroize;
+use std::ops::RangeInclusive;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
+
+use super::audio::{resample, MAX_BUFFER_DURATION_SECS};
+use super::DomainError;
+
+/// Sample rate Whisper expects.
+pub const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Longest raw PCM input accepted for transcription, matching the longest
+/// capture buffer.
+pub const MAX_PCM_INPUT_SECS: u32 = MAX_BUFFER_DURATION_SECS;
+
+/// Sample rates accepted for raw PCM input.
+const PCM_SAMPLE_RATES: RangeInclusive<u32> = 8_000..=192_000;
+
+/// Most candidates (`best_of` or `beam_size`) a decode will consider; larger
+/// values are clamped, as they cost time without improving accuracy.
+pub const MAX_SAMPLING_CANDIDATES: u32 = 8;
 
 /// Audio buffer that is securely zeroed on drop.
-/// Audio data never touches disk and is cleared from memory after transcription.
+/// Audio data never touches disk (unless `privacy.store_encrypted_audio` is
+/// opted into) and is cleared from memory after transcription.
 #[derive(Debug, Zeroize)]
 #[zeroize(drop)]
 pub struct AudioBuffer {
@@ -32,6 +54,35 @@ impl AudioBuffer {
         }
     }
 
+    /// Build a 16kHz buffer from mono PCM captured at `sample_rate`,
+    /// e.g. audio recorded by the frontend.
+    ///
+    /// Rejects unsupported sample rates and input longer than
+    /// `MAX_PCM_INPUT_SECS`. Intermediate copies are zeroized.
+    pub fn from_pcm(samples: &[i16], sample_rate: u32) -> Result<Self, DomainError> {
+        if !PCM_SAMPLE_RATES.contains(&sample_rate) {
+            return Err(DomainError::Audio(format!(
+                "Unsupported sample rate {}Hz, expected {}-{}Hz",
+                sample_rate,
+                PCM_SAMPLE_RATES.start(),
+                PCM_SAMPLE_RATES.end()
+            )));
+        }
+        let max_samples = MAX_PCM_INPUT_SECS as usize * sample_rate as usize;
+        if samples.len() > max_samples {
+            return Err(DomainError::Audio(format!(
+                "PCM input too long: {:.1}s, maximum is {}s",
+                samples.len() as f64 / sample_rate as f64,
+                MAX_PCM_INPUT_SECS
+            )));
+        }
+
+        let resampled = Zeroizing::new(resample(samples, sample_rate, WHISPER_SAMPLE_RATE));
+        let mut buffer = Self::with_capacity(WHISPER_SAMPLE_RATE, resampled.len());
+        buffer.push_samples(&resampled);
+        Ok(buffer)
+    }
+
     /// Append samples to the buffer.
     pub fn push_samples(&mut self, samples: &[i16]) {
         self.samples.extend_from_slice(samples);
@@ -42,6 +93,11 @@ impl AudioBuffer {
         &self.samples
     }
 
+    /// Get the samples for in-place processing.
+    pub fn samples_mut(&mut self) -> &mut [i16] {
+        &mut self.samples
+    }
+
     /// Get the sample rate.
     pub fn sample_rate(&self) -> u32 {
         self.sample_rate
@@ -67,6 +123,34 @@ impl AudioBuffer {
         self.samples.len()
     }
 
+    /// Encode as a 16-bit PCM WAV file (RIFF header plus samples).
+    ///
+    /// The result holds the audio in the clear; callers should zeroize it.
+    pub fn to_wav_bytes(&self) -> Vec<u8> {
+        let channels = self.channels.max(1) as u16;
+        let block_align = channels * 2;
+        let data_len = (self.samples.len() * 2) as u32;
+
+        let mut wav = Vec::with_capacity(44 + data_len as usize);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&channels.to_le_bytes());
+        wav.extend_from_slice(&self.sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(self.sample_rate * block_align as u32).to_le_bytes());
+        wav.extend_from_slice(&block_align.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        for sample in &self.samples {
+            wav.extend_from_slice(&sample.to_le_bytes());
+        }
+        wav
+    }
+
     /// Clear the buffer (samples are zeroed due to Zeroize).
     pub fn clear(&mut self) {
         self.samples.zeroize();
@@ -74,6 +158,144 @@ impl AudioBuffer {
     }
 }
 
+/// Summary of a completed dictation, emitted for usage stats.
+/// Contains only counters and metadata, never the transcribed text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DictationSummary {
+    /// Completion time as milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// Recorded audio duration in seconds.
+    pub recording_secs: f32,
+    /// Transcription duration in milliseconds.
+    pub transcription_ms: u64,
+    /// Number of characters in the transcribed text.
+    pub char_count: usize,
+    /// Number of whitespace-separated words in the transcribed text.
+    pub word_count: usize,
+    /// Language used or detected (ISO 639-1 code), None if unknown.
+    pub language: Option<String>,
+    /// Model used for transcription.
+    pub model: String,
+}
+
+impl DictationSummary {
+    /// Build a summary from a transcription, timestamped now.
+    pub fn new(
+        text: &str,
+        recording_secs: f32,
+        transcription_ms: u64,
+        language: Option<String>,
+        model: String,
+    ) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        Self {
+            timestamp_ms,
+            recording_secs,
+            transcription_ms,
+            char_count: text.chars().count(),
+            word_count: text.split_whitespace().count(),
+            language,
+            model,
+        }
+    }
+}
+
+/// Metadata of a recording stored encrypted on disk.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredRecording {
+    /// Identifier used to re-transcribe the recording.
+    pub id: String,
+    /// Recording time as milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// Recorded audio duration in seconds.
+    pub duration_secs: f32,
+    /// Sample rate in Hz.
+    pub sample_rate: u32,
+}
+
+/// How the decoder picks tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SamplingMode {
+    /// Take the likeliest token, keeping the best of `best_of` samples.
+    Greedy { best_of: u32 },
+    /// Beam search over `beam_size` hypotheses: slower, but often more
+    /// accurate on difficult audio.
+    Beam { beam_size: u32 },
+}
+
+impl Default for SamplingMode {
+    fn default() -> Self {
+        SamplingMode::Greedy { best_of: 1 }
+    }
+}
+
+impl SamplingMode {
+    /// Number of candidates considered (`best_of` or `beam_size`).
+    pub fn candidates(&self) -> u32 {
+        match self {
+            SamplingMode::Greedy { best_of } => *best_of,
+            SamplingMode::Beam { beam_size } => *beam_size,
+        }
+    }
+
+    /// This mode with its candidates clamped to `1..=MAX_SAMPLING_CANDIDATES`.
+    pub fn clamped(&self) -> Self {
+        let clamp = |n: u32| n.clamp(1, MAX_SAMPLING_CANDIDATES);
+        match self {
+            SamplingMode::Greedy { best_of } => SamplingMode::Greedy {
+                best_of: clamp(*best_of),
+            },
+            SamplingMode::Beam { beam_size } => SamplingMode::Beam {
+                beam_size: clamp(*beam_size),
+            },
+        }
+    }
+}
+
+/// A segment of a running decode, reported as soon as whisper finalizes it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    /// Position of the segment in the decode (0-based).
+    pub index: u32,
+    /// Segment start within the recording, in ms.
+    pub start_ms: u64,
+    /// Segment end within the recording, in ms.
+    pub end_ms: u64,
+    pub text: String,
+}
+
+/// A transcription as exported to the external JSON Lines sink.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptRecord {
+    /// Completion time as milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    pub text: String,
+    /// Language used or detected (ISO 639-1 code), None if unknown.
+    pub language: Option<String>,
+    /// Recorded audio duration in milliseconds.
+    pub duration_ms: u64,
+    /// Model used for transcription.
+    pub model: String,
+}
+
+impl TranscriptRecord {
+    /// Build a record for `text` from the summary of the same dictation.
+    pub fn new(summary: &DictationSummary, text: &str) -> Self {
+        Self {
+            timestamp: summary.timestamp_ms,
+            text: text.to_string(),
+            language: summary.language.clone(),
+            duration_ms: (summary.recording_secs * 1000.0).round() as u64,
+            model: summary.model.clone(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,4 +323,64 @@ mod tests {
         buffer.push_samples(&vec![0i16; 16000]);
         assert!((buffer.duration_secs() - 1.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_audio_buffer_to_wav_bytes() {
+        let mut buffer = AudioBuffer::new(16000);
+        buffer.push_samples(&[1, -1, i16::MAX]);
+        let wav = buffer.to_wav_bytes();
+
+        assert_eq!(wav.len(), 44 + 6);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[4..8].try_into().unwrap()), 36 + 6);
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        // Mono, 16kHz, 32000 bytes/s, 16-bit
+        assert_eq!(u16::from_le_bytes([wav[22], wav[23]]), 1);
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16000);
+        assert_eq!(u32::from_le_bytes(wav[28..32].try_into().unwrap()), 32000);
+        assert_eq!(u16::from_le_bytes([wav[34], wav[35]]), 16);
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 6);
+        assert_eq!(&wav[44..], &[0x01, 0x00, 0xff, 0xff, 0xff, 0x7f]);
+    }
+
+    #[test]
+    fn test_audio_buffer_from_pcm() {
+        // Already 16kHz: kept as is
+        let buffer = AudioBuffer::from_pcm(&[1, 2, 3], 16000).unwrap();
+        assert_eq!(buffer.samples(), &[1, 2, 3]);
+
+        // 1 second at 48kHz becomes 1 second at 16kHz
+        let buffer = AudioBuffer::from_pcm(&vec![0i16; 48000], 48000).unwrap();
+        assert_eq!(buffer.sample_rate(), WHISPER_SAMPLE_RATE);
+        assert_eq!(buffer.len(), 16000);
+
+        assert!(AudioBuffer::from_pcm(&[0], 0).is_err());
+        assert!(AudioBuffer::from_pcm(&[0], 1_000_000).is_err());
+
+        let too_long = vec![0i16; (MAX_PCM_INPUT_SECS as usize + 1) * 8000];
+        assert!(matches!(
+            AudioBuffer::from_pcm(&too_long, 8000),
+            Err(DomainError::Audio(_))
+        ));
+    }
+
+    #[test]
+    fn test_dictation_summary_counts() {
+        let summary = DictationSummary::new(
+            "Héllo  world,\nthis is OpenSay.",
+            2.5,
+            340,
+            Some("en".to_string()),
+            "whisper-small".to_string(),
+        );
+        assert_eq!(summary.char_count, 30);
+        assert_eq!(summary.word_count, 5);
+        assert_eq!(summary.transcription_ms, 340);
+        assert!(summary.timestamp_ms > 0);
+
+        let empty = DictationSummary::new("", 0.0, 0, None, "whisper-small".to_string());
+        assert_eq!(empty.char_count, 0);
+        assert_eq!(empty.word_count, 0);
+    }
 }
@@ -1,24 +1,149 @@
 use std::fs;
-use std::path::Path;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
-use tracing_appender::non_blocking::WorkerGuard;
+use parking_lot::{Condvar, Mutex};
+use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{EnvFilter, Layer};
 
-use crate::domain::DomainError;
+use crate::domain::{redact_secrets, DomainError};
+
+/// How long `LogHandle::flush` waits for the worker to catch up.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Sent through the worker queue by `LogHandle::flush` and never written to
+/// the file. Log lines are JSON, which can't contain a raw NUL.
+const FLUSH_MARKER: &[u8] = b"\0opensay-flush\0";
+
+/// `MakeWriter` for the log file, redacting each line.
+#[derive(Clone)]
+struct FileWriter(NonBlocking);
+
+impl<'a> MakeWriter<'a> for FileWriter {
+    type Writer = RedactingWriter<NonBlocking>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter(self.0.clone())
+    }
+}
+
+/// Number of flush markers the worker has reached.
+#[derive(Default)]
+struct FlushSignal {
+    reached: Mutex<u64>,
+    changed: Condvar,
+}
+
+/// The file appender as seen by the logging worker: writes pass through,
+/// flush markers are counted instead.
+struct MarkedAppender {
+    appender: RollingFileAppender,
+    signal: Arc<FlushSignal>,
+}
+
+impl Write for MarkedAppender {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf != FLUSH_MARKER {
+            return self.appender.write(buf);
+        }
+        // Everything queued before the marker has been written
+        self.appender.flush()?;
+        *self.signal.reached.lock() += 1;
+        self.signal.changed.notify_all();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.appender.flush()
+    }
+}
+
+/// Writer that scrubs secrets (see `redact_secrets`) from each log line
+/// before it reaches the file.
+struct RedactingWriter<W>(W);
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // The fmt layer writes each event in a single call
+        match std::str::from_utf8(buf) {
+            Ok(line) => self.0.write_all(redact_secrets(line).as_bytes())?,
+            Err(_) => self.0.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Handle to the file logging worker.
+///
+/// Must be kept alive for the duration of the application.
+/// When dropped, any remaining logs are flushed.
+pub struct LogHandle {
+    writer: NonBlocking,
+    signal: Arc<FlushSignal>,
+    _guard: WorkerGuard,
+}
+
+impl LogHandle {
+    /// Create a file logging worker writing to `logs_dir`.
+    fn new(logs_dir: &Path) -> (Self, FileWriter) {
+        let signal = Arc::new(FlushSignal::default());
+        let appender = MarkedAppender {
+            appender: file_appender(logs_dir),
+            signal: Arc::clone(&signal),
+        };
+        let (writer, guard) = tracing_appender::non_blocking(appender);
+
+        let handle = Self {
+            writer: writer.clone(),
+            signal,
+            _guard: guard,
+        };
+        (handle, FileWriter(writer))
+    }
+
+    /// Force buffered log lines to be written to disk.
+    ///
+    /// Queues a marker behind them and waits (up to `FLUSH_TIMEOUT`) for the
+    /// worker to reach it. Fails if it doesn't, e.g. because a full queue
+    /// dropped the marker.
+    pub fn flush(&self) -> Result<(), DomainError> {
+        let mut reached = self.signal.reached.lock();
+        let target = *reached + 1;
+        self.writer.make_writer().write_all(FLUSH_MARKER)?;
+        while *reached < target {
+            if self.signal.changed.wait_for(&mut reached, FLUSH_TIMEOUT).timed_out() {
+                return Err(DomainError::Io("Timed out flushing the log file".to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// File appender with daily rotation.
+fn file_appender(logs_dir: &Path) -> RollingFileAppender {
+    RollingFileAppender::new(Rotation::DAILY, logs_dir, "opensay.log")
+}
 
 /// Initialize the logging system with console output and file rotation.
 ///
-/// Returns a guard that must be kept alive for the duration of the application.
-/// When the guard is dropped, any remaining logs are flushed.
+/// Returns a handle that must be kept alive for the duration of the application.
+/// When the handle is dropped, any remaining logs are flushed.
 pub fn init_logging(
     logs_dir: &Path,
     level: &str,
     file_logging: bool,
-) -> Result<Option<WorkerGuard>, DomainError> {
+) -> Result<Option<LogHandle>, DomainError> {
     // Ensure logs directory exists
     if file_logging {
         fs::create_dir_all(logs_dir)?;
@@ -38,19 +163,12 @@ pub fn init_logging(
         .with_filter(env_filter.clone());
 
     if file_logging {
-        // File appender with daily rotation
-        let file_appender = RollingFileAppender::new(
-            Rotation::DAILY,
-            logs_dir,
-            "opensay.log",
-        );
-
-        // Non-blocking writer for the file appender
-        let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+        // Non-blocking writer for the daily rotating file appender
+        let (handle, writer) = LogHandle::new(logs_dir);
 
         // File layer with JSON format
         let file_layer = tracing_subscriber::fmt::layer()
-            .with_writer(non_blocking)
+            .with_writer(writer)
             .with_ansi(false)
             .json()
             .with_span_events(FmtSpan::CLOSE)
@@ -70,7 +188,7 @@ pub fn init_logging(
             );
         }
 
-        Ok(Some(guard))
+        Ok(Some(handle))
     } else {
         // Console only - use try_init to avoid panic if called twice
         let _ = tracing_subscriber::registry()
@@ -101,4 +219,41 @@ mod tests {
 
         let _ = fs::remove_dir_all(&temp_dir);
     }
+
+    #[test]
+    fn test_log_handle_flush() {
+        let temp_dir = env::temp_dir().join("opensay_log_flush_test");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let (handle, writer) = LogHandle::new(&temp_dir);
+        writer.make_writer().write_all(b"flushed line\n").unwrap();
+        handle.flush().unwrap();
+
+        let contents: String = fs::read_dir(&temp_dir)
+            .unwrap()
+            .filter_map(|entry| fs::read_to_string(entry.ok()?.path()).ok())
+            .collect();
+        assert!(contents.contains("flushed line"));
+
+        // The worker keeps running, and the marker never reaches the file
+        writer.make_writer().write_all(b"second line\n").unwrap();
+        handle.flush().unwrap();
+        let contents: String = fs::read_dir(&temp_dir)
+            .unwrap()
+            .filter_map(|entry| fs::read_to_string(entry.ok()?.path()).ok())
+            .collect();
+        assert!(contents.contains("second line"));
+        assert!(!contents.contains("opensay-flush"));
+        drop(handle);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_redacting_writer() {
+        let mut redacting = RedactingWriter(Vec::new());
+        redacting.write_all(b"url=https://x.test/?api_key=abc123\n").unwrap();
+        assert_eq!(redacting.0, b"url=https://x.test/?api_key=[REDACTED]\n");
+    }
 }
@@ -1,3 +1,7 @@
 pub mod logging;
+pub mod notifications;
+pub mod shortcut;
 
-pub use logging::init_logging;
+pub use logging::{init_logging, LogHandle};
+pub use notifications::spawn_device_alerts;
+pub use shortcut::{parse_shortcut, ShortcutAvailability, ShortcutRegistry, ShortcutStatus};
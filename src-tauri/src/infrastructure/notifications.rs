@@ -0,0 +1,122 @@
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::app::AppController;
+use crate::domain::AudioEvent;
+
+/// Minimum time between two device alerts, so a flapping device doesn't spam.
+const MIN_ALERT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Drops alerts that come sooner than `min_interval` after the last one shown.
+#[derive(Debug)]
+pub struct AlertRateLimiter {
+    min_interval: Duration,
+    last_shown: Option<Instant>,
+}
+
+impl AlertRateLimiter {
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_shown: None,
+        }
+    }
+
+    /// Check whether an alert may be shown at `now`, recording it if so.
+    pub fn allow(&mut self, now: Instant) -> bool {
+        if self
+            .last_shown
+            .is_some_and(|last| now.duration_since(last) < self.min_interval)
+        {
+            return false;
+        }
+        self.last_shown = Some(now);
+        true
+    }
+}
+
+/// Notification title and body for audio events the user should know about.
+pub fn device_alert(event: &AudioEvent) -> Option<(String, String)> {
+    match event {
+        AudioEvent::DeviceLost { device_name } => Some((
+            "Microphone disconnected".to_string(),
+            format!("{} was lost during recording.", device_name),
+        )),
+        AudioEvent::RecoveryFailed { attempts, .. } => Some((
+            "Microphone unavailable".to_string(),
+            format!("Could not reconnect after {} attempts.", attempts),
+        )),
+        _ => None,
+    }
+}
+
+/// Show OS notifications for device loss and failed recovery while
+/// `ui.notify_on_device_loss` is on.
+///
+/// The setting is read per event, so toggling it takes effect immediately.
+pub fn spawn_device_alerts<R: Runtime>(app: &AppHandle<R>) {
+    let handle = app.clone();
+    let mut events = app.state::<AppController>().subscribe_audio_events();
+    tauri::async_runtime::spawn(async move {
+        let mut limiter = AlertRateLimiter::new(MIN_ALERT_INTERVAL);
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            };
+            let Some((title, body)) = device_alert(&event) else {
+                continue;
+            };
+            if !handle.state::<AppController>().config().ui.notify_on_device_loss {
+                continue;
+            }
+            if !limiter.allow(Instant::now()) {
+                tracing::debug!("Device alert suppressed by rate limit");
+                continue;
+            }
+
+            if let Err(e) = handle.notification().builder().title(title).body(body).show() {
+                tracing::warn!("Failed to show device alert: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let mut limiter = AlertRateLimiter::new(Duration::from_secs(30));
+        let start = Instant::now();
+        assert!(limiter.allow(start));
+        assert!(!limiter.allow(start + Duration::from_secs(5)));
+        assert!(!limiter.allow(start + Duration::from_secs(29)));
+        assert!(limiter.allow(start + Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_device_alert_events() {
+        let lost = AudioEvent::DeviceLost {
+            device_name: "USB Mic".to_string(),
+        };
+        let (_, body) = device_alert(&lost).unwrap();
+        assert!(body.contains("USB Mic"));
+
+        let failed = AudioEvent::RecoveryFailed {
+            attempts: 3,
+            last_error: "gone".to_string(),
+        };
+        assert!(device_alert(&failed).is_some());
+        assert!(device_alert(&AudioEvent::LevelUpdate {
+            level: 0.5,
+            peak: 0.9,
+            level_db: -3.0,
+        }).is_none());
+    }
+}
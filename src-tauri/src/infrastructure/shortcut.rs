@@ -0,0 +1,347 @@
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime};
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
+
+use crate::domain::DomainError;
+
+/// Registration state of the global toggle shortcut, as shown in settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ShortcutStatus {
+    /// Human-readable combo, e.g. "Alt+Space".
+    pub shortcut: String,
+    /// Modifier names in display order.
+    pub modifiers: Vec<String>,
+    /// Main key name.
+    pub key: String,
+    /// Whether the OS accepted the registration.
+    pub registered: bool,
+    /// Registration error, if it failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ShortcutStatus {
+    /// Status for a shortcut that was registered successfully.
+    pub fn registered(shortcut: &Shortcut) -> Self {
+        Self::new(shortcut, None)
+    }
+
+    /// Status for a shortcut whose registration failed.
+    pub fn failed(shortcut: &Shortcut, error: impl Into<String>) -> Self {
+        Self::new(shortcut, Some(error.into()))
+    }
+
+    fn new(shortcut: &Shortcut, error: Option<String>) -> Self {
+        let modifiers = modifier_names(shortcut.mods);
+        let key = key_name(shortcut.key);
+        let shortcut = modifiers
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(key.as_str()))
+            .collect::<Vec<_>>()
+            .join("+");
+
+        Self {
+            shortcut,
+            modifiers,
+            key,
+            registered: error.is_none(),
+            error,
+        }
+    }
+}
+
+/// Whether a combo could be used as the toggle shortcut.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ShortcutAvailability {
+    /// The combo as given, or its display form once parsed.
+    pub shortcut: String,
+    pub available: bool,
+    /// Why it isn't available: a parse error or the registration conflict.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ShortcutAvailability {
+    fn unavailable(shortcut: String, error: impl Into<String>) -> Self {
+        Self {
+            shortcut,
+            available: false,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Owns the toggle shortcut registration and its latest status.
+#[derive(Debug, Default)]
+pub struct ShortcutRegistry {
+    /// Last shortcut the OS accepted, used as the fallback on conflict.
+    active: Mutex<Option<Shortcut>>,
+    status: Mutex<Option<ShortcutStatus>>,
+}
+
+impl ShortcutRegistry {
+    /// Get the current status, or `None` if no registration was attempted.
+    pub fn status(&self) -> Option<ShortcutStatus> {
+        self.status.lock().clone()
+    }
+
+    /// Register `shortcut` as the toggle shortcut, replacing the active one.
+    ///
+    /// If registration fails, a `"shortcut-failed"` event is emitted, the
+    /// previous working shortcut is re-registered, and `ShortcutConflict` is
+    /// returned.
+    pub fn register<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        shortcut: Shortcut,
+    ) -> Result<ShortcutStatus, DomainError> {
+        let global = app.global_shortcut();
+        let mut active = self.active.lock();
+        let previous = *active;
+
+        if previous == Some(shortcut) && global.is_registered(shortcut) {
+            return Ok(ShortcutStatus::registered(&shortcut));
+        }
+        if let Some(previous) = previous {
+            let _ = global.unregister(previous);
+        }
+
+        match global.register(shortcut) {
+            Ok(()) => {
+                *active = Some(shortcut);
+                let status = ShortcutStatus::registered(&shortcut);
+                tracing::info!("Global shortcut {} registered", status.shortcut);
+                self.publish(app, status.clone());
+                Ok(status)
+            }
+            Err(e) => {
+                let error = DomainError::ShortcutConflict {
+                    shortcut: ShortcutStatus::registered(&shortcut).shortcut,
+                    reason: e.to_string(),
+                };
+                tracing::warn!("{}", error);
+                self.publish(app, ShortcutStatus::failed(&shortcut, error.to_string()));
+
+                *active = None;
+                if let Some(previous) = previous {
+                    match global.register(previous) {
+                        Ok(()) => {
+                            *active = Some(previous);
+                            let status = ShortcutStatus::registered(&previous);
+                            tracing::info!("Restored previous shortcut {}", status.shortcut);
+                            self.publish(app, status);
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to restore previous shortcut: {}", e);
+                        }
+                    }
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Check whether `combo` could be registered, by registering it and
+    /// immediately unregistering it. The active shortcut is untouched.
+    pub fn probe<R: Runtime>(&self, app: &AppHandle<R>, combo: &str) -> ShortcutAvailability {
+        let shortcut = match parse_shortcut(combo) {
+            Ok(shortcut) => shortcut,
+            Err(e) => return ShortcutAvailability::unavailable(combo.to_string(), e.to_string()),
+        };
+        let name = ShortcutStatus::registered(&shortcut).shortcut;
+
+        // Hold the lock so a concurrent `register` can't interleave
+        let active = self.active.lock();
+        let global = app.global_shortcut();
+        if *active == Some(shortcut) {
+            return ShortcutAvailability {
+                shortcut: name,
+                available: true,
+                error: None,
+            };
+        }
+        if global.is_registered(shortcut) {
+            return ShortcutAvailability::unavailable(name, "Already registered by this app");
+        }
+
+        match global.register(shortcut) {
+            Ok(()) => {
+                if let Err(e) = global.unregister(shortcut) {
+                    tracing::warn!("Failed to unregister probed shortcut {}: {}", name, e);
+                }
+                ShortcutAvailability {
+                    shortcut: name,
+                    available: true,
+                    error: None,
+                }
+            }
+            Err(e) => {
+                // A failed registration may still leave a partial one behind
+                if global.is_registered(shortcut) {
+                    let _ = global.unregister(shortcut);
+                }
+                tracing::debug!("Shortcut {} unavailable: {}", name, e);
+                ShortcutAvailability::unavailable(name, e.to_string())
+            }
+        }
+    }
+
+    /// Record `status` and notify the frontend. The event can fire before the
+    /// frontend listens (at setup), so it also reads `status` on load via
+    /// the `get_active_shortcut` command.
+    fn publish<R: Runtime>(&self, app: &AppHandle<R>, status: ShortcutStatus) {
+        let event = if status.registered {
+            "shortcut-registered"
+        } else {
+            "shortcut-failed"
+        };
+        if let Err(e) = app.emit(event, &status) {
+            tracing::error!("Failed to emit {} event: {}", event, e);
+        }
+        *self.status.lock() = Some(status);
+    }
+}
+
+/// Parse a shortcut combo such as "Ctrl+Shift+Space", "CmdOrCtrl+Alt+R" or
+/// "F9": any number of modifiers followed by exactly one key, separated by
+/// "+" and matched case-insensitively.
+///
+/// Modifiers accept the usual aliases (Cmd/Command/Super/Meta/Win,
+/// Option/Alt, Control/Ctrl); CmdOrCtrl is Cmd on macOS and Ctrl elsewhere.
+pub fn parse_shortcut(combo: &str) -> Result<Shortcut, DomainError> {
+    let invalid =
+        |reason: String| DomainError::Config(format!("Invalid shortcut '{}': {}", combo, reason));
+
+    let tokens: Vec<&str> = combo.split('+').map(str::trim).collect();
+    let Some((&key, modifiers)) = tokens.split_last().filter(|_| !combo.trim().is_empty()) else {
+        return Err(invalid("empty".to_string()));
+    };
+
+    let mut mods = Modifiers::empty();
+    for &token in modifiers {
+        if token.is_empty() {
+            return Err(invalid("empty part between '+'".to_string()));
+        }
+        mods |= parse_modifier(token)
+            .ok_or_else(|| invalid(format!("unknown modifier '{}'", token)))?;
+    }
+
+    if key.is_empty() || parse_modifier(key).is_some() {
+        return Err(invalid("no key after the modifiers".to_string()));
+    }
+    // A lone key goes through the plugin's parser, which knows the key names
+    let code = key
+        .parse::<Shortcut>()
+        .map_err(|_| invalid(format!("unknown key '{}'", key)))?
+        .key;
+
+    Ok(Shortcut::new(Some(mods), code))
+}
+
+/// Modifier for a combo token, or `None` if it isn't one.
+fn parse_modifier(token: &str) -> Option<Modifiers> {
+    let modifier = match token.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Modifiers::CONTROL,
+        "alt" | "option" => Modifiers::ALT,
+        "shift" => Modifiers::SHIFT,
+        "cmd" | "command" | "super" | "meta" | "win" => Modifiers::SUPER,
+        "cmdorctrl" | "cmdorcontrol" | "commandorctrl" | "commandorcontrol" => {
+            if cfg!(target_os = "macos") {
+                Modifiers::SUPER
+            } else {
+                Modifiers::CONTROL
+            }
+        }
+        _ => return None,
+    };
+    Some(modifier)
+}
+
+/// Modifier names in the conventional Ctrl, Alt, Shift, Super order.
+fn modifier_names(mods: Modifiers) -> Vec<String> {
+    [
+        (Modifiers::CONTROL, "Ctrl"),
+        (Modifiers::ALT, "Alt"),
+        (Modifiers::SHIFT, "Shift"),
+        (Modifiers::SUPER, "Super"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| mods.contains(*flag))
+    .map(|(_, name)| name.to_string())
+    .collect()
+}
+
+/// Display name for a key code ("KeyR" -> "R", "Digit1" -> "1").
+fn key_name(code: Code) -> String {
+    let raw = code.to_string();
+    for prefix in ["Key", "Digit"] {
+        if let Some(rest) = raw.strip_prefix(prefix) {
+            if rest.len() == 1 {
+                return rest.to_string();
+            }
+        }
+    }
+    raw
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_display_string() {
+        let shortcut = Shortcut::new(Some(Modifiers::SHIFT | Modifiers::CONTROL), Code::KeyR);
+        let status = ShortcutStatus::registered(&shortcut);
+        assert_eq!(status.shortcut, "Ctrl+Shift+R");
+        assert_eq!(status.modifiers, vec!["Ctrl", "Shift"]);
+        assert_eq!(status.key, "R");
+        assert!(status.registered);
+
+        let shortcut = Shortcut::new(Some(Modifiers::ALT), Code::Space);
+        let status = ShortcutStatus::failed(&shortcut, "already in use");
+        assert_eq!(status.shortcut, "Alt+Space");
+        assert!(!status.registered);
+        assert_eq!(status.error.as_deref(), Some("already in use"));
+
+        assert_eq!(key_name(Code::Digit1), "1");
+        assert_eq!(key_name(Code::Enter), "Enter");
+    }
+
+    #[test]
+    fn test_parse_shortcut() {
+        let shortcut = parse_shortcut("Ctrl+Shift+R").unwrap();
+        assert_eq!(shortcut, Shortcut::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyR));
+
+        let err = parse_shortcut("Ctrl+Nope").unwrap_err();
+        assert!(matches!(err, DomainError::Config(_)));
+    }
+
+    #[test]
+    fn test_parse_shortcut_formats_and_aliases() {
+        let cmd_or_ctrl = if cfg!(target_os = "macos") {
+            Modifiers::SUPER
+        } else {
+            Modifiers::CONTROL
+        };
+        let valid = [
+            ("Alt+Space", Modifiers::ALT, Code::Space),
+            ("ctrl + shift + space", Modifiers::CONTROL | Modifiers::SHIFT, Code::Space),
+            ("CmdOrCtrl+Alt+R", cmd_or_ctrl | Modifiers::ALT, Code::KeyR),
+            ("F9", Modifiers::empty(), Code::F9),
+            ("Meta+1", Modifiers::SUPER, Code::Digit1),
+            ("Cmd+Super+K", Modifiers::SUPER, Code::KeyK),
+            ("Option+Control+Enter", Modifiers::ALT | Modifiers::CONTROL, Code::Enter),
+        ];
+        for (combo, mods, code) in valid {
+            let expected = Shortcut::new(Some(mods), code);
+            assert_eq!(parse_shortcut(combo).unwrap(), expected, "{}", combo);
+        }
+
+        let invalid = ["", "  ", "+", "Ctrl+", "Ctrl+Shift", "Alt", "Ctrl++R", "Hyper+R", "R+Ctrl"];
+        for combo in invalid {
+            assert!(parse_shortcut(combo).is_err(), "{}", combo);
+        }
+    }
+}
@@ -10,21 +10,42 @@ mod ports;
 use app::AppController;
 use commands::{
     // Config commands
-    get_config, update_config, is_network_blocked, get_paths,
+    get_config, update_config, is_network_blocked, get_paths, get_vad_defaults, flush_logs,
+    validate_language,
+    // Usage stats commands
+    get_usage_stats, reset_usage_stats,
+    // Telemetry commands
+    start_telemetry, stop_telemetry,
     // Audio commands
-    get_audio_config, get_audio_level, get_audio_state, get_recording_duration,
-    list_audio_devices, recover_audio, select_audio_device, start_recording, stop_recording,
-    toggle_recording,
+    get_audio_config, get_audio_level, get_audio_level_db, get_audio_peak, get_audio_state,
+    get_recording_duration, list_audio_devices, recover_audio, select_audio_device, set_input_gain,
+    set_buffer_duration, start_recording, stop_recording, measure_capture_rate, cancel_recovery,
+    toggle_recording, confirm_inject, set_muted, is_muted, set_next_output,
     // Transcription commands
     transcribe, load_model, load_model_by_id, is_model_loaded, unload_model,
+    retranscribe_last, transcribe_pcm, cancel_transcription, list_stored_recordings,
+    delete_stored_recording, retranscribe_stored, retranscribe_stored_batch, cancel_batch,
+    has_last_recording, clear_last_recording,
+    copy_last_transcription, validate_api_key, export_last_recording,
+    check_backend_available, get_pending_transcript, finalize_transcript, preview_text_processing,
+    get_skip_silent_buffers, set_skip_silent_buffers, get_effective_transcribe_config,
+    get_live_segments, set_live_segments,
     // Model management commands
-    get_model_catalog, list_installed_models, is_model_installed, download_model,
-    delete_model, get_models_dir,
+    get_model_catalog, get_models_unavailable_reason, list_installed_models, is_model_installed,
+    download_model, delete_model, get_models_dir, list_models_dir_contents, delete_models_dir_file,
+    get_catalog_json, export_catalog, setup_recommended_model, acknowledge_onboarding,
+    setup_default_model, get_download_queue, reorder_download, remove_from_queue,
+    suggest_model_for_language,
     // Hardware commands
-    get_hardware_profile, get_recommended_model,
+    get_hardware_profile, get_recommended_model, get_diagnostics, set_hardware_override,
+    clear_hardware_override, get_audio_pipeline_info, benchmark_models,
+    // Shortcut commands
+    get_active_shortcut, try_register_shortcut, is_shortcut_available,
 };
-use tauri::Emitter;
-use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
+use infrastructure::{parse_shortcut, spawn_device_alerts, ShortcutRegistry};
+use tauri::{Emitter, Manager};
+use tokio::sync::broadcast::error::RecvError;
+use tauri_plugin_global_shortcut::{Code, Modifiers, Shortcut, ShortcutState};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -39,6 +60,7 @@ pub fn run() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(
             tauri_plugin_global_shortcut::Builder::new()
                 .with_handler(|app, shortcut, event| {
@@ -52,15 +74,72 @@ pub fn run() {
                 .build(),
         )
         .manage(controller)
+        .manage(ShortcutRegistry::default())
         .setup(|app| {
-            // Register Alt+Space global shortcut
-            // NOTE: Shortcut is hardcoded; config.shortcut.toggle_shortcut is not parsed yet.
-            // Parsing arbitrary shortcut strings requires a custom parser (future work).
-            let shortcut = Shortcut::new(Some(Modifiers::ALT), Code::Space);
-            if let Err(e) = app.global_shortcut().register(shortcut) {
-                tracing::warn!("Failed to register global shortcut: {}", e);
-            } else {
-                tracing::info!("Global shortcut Alt+Space registered");
+            // Register the configured toggle shortcut, falling back to Alt+Space
+            // Failures are reported to the frontend as a "shortcut-failed" event
+            let combo = app.state::<AppController>().config().shortcut.toggle_shortcut;
+            let shortcut = parse_shortcut(&combo).unwrap_or_else(|e| {
+                tracing::warn!("{}; falling back to Alt+Space", e);
+                Shortcut::new(Some(Modifiers::ALT), Code::Space)
+            });
+            let _ = app.state::<ShortcutRegistry>().register(app.handle(), shortcut);
+
+            // Forward controller events to the frontend
+            let handle = app.handle().clone();
+            let mut events = app.state::<AppController>().subscribe_events();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    match events.recv().await {
+                        Ok(event) => {
+                            if let Err(e) = handle.emit(event.name(), &event) {
+                                tracing::error!("Failed to emit {} event: {}", event.name(), e);
+                            }
+                        }
+                        Err(RecvError::Lagged(skipped)) => {
+                            tracing::warn!(skipped, "Event bridge lagged, events dropped");
+                        }
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+            });
+
+            // Alert the user about a lost microphone, even when the app is in the background
+            spawn_device_alerts(app.handle());
+
+            // Recover from an unplugged microphone without user action, if enabled
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                handle.state::<AppController>().watch_audio_device().await;
+            });
+
+            // Tell the UI when a recording outgrows the audio buffer
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                handle.state::<AppController>().forward_audio_events().await;
+            });
+
+            // Tell the UI to prompt a download when there is no usable model
+            app.state::<AppController>().check_models_available();
+
+            // Finish an interrupted first-run model setup (no-op before onboarding)
+            if !app.state::<AppController>().config().ui.default_model_setup_done {
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let bundled_dir = commands::bundled_models_dir(&handle);
+                    let controller = handle.state::<AppController>();
+                    if let Err(e) = controller.ensure_default_model(bundled_dir.as_deref()).await {
+                        tracing::warn!(error = %e, "First-run model setup failed");
+                    }
+                });
+            }
+
+            // Warm up the model in the background so startup isn't blocked
+            if app.state::<AppController>().config().transcription.preload_model_on_startup {
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    handle.state::<AppController>().preload_model().await;
+                });
             }
             Ok(())
         })
@@ -70,34 +149,109 @@ pub fn run() {
             update_config,
             is_network_blocked,
             get_paths,
+            get_vad_defaults,
+            flush_logs,
+            validate_language,
+            // Usage stats commands
+            get_usage_stats,
+            reset_usage_stats,
+            // Telemetry commands
+            start_telemetry,
+            stop_telemetry,
             // Audio commands
             start_recording,
             stop_recording,
             toggle_recording,
+            confirm_inject,
             get_audio_state,
             get_audio_config,
             list_audio_devices,
             select_audio_device,
             get_recording_duration,
             get_audio_level,
+            get_audio_level_db,
+            get_audio_peak,
+            set_input_gain,
+            set_buffer_duration,
             recover_audio,
+            cancel_recovery,
+            measure_capture_rate,
+            set_muted,
+            is_muted,
+            set_next_output,
             // Transcription commands
             transcribe,
             load_model,
             load_model_by_id,
             is_model_loaded,
             unload_model,
+            retranscribe_last,
+            export_last_recording,
+            transcribe_pcm,
+            cancel_transcription,
+            list_stored_recordings,
+            delete_stored_recording,
+            retranscribe_stored,
+            retranscribe_stored_batch,
+            cancel_batch,
+            has_last_recording,
+            clear_last_recording,
+            copy_last_transcription,
+            check_backend_available,
+            validate_api_key,
+            get_pending_transcript,
+            finalize_transcript,
+            preview_text_processing,
+            get_effective_transcribe_config,
+            get_skip_silent_buffers,
+            set_skip_silent_buffers,
+            get_live_segments,
+            set_live_segments,
             // Model management commands
             get_model_catalog,
+            get_models_unavailable_reason,
+            suggest_model_for_language,
             list_installed_models,
             is_model_installed,
             download_model,
             delete_model,
             get_models_dir,
+            list_models_dir_contents,
+            delete_models_dir_file,
+            get_catalog_json,
+            export_catalog,
+            setup_recommended_model,
+            acknowledge_onboarding,
+            setup_default_model,
+            get_download_queue,
+            reorder_download,
+            remove_from_queue,
             // Hardware commands
             get_hardware_profile,
             get_recommended_model,
+            set_hardware_override,
+            clear_hardware_override,
+            benchmark_models,
+            get_diagnostics,
+            get_audio_pipeline_info,
+            // Shortcut commands
+            get_active_shortcut,
+            try_register_shortcut,
+            is_shortcut_available,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            tauri::RunEvent::ExitRequested { .. } => {
+                // Refuse shortcut presses while the app tears down
+                app.state::<AppController>().begin_shutdown();
+            }
+            tauri::RunEvent::Exit => {
+                let controller = app.state::<AppController>();
+                controller.begin_shutdown();
+                // Zeroize any retained recording before the process exits
+                controller.clear_last_recording();
+            }
+            _ => {}
+        });
 }
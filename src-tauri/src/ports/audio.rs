@@ -1,7 +1,12 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 use tokio::sync::broadcast;
 
-use crate::domain::{AudioBuffer, AudioConfig, AudioDevice, AudioEvent, AudioState, DomainError};
+use crate::domain::{
+    rms_to_dbfs, AudioBuffer, AudioConfig, AudioDevice, AudioEvent, AudioState,
+    CaptureRateMeasurement, DomainError, StreamInfo,
+};
 
 /// Port for audio capture operations.
 ///
@@ -26,12 +31,21 @@ pub trait AudioManager: Send + Sync {
     /// Get the audio configuration.
     fn config(&self) -> AudioConfig;
 
+    /// Apply an updated audio configuration (takes effect on the next recording).
+    fn update_config(&self, config: AudioConfig);
+
     /// List available audio input devices.
     fn list_input_devices(&self) -> Result<Vec<AudioDevice>, DomainError>;
 
     /// Select an input device by ID, or use the system default if None.
     fn select_input_device(&self, device_id: Option<&str>) -> Result<(), DomainError>;
 
+    /// Get the selected input device ID, or None for the system default.
+    fn selected_input_device(&self) -> Option<String>;
+
+    /// Describe the open input stream (recording or kept warm), if any.
+    fn stream_info(&self) -> Option<StreamInfo>;
+
     /// Subscribe to audio events.
     fn subscribe(&self) -> broadcast::Receiver<AudioEvent>;
 
@@ -40,6 +54,16 @@ pub trait AudioManager: Send + Sync {
     /// This is only valid when in the Error state.
     async fn recover(&self) -> Result<(), DomainError>;
 
+    /// Stop a running `recover` early instead of waiting out its backoff.
+    ///
+    /// The recovery ends in `Idle` if a device is available, else `Error`.
+    /// Returns false if no recovery is in progress.
+    fn cancel_recovery(&self) -> bool;
+
+    /// Watch for device loss and run `recover` on it when
+    /// `audio.auto_recover` is set. Runs until the event channel closes.
+    async fn watch_device_loss(&self);
+
     /// Get the current recording duration in seconds.
     ///
     /// Returns 0.0 if not recording.
@@ -49,4 +73,31 @@ pub trait AudioManager: Send + Sync {
     ///
     /// Returns 0.0 if not recording.
     fn current_level(&self) -> f32;
+
+    /// Get the current audio input level in dBFS (`LEVEL_FLOOR_DBFS` to 0),
+    /// for level meters.
+    fn current_level_db(&self) -> f32 {
+        rms_to_dbfs(self.current_level())
+    }
+
+    /// Get the peak input level (0.0 - 1.0) over the last level window;
+    /// near 1.0 the input is clipping.
+    ///
+    /// Returns 0.0 if not recording.
+    fn current_peak(&self) -> f32;
+
+    /// Check whether the capture backend answered its last command in time.
+    ///
+    /// False means the audio thread appears wedged; commands fail fast
+    /// with an error instead of hanging.
+    fn is_responsive(&self) -> bool;
+
+    /// Capture from the selected device for `duration` on a temporary stream
+    /// and compare the frames delivered with the device's reported rate.
+    ///
+    /// Runs outside the recording state machine and fails while recording.
+    async fn measure_capture_rate(
+        &self,
+        duration: Duration,
+    ) -> Result<CaptureRateMeasurement, DomainError>;
 }
@@ -7,14 +7,25 @@ use crate::domain::{DomainError, HardwareProfile, ModelCatalog, ModelRecommendat
 pub trait HardwareDetector: Send + Sync {
     /// Detect the current system's hardware profile.
     ///
-    /// This may be cached after the first call.
+    /// This may be cached after the first call. An override set with
+    /// `set_override` takes precedence.
     fn detect(&self) -> Result<HardwareProfile, DomainError>;
 
     /// Get a model recommendation based on the hardware profile.
     fn recommend_model(&self, catalog: &ModelCatalog) -> Result<ModelRecommendation, DomainError>;
 
+    /// Get the currently available (free + reclaimable) memory in bytes.
+    ///
+    /// Unlike the profile, this is measured on every call.
+    fn available_memory(&self) -> Result<u64, DomainError>;
+
     /// Get the cached hardware profile.
     ///
-    /// Returns the result of the last `detect()` call, or detects if not yet called.
-    fn profile(&self) -> Result<&HardwareProfile, DomainError>;
+    /// Returns the override if set, else the result of the last `detect()`
+    /// call, detecting if not yet called.
+    fn profile(&self) -> Result<HardwareProfile, DomainError>;
+
+    /// Use `profile` instead of the detected one, or go back to detection
+    /// with `None`. For misdetected hardware and benchmarking.
+    fn set_override(&self, profile: Option<HardwareProfile>);
 }
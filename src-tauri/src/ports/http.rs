@@ -21,6 +21,13 @@ pub trait HttpClient: Send + Sync {
         body: &T,
     ) -> Result<R, DomainError>;
 
+    /// Perform a GET request with a bearer token and report whether the
+    /// server accepted it.
+    ///
+    /// Returns `Ok(false)` when the credentials are rejected (401/403); any
+    /// other failure is an error. The token must never be logged.
+    async fn check_bearer_token(&self, url: &str, token: &str) -> Result<bool, DomainError>;
+
     /// Download a file to a specified path.
     async fn download_file(
         &self,
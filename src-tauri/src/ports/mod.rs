@@ -4,7 +4,10 @@ pub mod hardware;
 pub mod http;
 pub mod model_manager;
 pub mod output;
+pub mod recording_store;
+pub mod stats;
 pub mod transcriber;
+pub mod transcript_sink;
 
 pub use audio::AudioManager;
 pub use config::ConfigStore;
@@ -12,4 +15,10 @@ pub use hardware::HardwareDetector;
 pub use http::HttpClient;
 pub use model_manager::ModelManager;
 pub use output::OutputManager;
-pub use transcriber::{BackendCapabilities, TranscribeConfig, Transcriber, TranscriptionResult};
+pub use recording_store::RecordingStore;
+pub use stats::{BenchmarkStore, UsageStatsStore};
+pub use transcriber::{
+    BackendCapabilities, BackendStatus, SegmentListener, TranscribeConfig, Transcriber,
+    TranscriptionResult, WordTiming,
+};
+pub use transcript_sink::TranscriptSink;
@@ -1,9 +1,10 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use async_trait::async_trait;
 
 use crate::domain::{
-    DomainError, DownloadProgress, InstalledModel, ModelCatalog, Quantization,
+    DomainError, DownloadProgress, DownloadQueueEntry, InstalledModel, ModelCatalog,
+    ModelsDirEntry, Quantization,
 };
 
 /// Port for model management operations.
@@ -27,6 +28,7 @@ pub trait ModelManager: Send + Sync {
 
     /// Download and install a model.
     ///
+    /// Downloads are queued and run one at a time; this waits for its turn.
     /// The progress callback is called periodically with download progress.
     async fn download(
         &self,
@@ -35,14 +37,50 @@ pub trait ModelManager: Send + Sync {
         progress: Option<Box<dyn Fn(DownloadProgress) + Send + Sync>>,
     ) -> Result<InstalledModel, DomainError>;
 
+    /// Install a model from a local file (e.g. one bundled with the app),
+    /// verifying its checksum against the catalog.
+    fn install_from_file(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+        source: &Path,
+    ) -> Result<InstalledModel, DomainError>;
+
+    /// Get active and queued downloads in queue order.
+    fn download_queue(&self) -> Vec<DownloadQueueEntry>;
+
+    /// Move a queued download to `position` (0 = front). The active download
+    /// stays first.
+    fn reorder_download(
+        &self,
+        model_id: &str,
+        quant: Quantization,
+        position: usize,
+    ) -> Result<(), DomainError>;
+
+    /// Remove a download that hasn't started; its `download` call fails.
+    fn remove_from_queue(&self, model_id: &str, quant: Quantization) -> Result<(), DomainError>;
+
     /// Verify the integrity of an installed model.
     ///
     /// Returns true if the model's SHA-256 checksum matches.
     fn verify(&self, model_id: &str, quant: Quantization) -> Result<bool, DomainError>;
 
+    /// Flag an installed model whose checksum no longer matches, so it is
+    /// listed as needing a re-download.
+    fn mark_verification_failed(&self, model_id: &str, quant: Quantization);
+
     /// Delete an installed model.
     fn delete(&self, model_id: &str, quant: Quantization) -> Result<(), DomainError>;
 
     /// Get the models directory path.
     fn models_dir(&self) -> PathBuf;
+
+    /// List every file in the models directory with its classification,
+    /// including ones `list_installed` ignores.
+    fn list_dir_contents(&self) -> Result<Vec<ModelsDirEntry>, DomainError>;
+
+    /// Delete a file from the models directory by name. Fails for the file
+    /// of an active download.
+    fn delete_dir_file(&self, name: &str) -> Result<(), DomainError>;
 }
@@ -1,5 +1,7 @@
+
 use async_trait::async_trait;
 
+use crate::domain::config::OutputConfig;
 use crate::domain::error::DomainError;
 
 /// Port for text output/injection.
@@ -14,5 +16,17 @@ pub trait OutputManager: Send + Sync {
     /// 1. Write the text to the clipboard
     /// 2. Wait for clipboard sync (platform-specific delay)
     /// 3. Simulate a paste command (Cmd+V on macOS, Ctrl+V on Windows/Linux)
+    ///
+    /// In `OutputMode::ClipboardOnly`, step 3 (and the sync delay) is skipped.
     async fn inject_text(&self, text: &str) -> Result<(), DomainError>;
+
+    /// Set the clipboard text without simulating paste.
+    fn copy_to_clipboard(&self, text: &str) -> Result<(), DomainError>;
+
+    /// Identifier of the frontmost application: bundle ID on macOS, window
+    /// class on X11. `None` where it can't be detected (Wayland, Windows).
+    fn frontmost_app(&self) -> Option<String>;
+
+    /// Apply an updated output configuration.
+    fn update_config(&self, config: OutputConfig);
 }
@@ -0,0 +1,16 @@
+use crate::domain::{AudioBuffer, DomainError, StoredRecording};
+
+/// Port for keeping recordings on disk, encrypted at rest.
+pub trait RecordingStore: Send + Sync {
+    /// Encrypt and persist a recording.
+    fn save(&self, audio: &AudioBuffer) -> Result<StoredRecording, DomainError>;
+
+    /// List stored recordings, newest first. Does not decrypt audio.
+    fn list(&self) -> Result<Vec<StoredRecording>, DomainError>;
+
+    /// Decrypt a stored recording into memory.
+    fn load(&self, id: &str) -> Result<AudioBuffer, DomainError>;
+
+    /// Delete a stored recording.
+    fn delete(&self, id: &str) -> Result<(), DomainError>;
+}
@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+use crate::domain::{BenchmarkCache, DomainError, UsageStats};
+
+/// Port for persisting aggregate usage statistics.
+pub trait UsageStatsStore: Send + Sync {
+    /// Load statistics from persistent storage.
+    /// Returns empty statistics if none exist.
+    fn load(&self) -> Result<UsageStats, DomainError>;
+
+    /// Save statistics to persistent storage.
+    fn save(&self, stats: &UsageStats) -> Result<(), DomainError>;
+
+    /// Get the path to the statistics file.
+    fn stats_path(&self) -> PathBuf;
+}
+
+/// Port for persisting model benchmark results.
+pub trait BenchmarkStore: Send + Sync {
+    /// Load cached results. Returns an empty cache if none exist.
+    fn load(&self) -> Result<BenchmarkCache, DomainError>;
+
+    /// Save results to persistent storage.
+    fn save(&self, cache: &BenchmarkCache) -> Result<(), DomainError>;
+}
@@ -1,12 +1,18 @@
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
-use crate::domain::{AudioBuffer, DomainError};
+use crate::domain::{AudioBuffer, DomainError, SamplingMode, TranscriptSegment};
+
+/// Receives segments of running decodes (see `Transcriber::set_segment_listener`).
+pub type SegmentListener = Arc<dyn Fn(TranscriptSegment) + Send + Sync>;
 
 /// Configuration for transcription.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TranscribeConfig {
     /// Target language (ISO 639-1 code, e.g., "en", "fr").
     /// None for auto-detection.
@@ -20,6 +26,31 @@ pub struct TranscribeConfig {
     pub vad_entropy_threshold: f32,
     /// Number of threads to use (0 = auto).
     pub threads: u32,
+    /// Drop segments whose mean token probability is below this (0.0-1.0).
+    /// 0.0 keeps every segment.
+    pub min_segment_confidence: f32,
+    /// When VAD is disabled, return an empty result without decoding if the
+    /// whole buffer is below `silence_rms_threshold`.
+    pub skip_silent_buffers: bool,
+    /// RMS level (0.0-1.0) under which a buffer counts as silent.
+    pub silence_rms_threshold: f32,
+    /// Longer text is cut at a word boundary and flagged `truncated`, which
+    /// catches runaway repetition from degenerate decodes. 0 = unlimited.
+    pub max_output_chars: usize,
+    /// Report segments to the segment listener as they are decoded.
+    pub stream_segments: bool,
+    /// Return per-word timings in `TranscriptionResult::words`. Costs extra
+    /// decoding time.
+    pub word_timestamps: bool,
+    /// Translate the speech to English instead of transcribing it in the
+    /// spoken language (see `BackendCapabilities::translation_target`).
+    pub translate: bool,
+    /// Decoding strategy; candidate counts above `MAX_SAMPLING_CANDIDATES`
+    /// are clamped.
+    pub sampling: SamplingMode,
+    /// Text the decoder treats as preceding the audio, biasing it toward the
+    /// vocabulary in it (names, jargon, acronyms). Blank means none.
+    pub initial_prompt: Option<String>,
 }
 
 impl Default for TranscribeConfig {
@@ -31,10 +62,43 @@ impl Default for TranscribeConfig {
             vad_no_speech_threshold: 0.6,
             vad_entropy_threshold: 2.4,
             threads: 0,
+            min_segment_confidence: 0.0,
+            skip_silent_buffers: false,
+            // About -40 dBFS
+            silence_rms_threshold: 0.01,
+            // Far more than 10 minutes of speech
+            max_output_chars: 50_000,
+            stream_segments: false,
+            word_timestamps: false,
+            translate: false,
+            sampling: SamplingMode::default(),
+            initial_prompt: None,
         }
     }
 }
 
+impl TranscribeConfig {
+    /// The initial prompt to use, trimmed; `None` if unset or blank.
+    pub fn prompt(&self) -> Option<&str> {
+        self.initial_prompt
+            .as_deref()
+            .map(str::trim)
+            .filter(|prompt| !prompt.is_empty())
+    }
+}
+
+/// Timing of one word, for karaoke-style highlighting.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub text: String,
+    /// Word start within the recording, in ms.
+    pub start_ms: u64,
+    /// Word end within the recording, in ms.
+    pub end_ms: u64,
+    /// Mean probability of the word's tokens (0.0-1.0).
+    pub probability: f32,
+}
+
 /// Result of a transcription.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptionResult {
@@ -44,6 +108,16 @@ pub struct TranscriptionResult {
     pub detected_language: Option<String>,
     /// Transcription duration in milliseconds.
     pub duration_ms: u64,
+    /// Whether the text was cut at `TranscribeConfig::max_output_chars`.
+    #[serde(default)]
+    pub truncated: bool,
+    /// Timed segments making up `text`, for subtitle and caption exports.
+    /// Empty for backends without timestamps.
+    #[serde(default)]
+    pub segments: Vec<TranscriptSegment>,
+    /// Word timings, with `TranscribeConfig::word_timestamps`.
+    #[serde(default)]
+    pub words: Vec<WordTiming>,
 }
 
 /// Capabilities of a transcription backend.
@@ -55,8 +129,23 @@ pub struct BackendCapabilities {
     pub streaming: bool,
     /// Whether the backend requires network access.
     pub requires_network: bool,
+    /// Language `TranscribeConfig::translate` produces (ISO 639-1 code), or
+    /// `None` if the backend can't translate.
+    #[serde(default)]
+    pub translation_target: Option<String>,
+    /// Backend name for display.
+    pub name: String,
+}
+
+/// Availability of a transcription backend, with a reason when it can't run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendStatus {
     /// Backend name for display.
     pub name: String,
+    /// Whether the backend can transcribe right now.
+    pub available: bool,
+    /// Why the backend is unavailable (e.g. no model loaded, no network, bad key).
+    pub reason: Option<String>,
 }
 
 /// Port for transcription operations.
@@ -83,6 +172,67 @@ pub trait Transcriber: Send + Sync {
     /// For network backends, this checks connectivity.
     fn is_available(&self) -> bool;
 
+    /// Check availability and explain why the backend can't run.
+    ///
+    /// Network backends should override this with a lightweight authenticated
+    /// probe sent through `PrivacyGuard`.
+    async fn check_available(&self) -> BackendStatus {
+        let available = self.is_available();
+        BackendStatus {
+            name: self.capabilities().name,
+            available,
+            reason: (!available).then(|| "Backend not available".to_string()),
+        }
+    }
+
+    /// Abort transcriptions in progress, which then fail with a
+    /// "cancelled" error. Returns how many were running.
+    ///
+    /// Backends that can't interrupt a request return 0.
+    fn cancel_running(&self) -> usize {
+        0
+    }
+
+    /// Transcribe like `transcribe`, failing with a "cancelled" error once
+    /// `cancel` is set. Other transcriptions are unaffected.
+    ///
+    /// The default only checks `cancel` before starting.
+    async fn transcribe_cancellable(
+        &self,
+        audio: &AudioBuffer,
+        config: &TranscribeConfig,
+        cancel: Arc<AtomicBool>,
+    ) -> Result<TranscriptionResult, DomainError> {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(DomainError::Transcription("Transcription cancelled".to_string()));
+        }
+        self.transcribe(audio, config).await
+    }
+
+    /// Transcribe like `transcribe`, calling `on_partial` with the text so
+    /// far as it grows; the last call carries the complete text.
+    ///
+    /// This is approximate, not real-time streaming: it runs on a finished
+    /// recording, and backends may split it into chunks whose seams are less
+    /// accurate than a single pass. The default reports the full result once.
+    async fn transcribe_streaming(
+        &self,
+        audio: &AudioBuffer,
+        config: &TranscribeConfig,
+        // Lifetime spelled out: async_trait would tie `&str` to the call
+        on_partial: &(dyn for<'t> Fn(&'t str) + Send + Sync),
+    ) -> Result<TranscriptionResult, DomainError> {
+        let result = self.transcribe(audio, config).await?;
+        on_partial(&result.text);
+        Ok(result)
+    }
+
+    /// Set where segments go for transcriptions with `stream_segments`.
+    /// Segments are reported before confidence filtering and truncation.
+    ///
+    /// Backends that only return complete results ignore this.
+    fn set_segment_listener(&self, _listener: Option<SegmentListener>) {}
+
     /// Load a model from the specified path.
     ///
     /// For network backends, this may be a no-op.
@@ -93,6 +243,13 @@ pub trait Transcriber: Send + Sync {
 
     /// Check if a model is currently loaded.
     fn is_model_loaded(&self) -> bool;
+
+    /// Whether the loaded model can transcribe languages other than English.
+    ///
+    /// `None` when no model is loaded or the backend can't tell.
+    fn is_multilingual(&self) -> Option<bool> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -107,5 +264,23 @@ mod tests {
         assert!((config.vad_no_speech_threshold - 0.6).abs() < 0.01);
         assert!((config.vad_entropy_threshold - 2.4).abs() < 0.01);
         assert_eq!(config.threads, 0);
+        assert_eq!(config.min_segment_confidence, 0.0);
+        assert!(!config.translate);
+        assert!(config.initial_prompt.is_none());
+    }
+
+    #[test]
+    fn test_prompt_blank_is_none() {
+        let config = |prompt: Option<&str>| TranscribeConfig {
+            initial_prompt: prompt.map(String::from),
+            ..Default::default()
+        };
+        assert_eq!(config(None).prompt(), None);
+        assert_eq!(config(Some("")).prompt(), None);
+        assert_eq!(config(Some("  \n\t")).prompt(), None);
+        assert_eq!(
+            config(Some(" OpenSay, Kubernetes, gRPC ")).prompt(),
+            Some("OpenSay, Kubernetes, gRPC")
+        );
     }
 }
@@ -0,0 +1,17 @@
+use std::path::Path;
+
+use crate::domain::{DomainError, TranscriptRecord};
+
+/// Port for exporting transcriptions to external tooling.
+pub trait TranscriptSink: Send + Sync {
+    /// Check that `path` can be written, creating it (and its parent
+    /// directories) if needed.
+    fn prepare(&self, path: &Path) -> Result<(), DomainError>;
+
+    /// Append a record to `path`. Each record is written and flushed as a
+    /// single line so tailing readers never see partial output.
+    fn append(&self, path: &Path, record: &TranscriptRecord) -> Result<(), DomainError>;
+
+    /// Append plain text to `path` as a single line.
+    fn append_text(&self, path: &Path, text: &str) -> Result<(), DomainError>;
+}